@@ -1,9 +1,20 @@
-use std::{fs::read_to_string, path::Path};
+mod schema;
+
+use std::{
+    fs::{create_dir_all, read_to_string},
+    path::{Path, PathBuf},
+    process::exit,
+};
 
 use clap::Parser;
 use rustyline::{DefaultEditor, error::ReadlineError};
+use schema::{Format, print_schema, read_sql};
 use tracing::{error, info};
-use truffle::{Simulator, resolve::ResolvedQuery};
+use truffle::{DialectKind, Simulator, resolve::ResolvedQuery};
+use truffle_loader::{
+    config::{cache_path, load_config_from, load_simulator},
+    migrations::{apply_migrations, load_migrations_from, migrations_hash},
+};
 
 #[derive(clap::Parser)]
 #[command(version)]
@@ -12,12 +23,57 @@ pub struct Cli {
     command: Commands,
 }
 
+/// The dialects that can be selected from the command line.
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+enum Dialect {
+    Generic,
+    Sqlite,
+    Postgres,
+}
+
+impl From<Dialect> for DialectKind {
+    fn from(dialect: Dialect) -> Self {
+        match dialect {
+            Dialect::Generic => DialectKind::Generic,
+            Dialect::Sqlite => DialectKind::Sqlite,
+            Dialect::Postgres => DialectKind::Postgres,
+        }
+    }
+}
+
 #[derive(clap::Subcommand)]
 enum Commands {
     /// Validate all of the statements in a SQL file.
-    Validate { path: String },
+    Validate {
+        path: String,
+        /// The dialect of the SQL.
+        #[arg(long, value_enum, default_value_t = Dialect::Sqlite)]
+        dialect: Dialect,
+        /// Don't print the resulting schema.
+        #[arg(long, short)]
+        quiet: bool,
+    },
     /// Run a REPL.
-    Repl,
+    Repl {
+        /// The dialect of the SQL.
+        #[arg(long, value_enum, default_value_t = Dialect::Sqlite)]
+        dialect: Dialect,
+    },
+    /// Print the tables created by a SQL file (or a directory of migrations).
+    Schema {
+        path: PathBuf,
+        #[arg(long, value_enum, default_value_t = Format::Json)]
+        format: Format,
+        /// The dialect of the SQL.
+        #[arg(long, value_enum, default_value_t = Dialect::Sqlite)]
+        dialect: Dialect,
+    },
+    /// Apply the migrations and write the schema cache that the query macros use.
+    Prepare {
+        /// The directory with the `truffle.toml`.
+        #[arg(default_value = ".")]
+        path: PathBuf,
+    },
 }
 
 fn main() {
@@ -25,18 +81,71 @@ fn main() {
     let cli = Cli::parse();
 
     match cli.command {
-        Commands::Validate { path } => {
-            let sql = read_to_string(path).unwrap();
-            let mut sim = Simulator::default();
-            if let Err(err) = sim.execute(&sql) {
-                info!("{sim:#?}");
-                error!("{err}");
-            } else {
+        Commands::Validate {
+            path,
+            dialect,
+            quiet,
+        } => {
+            let sql = match read_to_string(&path) {
+                Ok(sql) => sql,
+                Err(e) => {
+                    eprintln!("Failed to read '{path}': {e}");
+                    exit(1);
+                }
+            };
+
+            let mut sim = Simulator::with_dialect(dialect.into());
+
+            // Every statement is validated, failed ones are skipped.
+            let failures: Vec<_> = sim
+                .execute_iter_with_sql(&sql)
+                .enumerate()
+                .filter_map(|(i, (statement, result))| result.err().map(|e| (i, statement, e)))
+                .collect();
+
+            if !quiet {
                 info!("{sim:#?}");
-                info!("Valid! (syntactically and semantically)");
+            }
+
+            for (i, statement, err) in &failures {
+                eprintln!("Statement {}: {err}\n    {statement}\n", i + 1);
+            }
+
+            // A failure exits with 1 so that CI (or a pre-commit hook) can be gated on it.
+            if !failures.is_empty() {
+                eprintln!("{} invalid statements", failures.len());
+                exit(1);
+            }
+
+            info!("Valid! (syntactically and semantically)");
+        }
+        Commands::Schema {
+            path,
+            format,
+            dialect,
+        } => {
+            let mut sim = Simulator::with_dialect(dialect.into());
+            let result = read_sql(&path).and_then(|files| {
+                for (path, sql) in files {
+                    sim.execute(&sql)
+                        .map_err(|e| format!("{}: {e}", path.display()))?;
+                }
+
+                print_schema(&sim, format)
+            });
+
+            if let Err(e) = result {
+                error!("{e}");
+                exit(1);
+            }
+        }
+        Commands::Prepare { path } => {
+            if let Err(e) = prepare(&path) {
+                error!("{e}");
+                exit(1);
             }
         }
-        Commands::Repl => {
+        Commands::Repl { dialect } => {
             fn execute_sql(sim: &mut Simulator, sql: &str) -> Option<ResolvedQuery> {
                 match sim.execute(sql) {
                     Ok(resolved) => {
@@ -50,16 +159,24 @@ fn main() {
                 }
             }
 
-            let mut sim = Simulator::default();
+            let mut sim = Simulator::with_dialect(dialect.into());
             let mut rl = DefaultEditor::new().unwrap();
 
-            println!("truffle repl!");
+            // A missing history file just means this is the first session.
+            let history = history_path();
+            _ = rl.load_history(&history);
+
+            println!("truffle repl! ({dialect:?})");
             println!("type any sql expression and it will tell you if it is valid or not!");
             println!("use .help to see the help menu.");
             loop {
                 let readline = rl.readline("truffle >> ");
                 match readline {
                     Ok(line) => {
+                        if !line.trim().is_empty() {
+                            _ = rl.add_history_entry(line.as_str());
+                        }
+
                         if line.starts_with('.') {
                             let mut pieces = line.split_terminator(' ');
                             match pieces.next().unwrap() {
@@ -68,6 +185,10 @@ fn main() {
                                     println!("    .table <TABLE> -> prints table info");
                                     println!("    .constraints <TABLE> -> prints constraints");
                                     println!("    .import <PATH> -> executes file at the path");
+                                    println!(
+                                        "    .describe <SQL> -> prints the inputs and outputs of the query"
+                                    );
+                                    println!("    .clear -> clears the history");
                                     println!("    .exit -> exit (can also ctrl+c)");
                                 }
                                 ".tables" => {
@@ -127,6 +248,19 @@ fn main() {
                                         println!("invalid path for importing");
                                     }
                                 }
+                                ".describe" => {
+                                    let sql = line.trim_start_matches(".describe").trim();
+
+                                    if sql.is_empty() {
+                                        println!("invalid sql for describing");
+                                    } else {
+                                        describe(&sim, sql);
+                                    }
+                                }
+                                ".clear" => {
+                                    _ = rl.clear_history();
+                                    println!("cleared history");
+                                }
                                 ".exit" => {
                                     break;
                                 }
@@ -156,6 +290,120 @@ fn main() {
                     }
                 }
             }
+
+            if let Err(e) = rl.save_history(&history) {
+                println!("failed to save history: {e}");
+            }
+        }
+    }
+}
+
+/// Prints the resolved inputs and outputs of the SQL as tables, without changing the Simulator.
+fn describe(sim: &Simulator, sql: &str) {
+    let resolved = match sim.clone().execute(sql) {
+        Ok(resolved) => resolved,
+        Err(e) => {
+            println!("❌ {e}");
+            return;
+        }
+    };
+
+    let nullable = |nullable: bool| if nullable { "yes" } else { "no" };
+
+    let inputs: Vec<[String; 3]> = resolved
+        .placeholders
+        .iter()
+        .zip(&resolved.inputs)
+        .map(|(placeholder, column)| {
+            [
+                placeholder.to_string(),
+                column.ty.to_string(),
+                nullable(column.nullable).to_string(),
+            ]
+        })
+        .collect();
+
+    let outputs: Vec<[String; 3]> = resolved
+        .outputs
+        .iter()
+        .map(|(key, column)| {
+            [
+                key.to_string(),
+                column.ty.to_string(),
+                nullable(column.nullable).to_string(),
+            ]
+        })
+        .collect();
+
+    print_rows("input", &inputs);
+    println!();
+    print_rows("output", &outputs);
+}
+
+/// Prints rows of (name, type, nullable) as an aligned table.
+fn print_rows(name: &str, rows: &[[String; 3]]) {
+    let header = [name.to_string(), "type".to_string(), "nullable".to_string()];
+
+    let mut widths = [0; 3];
+    for row in std::iter::once(&header).chain(rows) {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
         }
     }
+
+    let print_row = |row: &[String; 3]| {
+        println!(
+            "  {:<w0$}  {:<w1$}  {}",
+            row[0],
+            row[1],
+            row[2],
+            w0 = widths[0],
+            w1 = widths[1]
+        );
+    };
+
+    print_row(&header);
+    if rows.is_empty() {
+        println!("  (none)");
+    }
+
+    for row in rows {
+        print_row(row);
+    }
+}
+
+/// Where the REPL keeps its history between sessions, eg. `~/.truffle_history`.
+fn history_path() -> PathBuf {
+    std::env::var_os("HOME")
+        .map(PathBuf::from)
+        .unwrap_or_default()
+        .join(".truffle_history")
+}
+
+/// Builds the schema from the migrations of the project and writes it to the cache.
+fn prepare(dir: &Path) -> Result<(), String> {
+    let config = load_config_from(dir)?;
+    let mut sim = load_simulator(&config)?;
+
+    let migrations = load_migrations_from(dir, &config)?;
+    apply_migrations(&mut sim, &migrations)?;
+
+    let path = cache_path(dir, &config);
+    if let Some(parent) = path.parent() {
+        create_dir_all(parent).map_err(|e| format!("Failed to create '{parent:?}': {e}"))?;
+    }
+
+    sim.to_cache_with_hash(&path, &migrations_hash(&migrations))
+        .map_err(|e| e.to_string())?;
+
+    let tables = sim.get_tables();
+    let columns: usize = tables.values().map(|table| table.columns.len()).sum();
+
+    println!(
+        "Captured {} tables and {columns} columns from {} migrations into {path:?}",
+        tables.len(),
+        migrations.len()
+    );
+
+    Ok(())
 }
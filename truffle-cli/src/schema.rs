@@ -0,0 +1,216 @@
+use std::{
+    fs::read_to_string,
+    path::{Path, PathBuf},
+};
+
+use itertools::Itertools;
+use serde::Serialize;
+use truffle::{Constraint, DialectKind, OnAction, Simulator, Table, ty::SqlType};
+
+/// How `truffle schema` prints the schema.
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+pub enum Format {
+    Json,
+    Sql,
+}
+
+#[derive(Serialize)]
+struct SchemaOutput {
+    dialect: DialectKind,
+    tables: Vec<TableOutput>,
+}
+
+#[derive(Serialize)]
+struct TableOutput {
+    name: String,
+    comment: Option<String>,
+    columns: Vec<ColumnOutput>,
+    constraints: Vec<ConstraintOutput>,
+}
+
+#[derive(Serialize)]
+struct ColumnOutput {
+    name: String,
+    #[serde(rename = "type")]
+    ty: String,
+    nullable: bool,
+    default: bool,
+    comment: Option<String>,
+}
+
+#[derive(Serialize)]
+struct ConstraintOutput {
+    kind: &'static str,
+    columns: Vec<String>,
+    /// Only set for foreign keys.
+    references: Option<ReferenceOutput>,
+}
+
+#[derive(Serialize)]
+struct ReferenceOutput {
+    table: String,
+    columns: Vec<String>,
+    on_delete: &'static str,
+    on_update: &'static str,
+}
+
+/// The SQL files at the path, a single file or a directory of migrations in order.
+pub fn read_sql(path: &Path) -> Result<Vec<(PathBuf, String)>, String> {
+    let paths = if path.is_dir() {
+        path.read_dir()
+            .map_err(|e| format!("Failed to read directory '{path:?}': {e}"))?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "sql"))
+            .sorted()
+            .collect()
+    } else {
+        vec![path.to_path_buf()]
+    };
+
+    paths
+        .into_iter()
+        .map(|path| {
+            read_to_string(&path)
+                .map(|sql| (path.clone(), sql))
+                .map_err(|e| format!("Failed to read '{path:?}': {e}"))
+        })
+        .collect()
+}
+
+/// Prints every table of the Simulator in the format.
+pub fn print_schema(sim: &Simulator, format: Format) -> Result<(), String> {
+    let tables: Vec<TableOutput> = sim
+        .get_tables()
+        .iter()
+        .sorted_by(|a, b| a.0.cmp(b.0))
+        .map(|(name, table)| table_output(name, table))
+        .collect();
+
+    match format {
+        Format::Json => {
+            let schema = SchemaOutput {
+                dialect: sim.dialect.kind(),
+                tables,
+            };
+
+            let json = serde_json::to_string_pretty(&schema).map_err(|e| e.to_string())?;
+            println!("{json}");
+        }
+        Format::Sql => {
+            for table in tables {
+                println!("{}\n", create_table_sql(&table));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn table_output(name: &str, table: &Table) -> TableOutput {
+    let columns = table
+        .columns
+        .iter()
+        .map(|(name, column)| ColumnOutput {
+            name: name.clone(),
+            // Enum columns refer to their type by name, like in CREATE TABLE.
+            ty: match &column.ty {
+                SqlType::Enum { name, .. } => name.clone(),
+                ty => ty.to_string(),
+            },
+            nullable: column.nullable,
+            default: column.default,
+            comment: table.column_comments.get(name).cloned(),
+        })
+        .collect();
+
+    // Constraints are stored in hash maps, so they are sorted to keep the output stable.
+    let constraints = table
+        .get_all_constraints()
+        .iter()
+        .flat_map(|(key, constraints)| {
+            constraints
+                .iter()
+                .map(|constraint| constraint_output(&Table::compound_key_columns(key), constraint))
+        })
+        .sorted_by(|a, b| (a.kind, &a.columns).cmp(&(b.kind, &b.columns)))
+        .collect();
+
+    TableOutput {
+        name: name.to_string(),
+        comment: table.comment.clone(),
+        columns,
+        constraints,
+    }
+}
+
+fn constraint_output(columns: &[String], constraint: &Constraint) -> ConstraintOutput {
+    let (kind, references) = match constraint {
+        Constraint::PrimaryKey => ("primary_key", None),
+        Constraint::Unique => ("unique", None),
+        Constraint::Index => ("index", None),
+        Constraint::ForeignKey {
+            foreign_table,
+            foreign_columns,
+            on_delete,
+            on_update,
+        } => (
+            "foreign_key",
+            Some(ReferenceOutput {
+                table: foreign_table.clone(),
+                columns: foreign_columns.clone(),
+                on_delete: on_action_sql(on_delete),
+                on_update: on_action_sql(on_update),
+            }),
+        ),
+    };
+
+    ConstraintOutput {
+        kind,
+        columns: columns.to_vec(),
+        references,
+    }
+}
+
+fn on_action_sql(action: &OnAction) -> &'static str {
+    match action {
+        OnAction::Nothing => "no action",
+        OnAction::Restrict => "restrict",
+        OnAction::Cascade => "cascade",
+        OnAction::SetNull => "set null",
+        OnAction::SetDefault => "set default",
+    }
+}
+
+/// A normalized CREATE TABLE, with types written by their canonical names.
+///
+/// Defaults are only known to exist, so they can't be written back.
+fn create_table_sql(table: &TableOutput) -> String {
+    let columns = table.columns.iter().map(|column| {
+        let not_null = if column.nullable { "" } else { " not null" };
+        format!("{} {}{not_null}", column.name, column.ty)
+    });
+
+    let constraints = table.constraints.iter().filter_map(|constraint| {
+        let columns = constraint.columns.join(", ");
+
+        match (constraint.kind, &constraint.references) {
+            ("primary_key", _) => Some(format!("primary key ({columns})")),
+            ("unique", _) => Some(format!("unique ({columns})")),
+            ("foreign_key", Some(references)) => Some(format!(
+                "foreign key ({columns}) references {} ({}) on delete {} on update {}",
+                references.table,
+                references.columns.join(", "),
+                references.on_delete,
+                references.on_update
+            )),
+            // Indexes aren't part of CREATE TABLE.
+            _ => None,
+        }
+    });
+
+    format!(
+        "create table {} (\n    {}\n);",
+        table.name,
+        columns.chain(constraints).join(",\n    ")
+    )
+}
@@ -0,0 +1,231 @@
+use std::ops::ControlFlow;
+
+use sqlparser::ast::{
+    AlterTableOperation, Expr, ObjectName, visit_expressions, visit_expressions_mut,
+};
+use tracing::debug;
+
+use crate::{DialectKind, Error, Simulator, resolve::ResolvedQuery, table::Constraint, table_name};
+
+impl Simulator {
+    pub(crate) fn alter_table(
+        &mut self,
+        name: &ObjectName,
+        if_exists: bool,
+        operations: Vec<AlterTableOperation>,
+    ) -> Result<ResolvedQuery, Error> {
+        let old_key = match self.resolve_table_key(name) {
+            Ok(key) => key,
+            Err(Error::TableDoesntExist(_)) if if_exists => return Ok(ResolvedQuery::default()),
+            Err(e) => return Err(e),
+        };
+
+        let mut resolved = ResolvedQuery::default();
+
+        // Operations are applied to a copy, so a failing one leaves the schema untouched.
+        let mut table = self.tables[&old_key].clone();
+        let mut key = old_key.clone();
+        let mut qualifier = table_name(name);
+        let mut renamed_columns = vec![];
+
+        for operation in operations {
+            match operation {
+                AlterTableOperation::AddColumn {
+                    if_not_exists,
+                    column_def,
+                    ..
+                } => {
+                    // Ensure that this column doesn't already exist.
+                    if table.has_column(&column_def.name.value) {
+                        if if_not_exists {
+                            continue;
+                        }
+
+                        return Err(Error::ColumnAlreadyExists(column_def.name.value));
+                    }
+
+                    self.create_column(&mut table, column_def, &mut resolved)?;
+                    self.validate_deferred(&qualifier, &table, &mut resolved)?;
+                }
+                AlterTableOperation::DropColumn {
+                    column_name,
+                    if_exists,
+                    ..
+                } => {
+                    let column_name = &column_name.value;
+
+                    if !table.has_column(column_name) {
+                        if if_exists {
+                            continue;
+                        }
+
+                        return Err(Error::ColumnDoesntExist(column_name.to_string()));
+                    }
+
+                    // Ensure that no foreign key references this column.
+                    let referenced = self
+                        .tables
+                        .iter()
+                        .filter(|(k, _)| **k != old_key)
+                        .map(|(_, t)| t)
+                        .chain([&table])
+                        .flat_map(|t| t.constraints.values().flatten())
+                        .any(|c| match c {
+                            Constraint::ForeignKey {
+                                foreign_table,
+                                foreign_columns,
+                                ..
+                            } => *foreign_table == old_key && foreign_columns.contains(column_name),
+                            _ => false,
+                        });
+
+                    if referenced {
+                        return Err(Error::ForeignKeyConstraint(column_name.to_string()));
+                    }
+
+                    // Generated columns that use this column have to be dropped first.
+                    if table.generated.iter().any(|(generated, expr)| {
+                        generated != column_name && Self::references_column(expr, column_name)
+                    }) {
+                        return Err(Error::ColumnInUse(column_name.to_string()));
+                    }
+
+                    // Postgres drops the CHECK constraints that use the column, SQLite refuses.
+                    if table
+                        .checks
+                        .iter()
+                        .any(|expr| Self::references_column(expr, column_name))
+                    {
+                        if self.dialect.kind() == DialectKind::Sqlite {
+                            return Err(Error::ColumnInUse(column_name.to_string()));
+                        }
+
+                        table
+                            .checks
+                            .retain(|expr| !Self::references_column(expr, column_name));
+                    }
+
+                    table.remove_column(column_name);
+                    self.validate_deferred(&qualifier, &table, &mut resolved)?;
+                }
+                AlterTableOperation::RenameColumn {
+                    old_column_name,
+                    new_column_name,
+                } => {
+                    let (old, new) = (old_column_name.value, new_column_name.value);
+
+                    if !table.has_column(&old) {
+                        return Err(Error::ColumnDoesntExist(old));
+                    }
+
+                    if table.has_column(&new) {
+                        return Err(Error::ColumnAlreadyExists(new));
+                    }
+
+                    table.rename_column(&old, &new);
+
+                    // CHECK and generation expressions follow the renamed column.
+                    for expr in table.checks.iter_mut().chain(table.generated.values_mut()) {
+                        Self::rename_column_in_expr(expr, &old, &new);
+                    }
+                    renamed_columns.push((old, new));
+                }
+                AlterTableOperation::RenameTable {
+                    table_name: new_name,
+                } => {
+                    // An unqualified name keeps the table in its schema.
+                    let new_key = match key.rsplit_once('.') {
+                        Some((schema, _)) if new_name.0.len() == 1 => {
+                            format!("{schema}.{}", table_name(&new_name))
+                        }
+                        _ => self.table_key(&new_name)?,
+                    };
+
+                    if self.has_table(&new_key) {
+                        return Err(Error::TableAlreadyExists(new_key));
+                    }
+
+                    key = new_key;
+                    qualifier = table_name(&new_name);
+                }
+                _ => {
+                    return Err(Error::Unsupported(format!(
+                        "Unsupported operation in ALTER TABLE: {operation}"
+                    )));
+                }
+            }
+        }
+
+        debug!(name = %key, "Altering Table");
+        let tables = self.get_tables_mut();
+        tables.remove(&old_key);
+        tables.insert(key.clone(), table);
+
+        // Foreign keys follow the renamed table and columns.
+        for constraints in tables.values_mut().flat_map(|t| t.constraints.values_mut()) {
+            *constraints = std::mem::take(constraints)
+                .into_iter()
+                .map(|c| match c {
+                    Constraint::ForeignKey {
+                        foreign_table,
+                        mut foreign_columns,
+                        on_delete,
+                        on_update,
+                    } if foreign_table == old_key => {
+                        for (old, new) in &renamed_columns {
+                            for column in foreign_columns.iter_mut().filter(|c| *c == old) {
+                                *column = new.clone();
+                            }
+                        }
+
+                        Constraint::ForeignKey {
+                            foreign_table: key.clone(),
+                            foreign_columns,
+                            on_delete,
+                            on_update,
+                        }
+                    }
+                    c => c,
+                })
+                .collect();
+        }
+
+        Ok(resolved)
+    }
+
+    /// Whether an expression references a column.
+    fn references_column(expr: &Expr, column: &str) -> bool {
+        visit_expressions(expr, |expr| {
+            let ident = match expr {
+                Expr::Identifier(ident) => Some(ident),
+                Expr::CompoundIdentifier(idents) => idents.last(),
+                _ => None,
+            };
+
+            match ident {
+                Some(ident) if ident.value == column => ControlFlow::Break(()),
+                _ => ControlFlow::Continue(()),
+            }
+        })
+        .is_break()
+    }
+
+    /// Rewrites the references to a column within an expression.
+    fn rename_column_in_expr(expr: &mut Expr, old: &str, new: &str) {
+        let _ = visit_expressions_mut(expr, |expr| {
+            let ident = match expr {
+                Expr::Identifier(ident) => Some(ident),
+                Expr::CompoundIdentifier(idents) => idents.last_mut(),
+                _ => None,
+            };
+
+            if let Some(ident) = ident
+                && ident.value == old
+            {
+                ident.value = new.to_string();
+            }
+
+            ControlFlow::<()>::Continue(())
+        });
+    }
+}
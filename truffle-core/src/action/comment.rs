@@ -0,0 +1,70 @@
+use sqlparser::ast::{CommentObject, ObjectName};
+use tracing::{debug, warn};
+
+use crate::{Error, Simulator, object_name_to_strings, resolve::ResolvedQuery};
+
+impl Simulator {
+    pub(crate) fn comment(
+        &mut self,
+        object_type: &CommentObject,
+        object_name: &ObjectName,
+        comment: Option<String>,
+        if_exists: bool,
+    ) -> Result<ResolvedQuery, Error> {
+        let names = object_name_to_strings(object_name);
+
+        let result = match object_type {
+            CommentObject::Table => self.resolve_table_key(object_name).map(|key| {
+                // `IS NULL` removes the comment.
+                self.get_tables_mut().get_mut(&key).unwrap().comment = comment;
+            }),
+            CommentObject::Column => {
+                // The column is always qualified by its table, eg. `account.email`.
+                let Some((_, table_name)) =
+                    object_name.0.split_last().filter(|(_, t)| !t.is_empty())
+                else {
+                    return Err(Error::Sql(format!(
+                        "COMMENT ON COLUMN requires a table qualifier: {object_name}"
+                    )));
+                };
+
+                let column_name = names.last().unwrap();
+
+                self.resolve_table_key(&ObjectName(table_name.to_vec()))
+                    .and_then(|key| {
+                        let table = self.get_tables_mut().get_mut(&key).unwrap();
+
+                        if !table.has_column(column_name) {
+                            return Err(Error::ColumnDoesntExist(column_name.to_string()));
+                        }
+
+                        match comment {
+                            Some(comment) => {
+                                table
+                                    .column_comments
+                                    .insert(column_name.to_string(), comment);
+                            }
+                            None => {
+                                table.column_comments.remove(column_name);
+                            }
+                        }
+
+                        Ok(())
+                    })
+            }
+            _ => {
+                warn!(object = %object_type, "Unsupported Comment");
+                Ok(())
+            }
+        };
+
+        match result {
+            Ok(()) => debug!(object = %object_type, name = %object_name, "Commenting"),
+            // IF EXISTS only covers the table, a missing column is still an error.
+            Err(Error::TableDoesntExist(_)) if if_exists => {}
+            Err(e) => return Err(e),
+        }
+
+        Ok(ResolvedQuery::default())
+    }
+}
@@ -1,14 +1,23 @@
-use sqlparser::ast::{ColumnOption, CreateTable, ReferentialAction, TableConstraint};
-use tracing::debug;
+use sqlparser::{
+    ast::{
+        ColumnDef, ColumnOption, CreateTable, DataType, Expr, ReferentialAction, TableConstraint,
+        Value,
+    },
+    keywords::Keyword,
+    tokenizer::Token,
+};
+use tracing::{debug, warn};
 
 use crate::{
     Error, Simulator,
     column::Column,
+    dialect::DialectKind,
     expr::{ColumnInferrer, InferConstraints, InferContext},
     object_name_to_strings,
     resolve::ResolvedQuery,
     table::{Constraint, Table},
-    ty::SqlType,
+    table_name,
+    ty::{SqlType, is_serial},
 };
 
 impl Simulator {
@@ -16,7 +25,16 @@ impl Simulator {
         &mut self,
         create_table: CreateTable,
     ) -> Result<ResolvedQuery, Error> {
-        let name = &object_name_to_strings(&create_table.name)[0];
+        let name = &self.table_key(&create_table.name)?;
+        let qualifier = table_name(&create_table.name);
+
+        // Tables can only be created in a schema that exists.
+        if let [schema, _] = object_name_to_strings(&create_table.name).as_slice()
+            && schema != &self.default_schema
+            && !self.has_schema(schema)
+        {
+            return Err(Error::SchemaDoesntExist(schema.clone()));
+        }
 
         // Ensure that this table doesn't already exist.
         if !create_table.if_not_exists && self.tables.contains_key(name) {
@@ -26,135 +44,9 @@ impl Simulator {
         let mut resolved = ResolvedQuery::default();
 
         let mut table = Table::default();
-        for column in create_table.columns {
-            let column_name = &column.name.value;
-            let mut nullable = true;
-            let mut default = false;
-            let ty: SqlType = column.data_type.into();
-
-            // Handle options/constraints on a column level.
-            for option in column.options {
-                match option.option {
-                    ColumnOption::Null => {
-                        nullable = true;
-                    }
-                    ColumnOption::NotNull => {
-                        nullable = false;
-                    }
-                    ColumnOption::Default(expr) => {
-                        let inferrer = CreateTableInferrer::default();
-                        self.infer_expr_column(
-                            &expr,
-                            InferContext {
-                                constraints: InferConstraints {
-                                    ty: Some(ty.clone()),
-                                    ..Default::default()
-                                },
-                                ..Default::default()
-                            },
-                            &inferrer,
-                            &mut resolved,
-                        )?;
-
-                        default = true;
-                    }
-                    ColumnOption::Unique { is_primary, .. } => {
-                        table.insert_constraint(&[column_name], Constraint::Unique);
-                        if is_primary {
-                            nullable = false;
-                            table.insert_constraint(&[column_name], Constraint::PrimaryKey);
-                        }
-                    }
-                    ColumnOption::ForeignKey {
-                        foreign_table,
-                        referred_columns,
-                        on_delete,
-                        on_update,
-                        ..
-                    } => {
-                        let foreign_table_name = object_name_to_strings(&foreign_table)
-                            .first()
-                            .unwrap()
-                            .to_string();
-
-                        // Verify that foreign table exists.
-                        let f_table = self.get_table(&foreign_table_name).ok_or_else(|| {
-                            Error::TableDoesntExist(foreign_table_name.to_string())
-                        })?;
-
-                        if referred_columns.len() > 1 {
-                            return Err(Error::Sql(
-                                "Cannot have more than 1 foreign column".to_string(),
-                            ));
-                        }
-
-                        let mut foreign_columns = vec![];
-
-                        if let Some(foreign_column) = referred_columns.first() {
-                            let foreign_column_name = &foreign_column.value;
-
-                            // Verify that foreign column exists.
-                            let f_column =
-                                f_table.get_column(foreign_column_name).ok_or_else(|| {
-                                    Error::ColumnDoesntExist(foreign_column_name.to_string())
-                                })?;
-
-                            // Verify that the foreign column is UNIQUE.
-                            if !f_table.is_unique(&[foreign_column_name]) {
-                                return Err(Error::ForeignKeyConstraint(
-                                    foreign_column_name.to_string(),
-                                ));
-                            }
-
-                            // Verify that they are of the same type.
-                            if ty != f_column.ty {
-                                return Err(Error::TypeMismatch {
-                                    expected: f_column.ty.clone(),
-                                    got: ty,
-                                });
-                            }
-
-                            if let Some(on_delete) = on_delete {
-                                validate_on_action(&on_delete, column_name, nullable, default)?;
-                            }
-
-                            if let Some(on_update) = on_update {
-                                validate_on_action(&on_update, column_name, nullable, default)?;
-                            }
-
-                            foreign_columns.push(foreign_column_name.to_string());
-                        }
 
-                        table.insert_constraint(
-                            &[column_name],
-                            Constraint::ForeignKey {
-                                foreign_table: foreign_table_name,
-                                foreign_columns,
-                                on_delete: on_delete.map(|od| od.into()).unwrap_or_default(),
-                                on_update: on_update.map(|ou| ou.into()).unwrap_or_default(),
-                            },
-                        );
-                    }
-                    _ => {
-                        return Err(Error::Unsupported(format!(
-                            "Unsupported option in CREATE TABLE: {option:#?}"
-                        )));
-                    }
-                }
-            }
-
-            let col = Column {
-                ty,
-                nullable,
-                default,
-            };
-
-            // Ensure that this column doen't already exist.
-            if table.columns.contains_key(column_name) {
-                return Err(Error::ColumnAlreadyExists(column_name.to_string()));
-            }
-
-            table.columns.insert(column_name.to_string(), col);
+        for column in create_table.columns {
+            self.create_column(&mut table, column, &mut resolved)?;
         }
 
         // Handle table level constraints.
@@ -202,14 +94,8 @@ impl Simulator {
                 } => {
                     // TODO: Properly support foreign key names.
 
-                    let foreign_table_name = object_name_to_strings(&foreign_table)
-                        .first()
-                        .unwrap()
-                        .to_string();
-
-                    let f_table = self
-                        .get_table(&foreign_table_name)
-                        .ok_or_else(|| Error::TableDoesntExist(foreign_table_name.clone()))?;
+                    let foreign_table_name = self.resolve_table_key(&foreign_table)?;
+                    let f_table = &self.tables[&foreign_table_name];
 
                     let local_column_names: Vec<String> =
                         columns.iter().map(|c| c.value.to_string()).collect();
@@ -274,40 +160,320 @@ impl Simulator {
                         },
                     );
                 }
+                TableConstraint::Check { expr, .. } => {
+                    table.checks.push(*expr);
+                }
                 _ => {
                     return Err(Error::Unsupported(format!(
-                        "Unsupported table constraint on CREATE TABLE: {constraint:#?}"
+                        "Unsupported table constraint on CREATE TABLE: {constraint}"
                     )));
                 }
             }
         }
 
+        self.validate_deferred(&qualifier, &table, &mut resolved)?;
+
         debug!(name = %name, "Creating Table");
-        self.tables.insert(name.to_string(), table);
+        self.get_tables_mut().insert(name.to_string(), table);
 
         Ok(ResolvedQuery::default())
     }
+
+    /// Adds a column and its column level constraints to the table.
+    ///
+    /// CHECK and generation expressions can reference any column,
+    /// so they are kept on the table and validated once it is built.
+    pub(crate) fn create_column(
+        &self,
+        table: &mut Table,
+        column: ColumnDef,
+        resolved: &mut ResolvedQuery,
+    ) -> Result<(), Error> {
+        let column_name = &column.name.value;
+        let mut nullable = true;
+        let mut default = false;
+        let mut primary = false;
+        let mut autoincrement = false;
+        let ty = self.sql_type(&column.data_type);
+
+        // Network address types only exist in Postgres.
+        if ty.is_network() && self.dialect.kind() != DialectKind::Postgres {
+            return Err(Error::InvalidType(ty.to_string()));
+        }
+
+        // SERIAL columns are backed by a sequence in Postgres.
+        let serial = self.dialect.kind() != DialectKind::Sqlite
+            && matches!(&column.data_type, DataType::Custom(name, _) if is_serial(name));
+
+        // Handle options/constraints on a column level.
+        for option in column.options {
+            match option.option {
+                ColumnOption::Null => {
+                    nullable = true;
+                }
+                ColumnOption::NotNull => {
+                    nullable = false;
+                }
+                ColumnOption::Default(expr) => {
+                    let inferrer = CreateTableInferrer::default();
+                    self.infer_expr_column(
+                        &expr,
+                        InferContext {
+                            constraints: InferConstraints {
+                                ty: Some(ty.clone()),
+                                ..Default::default()
+                            },
+                            ..Default::default()
+                        },
+                        &inferrer,
+                        resolved,
+                    )?;
+
+                    if let SqlType::Varchar { len } | SqlType::Char { len } = ty
+                        && let Expr::Value(value) = &expr
+                        && let Value::SingleQuotedString(value) = &value.value
+                        && value.chars().count() as u64 > len
+                    {
+                        warn!(
+                            column = %column_name,
+                            "DEFAULT is longer than the column's length of {len}"
+                        );
+                    }
+
+                    default = true;
+                }
+                ColumnOption::Unique { is_primary, .. } => {
+                    table.insert_constraint(&[column_name], Constraint::Unique);
+                    if is_primary {
+                        nullable = false;
+                        primary = true;
+                        table.insert_constraint(&[column_name], Constraint::PrimaryKey);
+                    }
+                }
+                ColumnOption::ForeignKey {
+                    foreign_table,
+                    referred_columns,
+                    on_delete,
+                    on_update,
+                    ..
+                } => {
+                    // Verify that foreign table exists.
+                    let foreign_table_name = self.resolve_table_key(&foreign_table)?;
+                    let f_table = &self.tables[&foreign_table_name];
+
+                    if referred_columns.len() > 1 {
+                        return Err(Error::Sql(
+                            "Cannot have more than 1 foreign column".to_string(),
+                        ));
+                    }
+
+                    let mut foreign_columns = vec![];
+
+                    if let Some(foreign_column) = referred_columns.first() {
+                        let foreign_column_name = &foreign_column.value;
+
+                        // Verify that foreign column exists.
+                        let f_column =
+                            f_table.get_column(foreign_column_name).ok_or_else(|| {
+                                Error::ColumnDoesntExist(foreign_column_name.to_string())
+                            })?;
+
+                        // Verify that the foreign column is UNIQUE.
+                        if !f_table.is_unique(&[foreign_column_name]) {
+                            return Err(Error::ForeignKeyConstraint(
+                                foreign_column_name.to_string(),
+                            ));
+                        }
+
+                        // Verify that they are of the same type.
+                        if ty != f_column.ty {
+                            return Err(Error::TypeMismatch {
+                                expected: f_column.ty.clone(),
+                                got: ty,
+                            });
+                        }
+
+                        if let Some(on_delete) = on_delete {
+                            validate_on_action(&on_delete, column_name, nullable, default)?;
+                        }
+
+                        if let Some(on_update) = on_update {
+                            validate_on_action(&on_update, column_name, nullable, default)?;
+                        }
+
+                        foreign_columns.push(foreign_column_name.to_string());
+                    }
+
+                    table.insert_constraint(
+                        &[column_name],
+                        Constraint::ForeignKey {
+                            foreign_table: foreign_table_name,
+                            foreign_columns,
+                            on_delete: on_delete.map(|od| od.into()).unwrap_or_default(),
+                            on_update: on_update.map(|ou| ou.into()).unwrap_or_default(),
+                        },
+                    );
+                }
+                ColumnOption::Check(expr) => {
+                    table.checks.push(expr);
+                }
+                ColumnOption::Generated {
+                    generation_expr, ..
+                } => {
+                    // Generated columns are never written to directly.
+                    if let Some(expr) = generation_expr {
+                        table.generated.insert(column_name.to_string(), expr);
+                    }
+
+                    default = true;
+                }
+                ColumnOption::DialectSpecific(ref tokens)
+                    if matches!(
+                        tokens.as_slice(),
+                        [Token::Word(word)] if word.keyword == Keyword::AUTOINCREMENT
+                    ) =>
+                {
+                    autoincrement = true;
+                }
+                _ => {
+                    return Err(Error::Unsupported(format!(
+                        "Unsupported option in CREATE TABLE: {option}"
+                    )));
+                }
+            }
+        }
+
+        if autoincrement {
+            if !primary || ty != SqlType::Integer {
+                return Err(Error::Sql(format!(
+                    "AUTOINCREMENT is only allowed on an INTEGER PRIMARY KEY: {column_name}"
+                )));
+            }
+
+            default = true;
+        }
+
+        if serial {
+            nullable = false;
+            default = true;
+        }
+
+        let col = Column {
+            ty,
+            nullable,
+            default,
+        };
+
+        // Ensure that this column doen't already exist.
+        if table.columns.contains_key(column_name) {
+            return Err(Error::ColumnAlreadyExists(column_name.to_string()));
+        }
+
+        table.columns.insert(column_name.to_string(), col);
+
+        Ok(())
+    }
+
+    /// Validates the CHECK and generation expressions of the table against its columns.
+    pub(crate) fn validate_deferred(
+        &self,
+        qualifier: &str,
+        table: &Table,
+        resolved: &mut ResolvedQuery,
+    ) -> Result<(), Error> {
+        let inferrer = CreateTableInferrer {
+            table: Some((qualifier, table)),
+        };
+
+        for check in &table.checks {
+            let infer = self.infer_expr_column(
+                check,
+                InferContext {
+                    constraints: InferConstraints {
+                        ty: Some(SqlType::Boolean),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                &inferrer,
+                resolved,
+            )?;
+
+            if infer.column.ty != SqlType::Boolean {
+                return Err(Error::TypeMismatch {
+                    expected: SqlType::Boolean,
+                    got: infer.column.ty,
+                });
+            }
+        }
+
+        for (name, expr) in &table.generated {
+            let ty = table.columns[name].ty.clone();
+            let infer = self.infer_expr_column(
+                expr,
+                InferContext {
+                    constraints: InferConstraints {
+                        ty: Some(ty.clone()),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                &inferrer,
+                resolved,
+            )?;
+
+            if infer.column.ty != ty {
+                return Err(Error::TypeMismatch {
+                    expected: ty,
+                    got: infer.column.ty,
+                });
+            }
+        }
+
+        Ok(())
+    }
 }
 
+/// Resolves columns inside of CREATE TABLE.
+///
+/// DEFAULT expressions can't reference columns, while CHECK and generation
+/// expressions can reference the columns of the table being created.
 #[derive(Default)]
-struct CreateTableInferrer {}
+struct CreateTableInferrer<'a> {
+    table: Option<(&'a str, &'a Table)>,
+}
 
-impl ColumnInferrer for CreateTableInferrer {
+impl<'a> ColumnInferrer for CreateTableInferrer<'a> {
     fn infer_unqualified_column(
         &self,
         _: &Simulator,
         column: &str,
     ) -> Result<Option<Column>, Error> {
-        Err(Error::InvalidDefault(column.to_string()))
+        let Some((_, table)) = self.table else {
+            return Err(Error::InvalidDefault(column.to_string()));
+        };
+
+        Ok(table.get_column(column).cloned())
     }
 
     fn infer_qualified_column(
         &self,
         _: &Simulator,
-        _: &str,
+        qualifier: &str,
         column: &str,
     ) -> Result<Column, Error> {
-        Err(Error::InvalidDefault(column.to_string()))
+        let Some((name, table)) = self.table else {
+            return Err(Error::InvalidDefault(column.to_string()));
+        };
+
+        table
+            .get_column(column)
+            .filter(|_| qualifier == name)
+            .cloned()
+            .ok_or_else(|| Error::QualifiedColumnDoesntExist {
+                qualifier: qualifier.to_string(),
+                column: column.to_string(),
+            })
     }
 }
 
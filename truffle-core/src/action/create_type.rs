@@ -0,0 +1,43 @@
+use sqlparser::ast::{ObjectName, UserDefinedTypeRepresentation};
+use tracing::debug;
+
+use crate::{Error, Simulator, object_name_to_strings, resolve::ResolvedQuery, ty::SqlType};
+
+impl Simulator {
+    pub(crate) fn create_type(
+        &mut self,
+        name: &ObjectName,
+        representation: UserDefinedTypeRepresentation,
+    ) -> Result<ResolvedQuery, Error> {
+        // Types can only be created in a schema that exists.
+        if let [schema, _] = object_name_to_strings(name).as_slice()
+            && schema != &self.default_schema
+            && !self.has_schema(schema)
+        {
+            return Err(Error::SchemaDoesntExist(schema.clone()));
+        }
+
+        // Unquoted names are already folded by the tokenizer, so the name is kept as it is.
+        let name = self.type_key(name)?;
+
+        if self.types.contains_key(&name) {
+            return Err(Error::TypeAlreadyExists(name));
+        }
+
+        let ty = match representation {
+            UserDefinedTypeRepresentation::Enum { labels } => SqlType::Enum {
+                name: name.clone(),
+                // Labels are case-sensitive.
+                variants: labels.into_iter().map(|label| label.value).collect(),
+            },
+            representation => {
+                return Err(Error::Unsupported(representation.to_string()));
+            }
+        };
+
+        debug!(name = %name, ty = ?ty, "Creating Type");
+        self.types.insert(name, ty);
+
+        Ok(ResolvedQuery::default())
+    }
+}
@@ -3,8 +3,8 @@ use sqlparser::ast::{Delete, FromTable, TableFactor};
 use crate::{
     Error, Simulator,
     expr::{InferConstraints, InferContext},
-    object_name_to_strings,
     resolve::ResolvedQuery,
+    table_name,
     ty::SqlType,
 };
 
@@ -16,48 +16,27 @@ impl Simulator {
         let mut contexts = vec![];
         let mut resolved = ResolvedQuery::default();
 
-        match delete.from {
-            FromTable::WithFromKeyword(tables_with_joins) => {
-                for from in tables_with_joins {
-                    // TODO: Remove this duplication.
-                    let TableFactor::Table { name, alias, .. } = &from.relation else {
-                        return Err(Error::Unsupported(
-                            "Unsupported DELETE relation".to_string(),
-                        ));
-                    };
-                    let from_table_name = &object_name_to_strings(name)[0];
-                    let from_table_alias = alias.as_ref().map(|a| &a.name.value);
-
-                    let from_table = self
-                        .get_table(from_table_name)
-                        .ok_or_else(|| Error::TableDoesntExist(from_table_name.clone()))?;
-
-                    if let Some(alias) = &from_table_alias
-                        && self.has_table(alias)
-                    {
-                        return Err(Error::AliasIsTableName(alias.to_string()));
-                    }
-
-                    let join_table = self.infer_joins(
-                        from_table,
-                        from_table_name,
-                        from_table_alias,
-                        &from.joins,
-                        &mut resolved,
-                    )?;
-
-                    contexts.push(join_table);
-                }
-            }
+        let tables = match &delete.from {
+            FromTable::WithFromKeyword(tables_with_joins) => tables_with_joins,
             FromTable::WithoutKeyword(_) => {
                 return Err(Error::Unsupported(
                     "DELETE FROM without FROM keyword".to_string(),
                 ));
             }
+        };
+
+        for from in tables {
+            contexts.push(self.infer_table_with_joins(from, &mut resolved)?);
+        }
+
+        // Tables from USING can be referenced by WHERE and RETURNING.
+        for using in delete.using.iter().flatten() {
+            contexts.push(self.infer_table_with_joins(using, &mut resolved)?);
         }
 
         let inferrer = JoinInferrer {
             join_contexts: &contexts,
+            outer: None,
         };
 
         if let Some(selection) = delete.selection {
@@ -82,6 +61,26 @@ impl Simulator {
             }
         }
 
+        if let Some(returning) = delete.returning {
+            // RETURNING wildcards expand to the table being deleted from.
+            let TableFactor::Table { name, alias, .. } = &tables[0].relation else {
+                unreachable!("DELETE relation was resolved above");
+            };
+
+            let table_name = &table_name(name);
+            let table_alias = alias.as_ref().map(|a| a.name.value.as_str());
+            let table = self.find_table(name)?;
+
+            self.process_returning(
+                returning,
+                &inferrer,
+                table_name,
+                table_alias,
+                table,
+                &mut resolved,
+            )?;
+        }
+
         Ok(resolved)
     }
 }
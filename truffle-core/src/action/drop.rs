@@ -1,34 +1,92 @@
 use sqlparser::ast::{ObjectName, ObjectType};
 use tracing::{debug, warn};
 
-use crate::{Error, Simulator, object_name_to_strings, resolve::ResolvedQuery, table::Constraint};
+use crate::{Error, Simulator, resolve::ResolvedQuery, table::Constraint, ty::SqlType};
 
 impl Simulator {
     pub(crate) fn drop(
         &mut self,
         object_type: &ObjectType,
         names: Vec<ObjectName>,
+        if_exists: bool,
+        cascade: bool,
     ) -> Result<ResolvedQuery, Error> {
         if matches!(object_type, ObjectType::Table) {
-            for name in names.iter().flat_map(object_name_to_strings) {
+            let mut dropped = vec![];
+
+            for name in &names {
                 // Ensure that the table being dropped exists.
-                if !self.tables.contains_key(&name) {
-                    return Err(Error::TableDoesntExist(name.to_string()));
+                match self.resolve_table_key(name) {
+                    Ok(name) => dropped.push(name),
+                    Err(Error::TableDoesntExist(_)) if if_exists => {
+                        debug!(name = %name, "Skipping Drop of missing Table");
+                    }
+                    Err(e) => return Err(e),
                 }
+            }
 
-                // Ensure that this table isn't a constraint on another table.
-                for (_, constraints) in self.tables.iter().flat_map(|t| &t.1.constraints) {
-                    for constraint in constraints {
-                        if let Constraint::ForeignKey { foreign_table, .. } = constraint
-                            && foreign_table == &name
-                        {
-                            return Err(Error::ForeignKeyConstraint(name.to_string()));
-                        }
+            let references_dropped = |c: &Constraint| match c {
+                Constraint::ForeignKey { foreign_table, .. } => dropped.contains(foreign_table),
+                _ => false,
+            };
+
+            // Ensure that no other table references a dropped table.
+            for (_, table) in self
+                .get_tables_mut()
+                .iter_mut()
+                .filter(|t| !dropped.contains(t.0))
+            {
+                for constraints in table.constraints.values_mut() {
+                    if cascade {
+                        // CASCADE only drops the foreign keys, not the referencing tables.
+                        constraints.retain(|c| !references_dropped(c));
+                    } else if let Some(Constraint::ForeignKey { foreign_table, .. }) =
+                        constraints.iter().find(|c| references_dropped(c))
+                    {
+                        return Err(Error::ForeignKeyConstraint(foreign_table.to_string()));
                     }
                 }
+            }
 
+            for name in dropped {
                 debug!(name = %name, "Dropping Table");
-                self.tables.remove(&name);
+                self.get_tables_mut().remove(&name);
+            }
+        } else if matches!(object_type, ObjectType::Type) {
+            let mut dropped = vec![];
+
+            for name in &names {
+                // Ensure that the type being dropped exists.
+                let key = self.type_key(name)?;
+                if self.types.contains_key(&key) {
+                    dropped.push(key);
+                } else if if_exists {
+                    debug!(name = %name, "Skipping Drop of missing Type");
+                } else {
+                    return Err(Error::TypeDoesntExist(key));
+                }
+            }
+
+            // Ensure that no column still uses a dropped type.
+            for table in self.tables.values() {
+                for (column_name, column) in &table.columns {
+                    if let Some(name) = dropped.iter().find(|name| uses_type(&column.ty, name)) {
+                        // CASCADE would drop the columns, which isn't simulated.
+                        if cascade {
+                            return Err(Error::Unsupported(format!("DROP TYPE {name} CASCADE")));
+                        }
+
+                        return Err(Error::TypeInUse {
+                            name: name.clone(),
+                            column: column_name.clone(),
+                        });
+                    }
+                }
+            }
+
+            for name in dropped {
+                debug!(name = %name, "Dropping Type");
+                self.types.remove(&name);
             }
         } else {
             warn!(object = %object_type, "Unsupported Drop");
@@ -37,3 +95,14 @@ impl Simulator {
         Ok(ResolvedQuery::default())
     }
 }
+
+/// Whether the type is the named enum, or an array of it.
+fn uses_type(ty: &SqlType, name: &str) -> bool {
+    match ty {
+        SqlType::Enum {
+            name: enum_name, ..
+        } => enum_name == name,
+        SqlType::Array(inner) => uses_type(inner, name),
+        _ => false,
+    }
+}
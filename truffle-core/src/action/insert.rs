@@ -1,5 +1,6 @@
 use sqlparser::ast::{
-    Expr, Insert, SelectItem, SelectItemQualifiedWildcardKind, SetExpr, TableObject,
+    AssignmentTarget, ConflictTarget, Expr, Insert, OnConflict, OnConflictAction, OnInsert,
+    SetExpr, TableObject,
 };
 
 use crate::{
@@ -7,8 +8,10 @@ use crate::{
     column::Column,
     expr::{ColumnInferrer, InferConstraints, InferContext},
     object_name_to_strings,
-    resolve::{ColumnRef, ResolvedQuery},
+    resolve::ResolvedQuery,
     table::Table,
+    table_name,
+    ty::SqlType,
 };
 
 impl Simulator {
@@ -19,20 +22,35 @@ impl Simulator {
 
         // Only POSTGRES uses this.
         let alias = ins.table_alias.map(|i| i.value);
-        let table_name = &object_name_to_strings(&table_object_name)[0];
+        let table_name = &table_name(&table_object_name);
 
-        let table = self
-            .get_table(table_name)
-            .ok_or_else(|| Error::TableDoesntExist(table_name.clone()))?;
+        let table = self.find_table(&table_object_name)?;
 
-        let mut provided_columns = vec![];
-        for column in ins.columns {
-            let column_name = column.value;
-            if !table.has_column(&column_name) {
-                return Err(Error::ColumnDoesntExist(column_name));
+        // Without an explicit column list, every column is provided in table order.
+        let provided_columns = if ins.columns.is_empty() {
+            table.columns.iter().collect::<Vec<_>>()
+        } else {
+            let mut provided_columns = vec![];
+            for column in &ins.columns {
+                let (column_name, column) = table
+                    .columns
+                    .get_key_value(&column.value)
+                    .ok_or_else(|| Error::ColumnDoesntExist(column.value.clone()))?;
+
+                provided_columns.push((column_name, column));
             }
 
-            provided_columns.push(column_name);
+            provided_columns
+        };
+
+        // Columns that were not named must be able to take a value on their own.
+        for (column_name, column) in &table.columns {
+            if !column.nullable
+                && !column.default
+                && !provided_columns.iter().any(|(pc, _)| *pc == column_name)
+            {
+                return Err(Error::RequiredColumnMissing(column_name.to_string()));
+            }
         }
 
         // This stores the return information for this query.
@@ -41,6 +59,7 @@ impl Simulator {
             table,
             table_name,
             alias: alias.as_deref(),
+            excluded: false,
         };
 
         let source = ins.source.unwrap();
@@ -48,67 +67,56 @@ impl Simulator {
             SetExpr::Values(values) => {
                 for row in values.rows {
                     // Ensure we have the correct number of columns.
-                    if provided_columns.is_empty() {
-                        if table.columns.len() != row.len() {
-                            return Err(Error::ColumnCountMismatch {
-                                expected: table.columns.len(),
-                                got: row.len(),
-                            });
-                        }
-                    } else if provided_columns.len() != row.len() {
+                    if provided_columns.len() != row.len() {
                         return Err(Error::ColumnCountMismatch {
                             expected: provided_columns.len(),
                             got: row.len(),
                         });
                     }
 
-                    for (i, (column_name, column)) in table.columns.iter().enumerate() {
-                        if provided_columns.is_empty() {
-                            // Implicit (Table Index) Columns.
-                            let expr = &row[i];
-
-                            _ = self.infer_expr_column(
-                                expr,
-                                InferContext {
-                                    constraints: InferConstraints {
-                                        ty: Some(column.ty.clone()),
-                                        nullable: Some(column.nullable),
-                                        ..Default::default()
-                                    },
-                                    ..Default::default()
-                                },
-                                &inferrer,
-                                &mut resolved,
-                            )?;
-                        } else if let Some(index) =
-                            provided_columns.iter().position(|pc| pc == column_name)
-                        {
-                            // If the column was named explicitly...
-                            let expr = &row[index];
-
-                            _ = self.infer_expr_column(
-                                expr,
-                                InferContext {
-                                    constraints: InferConstraints {
-                                        ty: Some(column.ty.clone()),
-                                        nullable: Some(column.nullable),
-                                        ..Default::default()
-                                    },
+                    for (expr, (column_name, column)) in row.iter().zip(&provided_columns) {
+                        // DEFAULT takes the column's default, so there is nothing to infer.
+                        if is_default_keyword(expr) {
+                            if !column.default {
+                                return Err(Error::DefaultOnNotDefaultColumn(
+                                    column_name.to_string(),
+                                ));
+                            }
+
+                            continue;
+                        }
+
+                        _ = self.infer_expr_column(
+                            expr,
+                            InferContext {
+                                constraints: InferConstraints {
+                                    ty: Some(column.ty.clone()),
+                                    nullable: Some(column.nullable),
                                     ..Default::default()
                                 },
-                                &inferrer,
-                                &mut resolved,
-                            )?;
-                        } else if !(column.nullable || column.default) {
-                            // If the column was not named explicitly, we check it.
-                            return Err(Error::RequiredColumnMissing(column_name.to_string()));
-                        }
+                                ..Default::default()
+                            },
+                            &inferrer,
+                            &mut resolved,
+                        )?;
                     }
                 }
             }
             _ => todo!("Unexpected body for INSERT"),
         }
 
+        match ins.on {
+            Some(OnInsert::OnConflict(on_conflict)) => {
+                self.on_conflict(on_conflict, &inferrer, &mut resolved)?;
+            }
+            Some(on) => {
+                return Err(Error::Unsupported(format!(
+                    "Unsupported INSERT clause: {on}"
+                )));
+            }
+            None => {}
+        }
+
         if let Some(returning) = ins.returning {
             self.process_returning(
                 returning,
@@ -122,12 +130,110 @@ impl Simulator {
 
         Ok(resolved)
     }
+
+    fn on_conflict(
+        &self,
+        on_conflict: OnConflict,
+        inferrer: &InsertInferrer,
+        resolved: &mut ResolvedQuery,
+    ) -> Result<(), Error> {
+        let table = inferrer.table;
+
+        match on_conflict.conflict_target {
+            Some(ConflictTarget::Columns(columns)) => {
+                let columns: Vec<_> = columns.into_iter().map(|c| c.value).collect();
+
+                for column in &columns {
+                    if !table.has_column(column) {
+                        return Err(Error::ColumnDoesntExist(column.to_string()));
+                    }
+                }
+
+                // The conflict can only happen on a unique set of columns.
+                if !(table.is_primary_key(&columns) || table.is_unique(&columns)) {
+                    return Err(Error::ConflictTargetNotUnique(columns.join(", ")));
+                }
+            }
+            Some(ConflictTarget::OnConstraint(name)) => {
+                return Err(Error::Unsupported(format!(
+                    "ON CONFLICT ON CONSTRAINT {name}"
+                )));
+            }
+            None => {}
+        }
+
+        let OnConflictAction::DoUpdate(do_update) = on_conflict.action else {
+            return Ok(());
+        };
+
+        // The excluded pseudo-table holds the row that was proposed for insertion.
+        let inferrer = InsertInferrer {
+            excluded: true,
+            ..*inferrer
+        };
+
+        for assignment in do_update.assignments {
+            let AssignmentTarget::ColumnName(object_name) = assignment.target else {
+                return Err(Error::Unsupported(
+                    "Tuple assignment in ON CONFLICT".to_string(),
+                ));
+            };
+
+            let name = &object_name_to_strings(&object_name)[0];
+            let column = table
+                .get_column(name)
+                .ok_or_else(|| Error::ColumnDoesntExist(name.to_string()))?;
+
+            self.infer_expr_column(
+                &assignment.value,
+                InferContext {
+                    constraints: InferConstraints {
+                        ty: Some(column.ty.clone()),
+                        nullable: Some(column.nullable),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                &inferrer,
+                resolved,
+            )?;
+        }
+
+        if let Some(selection) = do_update.selection {
+            self.infer_expr_column(
+                &selection,
+                InferContext {
+                    constraints: InferConstraints {
+                        ty: Some(SqlType::Boolean),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                &inferrer,
+                resolved,
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The DEFAULT keyword in a VALUES row is parsed as a bare identifier.
+fn is_default_keyword(expr: &Expr) -> bool {
+    matches!(
+        expr,
+        Expr::Identifier(ident)
+            if ident.quote_style.is_none() && ident.value.eq_ignore_ascii_case("default")
+    )
 }
 
+#[derive(Clone, Copy)]
 struct InsertInferrer<'a> {
     table: &'a Table,
     table_name: &'a str,
     alias: Option<&'a str>,
+    // Whether the `excluded` pseudo-table can be referenced (ON CONFLICT DO UPDATE).
+    excluded: bool,
 }
 
 impl<'a> ColumnInferrer for InsertInferrer<'a> {
@@ -145,7 +251,10 @@ impl<'a> ColumnInferrer for InsertInferrer<'a> {
         qualifier: &str,
         column: &str,
     ) -> Result<Column, Error> {
-        if qualifier == self.table_name || self.alias.is_some_and(|a| a == qualifier) {
+        if qualifier == self.table_name
+            || self.alias.is_some_and(|a| a == qualifier)
+            || (self.excluded && qualifier == "excluded")
+        {
             Ok(self
                 .table
                 .get_column(column)
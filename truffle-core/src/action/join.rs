@@ -1,22 +1,52 @@
 use std::{
-    collections::{HashMap, hash_map},
+    collections::{HashMap, HashSet, hash_map},
     rc::Rc,
 };
 
 use itertools::Itertools;
-use sqlparser::ast::{Join, JoinConstraint, JoinOperator, TableFactor};
+use sqlparser::ast::{Join, JoinConstraint, JoinOperator, TableFactor, TableWithJoins};
+use tracing::debug;
 
 use crate::{
     Error, Simulator,
     column::Column,
     expr::{ColumnInferrer, InferConstraints, InferContext},
     object_name_to_strings,
-    resolve::ResolvedQuery,
+    resolve::{ColumnRef, ResolvedQuery},
     table::Table,
+    table_name,
     ty::SqlType,
 };
 
 impl Simulator {
+    /// Resolves a table and its joins from the FROM or USING list of a write statement.
+    pub(crate) fn infer_table_with_joins(
+        &self,
+        from: &TableWithJoins,
+        resolved: &mut ResolvedQuery,
+    ) -> Result<JoinContext, Error> {
+        let TableFactor::Table { name, alias, .. } = &from.relation else {
+            return Err(Error::Unsupported(format!(
+                "Unsupported table relation: {}",
+                from.relation
+            )));
+        };
+
+        let table_name = &table_name(name);
+        let table_alias = alias.as_ref().map(|a| &a.name.value);
+
+        let table = self.find_table(name)?;
+
+        // Ensure that the alias isn't a table name.
+        if let Some(alias) = &table_alias
+            && self.has_table(alias)
+        {
+            return Err(Error::AliasIsTableName(alias.to_string()));
+        }
+
+        self.infer_joins(table, table_name, table_alias, &from.joins, resolved)
+    }
+
     pub(crate) fn infer_joins(
         &self,
         table: &Table,
@@ -30,12 +60,10 @@ impl Simulator {
         for join in joins {
             match &join.relation {
                 TableFactor::Table { name, alias, .. } => {
-                    let right_table_name = &object_name_to_strings(name)[0];
+                    let right_table_name = &table_name(name);
                     let right_table_alias = alias.as_ref().map(|a| &a.name.value);
 
-                    let right_table = self
-                        .get_table(right_table_name)
-                        .ok_or_else(|| Error::TableDoesntExist(right_table_name.clone()))?;
+                    let right_table = self.find_table(name)?;
 
                     if let Some(alias) = &right_table_alias
                         && self.has_table(alias)
@@ -43,23 +71,27 @@ impl Simulator {
                         return Err(Error::AliasIsTableName(alias.to_string()));
                     }
 
+                    let right = JoinedTable {
+                        table: right_table,
+                        name: right_table_name,
+                        alias: right_table_alias,
+                    };
+
                     match &join.join_operator {
                         JoinOperator::Join(join_constraint)
                         | JoinOperator::Inner(join_constraint) => self.handle_join_constraint(
                             join_constraint,
                             &mut join_ctx,
-                            right_table,
-                            right_table_name,
-                            right_table_alias,
+                            &right,
+                            NullableSide::Neither,
                             resolved,
                         )?,
                         JoinOperator::Left(join_constraint)
                         | JoinOperator::LeftOuter(join_constraint) => self.handle_join_constraint(
                             join_constraint,
                             &mut join_ctx,
-                            right_table,
-                            right_table_name,
-                            right_table_alias,
+                            &right,
+                            NullableSide::Right,
                             resolved,
                         )?,
                         JoinOperator::Right(join_constraint)
@@ -67,29 +99,23 @@ impl Simulator {
                             .handle_join_constraint(
                                 join_constraint,
                                 &mut join_ctx,
-                                right_table,
-                                right_table_name,
-                                right_table_alias,
+                                &right,
+                                NullableSide::Left,
                                 resolved,
                             )?,
                         JoinOperator::FullOuter(join_constraint) => self.handle_join_constraint(
                             join_constraint,
                             &mut join_ctx,
-                            right_table,
-                            right_table_name,
-                            right_table_alias,
+                            &right,
+                            NullableSide::Both,
                             resolved,
                         )?,
-                        JoinOperator::CrossJoin => join_ctx.join_table(
-                            right_table,
-                            right_table_name,
-                            right_table_alias,
-                            JoinKind::Cross,
-                        )?,
+                        JoinOperator::CrossJoin => {
+                            join_ctx.join_table(&right, JoinKind::Cross, NullableSide::Neither)?
+                        }
                         _ => {
                             return Err(Error::Unsupported(format!(
-                                "Unsupported Join Operator: {:?}",
-                                join.join_operator
+                                "Unsupported Join Operator: {join}"
                             )));
                         }
                     }
@@ -110,21 +136,13 @@ impl Simulator {
         &self,
         join_constraint: &JoinConstraint,
         join_ctx: &mut JoinContext,
-        right_table: &Table,
-        right_table_name: &str,
-        right_table_alias: Option<&String>,
+        right: &JoinedTable,
+        nullable_side: NullableSide,
         resolved: &mut ResolvedQuery,
     ) -> Result<(), Error> {
         match join_constraint {
             JoinConstraint::On(expr) => {
-                let inferrer = JoinContextInferrer {
-                    join_ctx,
-                    right_table: (
-                        right_table_name,
-                        right_table_alias.map(|x| x.as_str()),
-                        right_table,
-                    ),
-                };
+                let inferrer = JoinContextInferrer { join_ctx, right };
 
                 let infer = self.infer_expr_column(
                     expr,
@@ -146,12 +164,7 @@ impl Simulator {
                     });
                 }
 
-                join_ctx.join_table(
-                    right_table,
-                    right_table_name,
-                    right_table_alias,
-                    JoinKind::Cross,
-                )?;
+                join_ctx.join_table(right, JoinKind::Cross, nullable_side)?;
             }
             JoinConstraint::Using(names) => {
                 let column_names: Vec<String> = names
@@ -163,7 +176,7 @@ impl Simulator {
                     let left_ty = if let Some((col_ref, _)) = join_ctx
                         .refs
                         .iter()
-                        .filter(|(r, _)| &r.name == column_name)
+                        .filter(|(r, _)| *r.name == **column_name)
                         .at_most_one()
                         .map_err(|_| Error::AmbiguousColumn(column_name.to_string()))?
                     {
@@ -175,7 +188,7 @@ impl Simulator {
                         return Err(Error::ColumnDoesntExist(column_name.to_string()));
                     };
 
-                    let right_ty = right_table.get_column(column_name).map(|rc| rc.ty.clone());
+                    let right_ty = right.table.get_column(column_name).map(|rc| rc.ty.clone());
 
                     match (left_ty, right_ty) {
                         (Some(lty), Some(rty)) => {
@@ -194,12 +207,7 @@ impl Simulator {
                     }
                 }
 
-                join_ctx.join_table(
-                    right_table,
-                    right_table_name,
-                    right_table_alias,
-                    JoinKind::Using(column_names),
-                )?;
+                join_ctx.join_table(right, JoinKind::Using(column_names), nullable_side)?;
             }
             JoinConstraint::Natural => {
                 let mut found_common_column = false;
@@ -208,7 +216,7 @@ impl Simulator {
                 for (col_ref, column) in join_ctx.refs.iter().unique_by(|r| Rc::as_ptr(r.1)) {
                     let column_name = &col_ref.name;
 
-                    if let Some(right_column) = right_table.get_column(column_name) {
+                    if let Some(right_column) = right.table.get_column(column_name) {
                         // Check if types match
                         if column.ty == right_column.ty {
                             found_common_column = true;
@@ -225,20 +233,10 @@ impl Simulator {
                     return Err(Error::NoCommonColumn);
                 }
 
-                join_ctx.join_table(
-                    right_table,
-                    right_table_name,
-                    right_table_alias,
-                    JoinKind::Natural,
-                )?;
+                join_ctx.join_table(right, JoinKind::Natural, nullable_side)?;
             }
             JoinConstraint::None => {
-                join_ctx.join_table(
-                    right_table,
-                    right_table_name,
-                    right_table_alias,
-                    JoinKind::Cross,
-                )?;
+                join_ctx.join_table(right, JoinKind::Cross, nullable_side)?;
             }
         };
 
@@ -246,17 +244,20 @@ impl Simulator {
     }
 }
 
+/// A column name with the table or alias that qualifies it.
+///
+/// The names are interned by the [`JoinContext`], so the keys of every table and alias share them.
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
 pub struct QualifiedColumnName {
-    pub qualifier: String,
-    pub name: String,
+    pub qualifier: Rc<str>,
+    pub name: Rc<str>,
 }
 
 impl QualifiedColumnName {
-    pub fn new(qualifier: impl ToString, name: impl ToString) -> QualifiedColumnName {
+    pub fn new(qualifier: &Rc<str>, name: &Rc<str>) -> QualifiedColumnName {
         QualifiedColumnName {
-            qualifier: qualifier.to_string(),
-            name: name.to_string(),
+            qualifier: qualifier.clone(),
+            name: name.clone(),
         }
     }
 }
@@ -264,6 +265,14 @@ impl QualifiedColumnName {
 #[derive(Debug)]
 pub struct JoinContext {
     pub refs: HashMap<QualifiedColumnName, Rc<Column>>,
+    names: HashSet<Rc<str>>,
+}
+
+/// The table on the right-hand side of a join.
+struct JoinedTable<'a> {
+    table: &'a Table,
+    name: &'a str,
+    alias: Option<&'a String>,
 }
 
 enum JoinKind {
@@ -272,296 +281,267 @@ enum JoinKind {
     Using(Vec<String>),
 }
 
+/// The side(s) of a join whose columns become nullable for unmatched rows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum NullableSide {
+    Neither,
+    Left,
+    Right,
+    Both,
+}
+
+impl NullableSide {
+    fn left(&self) -> bool {
+        matches!(self, NullableSide::Left | NullableSide::Both)
+    }
+
+    fn right(&self) -> bool {
+        matches!(self, NullableSide::Right | NullableSide::Both)
+    }
+}
+
 impl JoinContext {
     fn from_table(
         table: &Table,
         name: impl ToString,
         alias: Option<impl ToString>,
     ) -> Result<JoinContext, Error> {
-        let table_columns = table.columns.clone();
-        let mut refs = HashMap::new();
+        let mut join_ctx = JoinContext {
+            refs: HashMap::new(),
+            names: HashSet::new(),
+        };
 
-        let table_name = name.to_string();
+        let table_name = join_ctx.intern(&name.to_string());
+        let alias = alias.map(|alias| join_ctx.intern(&alias.to_string()));
 
-        for (column_name, column) in table_columns.iter() {
+        debug!(table = %table_name, alias = ?alias, "Join Context");
+
+        for (column_name, column) in table.columns.iter() {
+            let column_name = join_ctx.intern(column_name);
             let col_rc = Rc::new(column.clone());
+
             assert!(
-                refs.insert(
-                    QualifiedColumnName::new(&table_name, column_name),
-                    col_rc.clone()
-                )
-                .is_none()
+                join_ctx
+                    .refs
+                    .insert(
+                        QualifiedColumnName::new(&table_name, &column_name),
+                        col_rc.clone()
+                    )
+                    .is_none()
             );
 
             if let Some(alias) = &alias {
                 assert!(
-                    refs.insert(
-                        QualifiedColumnName::new(alias.to_string(), column_name),
-                        col_rc
-                    )
-                    .is_none()
+                    join_ctx
+                        .refs
+                        .insert(QualifiedColumnName::new(alias, &column_name), col_rc)
+                        .is_none()
                 )
             }
         }
 
-        Ok(JoinContext { refs })
+        Ok(join_ctx)
     }
 
-    fn join_table(
+    /// The shared copy of the name, so that it is only allocated once per context.
+    fn intern(&mut self, name: &str) -> Rc<str> {
+        if let Some(interned) = self.names.get(name) {
+            return interned.clone();
+        }
+
+        let interned: Rc<str> = Rc::from(name);
+        self.names.insert(interned.clone());
+        interned
+    }
+
+    /// Adds the logical column under the table name and the alias.
+    fn insert_column(
         &mut self,
-        table: &Table,
-        table_name: impl ToString,
-        alias: Option<impl ToString>,
-        kind: JoinKind,
+        table_name: &Rc<str>,
+        alias: Option<&Rc<str>>,
+        column_name: &Rc<str>,
+        col_rc: Rc<Column>,
     ) -> Result<(), Error> {
-        let columns = table.columns.clone();
-        let table_name = table_name.to_string();
-
-        match kind {
-            JoinKind::Cross => {
-                // add all columns from the right to the left
-                for (column_name, column) in columns.iter() {
-                    let existing_column_rc = self
-                        .refs
-                        .iter()
-                        .filter(|r| r.0.qualifier == table_name)
-                        .find_map(|(col_ref, col_rc)| {
-                            if col_ref.name == *column_name {
-                                Some(col_rc.clone())
-                            } else {
-                                None
-                            }
-                        });
+        match self
+            .refs
+            .entry(QualifiedColumnName::new(table_name, column_name))
+        {
+            hash_map::Entry::Occupied(occupied_entry) => {
+                assert!(
+                    Rc::ptr_eq(occupied_entry.get(), &col_rc),
+                    "Table name collision with different logical columns"
+                )
+            }
+            hash_map::Entry::Vacant(vacant_entry) => {
+                vacant_entry.insert(col_rc.clone());
+            }
+        }
 
-                    let col_rc = existing_column_rc.unwrap_or_else(|| Rc::new(column.clone()));
+        if let Some(alias) = alias {
+            self.refs
+                .insert(QualifiedColumnName::new(alias, column_name), col_rc)
+                .map_or(Ok(()), |_| Err(Error::AmbiguousAlias(alias.to_string())))?;
+        }
 
-                    match self
-                        .refs
-                        .entry(QualifiedColumnName::new(&table_name, column_name))
-                    {
-                        hash_map::Entry::Occupied(occupied_entry) => {
-                            assert!(
-                                Rc::ptr_eq(occupied_entry.get(), &col_rc),
-                                "Table name collision with different logical columns"
-                            )
-                        }
-                        hash_map::Entry::Vacant(vacant_entry) => {
-                            vacant_entry.insert(col_rc.clone());
-                        }
-                    }
+        Ok(())
+    }
 
-                    if let Some(alias) = &alias {
-                        self.refs
-                            .insert(
-                                QualifiedColumnName::new(alias.to_string(), column_name),
-                                col_rc,
-                            )
-                            .map_or(Ok(()), |_| Err(Error::AmbiguousAlias(alias.to_string())))?;
-                    }
-                }
+    fn join_table(
+        &mut self,
+        right: &JoinedTable,
+        kind: JoinKind,
+        nullable_side: NullableSide,
+    ) -> Result<(), Error> {
+        let mut columns = right.table.columns.clone();
+        let table_name = self.intern(right.name);
+        let alias = right.alias.map(|alias| self.intern(alias));
+
+        // The logical columns that the shared columns (NATURAL/USING) are merged into.
+        let mut merged: HashMap<String, Rc<Column>> = HashMap::new();
+        for column_name in columns.keys() {
+            let col_rc = match &kind {
+                JoinKind::Cross => None,
+                JoinKind::Natural => self
+                    .refs
+                    .iter()
+                    .find(|(r, _)| *r.name == **column_name)
+                    .map(|(_, col_rc)| col_rc.clone()),
+                JoinKind::Using(commons) if commons.contains(column_name) => Some(
+                    self.refs
+                        .iter()
+                        .filter(|(r, _)| *r.name == **column_name)
+                        .map(|(_, col_rc)| col_rc.clone())
+                        .exactly_one()
+                        .unwrap(),
+                ),
+                JoinKind::Using(_) => None,
+            };
+
+            if let Some(col_rc) = col_rc {
+                merged.insert(column_name.clone(), col_rc);
             }
-            JoinKind::Natural => {
-                let all_existing_columns: Vec<String> =
-                    self.refs.keys().map(|r| r.name.clone()).collect();
-
-                for (column_name, column) in columns.iter() {
-                    if all_existing_columns.contains(column_name) {
-                        let existing_col_rc = self
-                            .refs
-                            .iter()
-                            .find_map(|(col_ref, col_rc)| {
-                                if col_ref.name == *column_name {
-                                    Some(col_rc.clone())
-                                } else {
-                                    None
-                                }
-                            })
-                            .unwrap();
-
-                        match self
-                            .refs
-                            .entry(QualifiedColumnName::new(&table_name, column_name))
-                        {
-                            hash_map::Entry::Occupied(occupied_entry) => {
-                                assert!(
-                                    Rc::ptr_eq(occupied_entry.get(), &existing_col_rc),
-                                    "Table name collision with different logical columns"
-                                )
-                            }
-                            hash_map::Entry::Vacant(vacant_entry) => {
-                                vacant_entry.insert(existing_col_rc.clone());
-                            }
-                        }
+        }
 
-                        if let Some(alias) = &alias {
-                            self.refs
-                                .insert(
-                                    QualifiedColumnName::new(alias.to_string(), column_name),
-                                    existing_col_rc,
-                                )
-                                .map_or(Ok(()), |_| {
-                                    Err(Error::AmbiguousAlias(alias.to_string()))
-                                })?;
-                        }
-                    } else {
-                        let col_rc = Rc::new(column.clone());
-
-                        match self
-                            .refs
-                            .entry(QualifiedColumnName::new(&table_name, column_name))
-                        {
-                            hash_map::Entry::Occupied(occupied_entry) => {
-                                assert!(
-                                    Rc::ptr_eq(occupied_entry.get(), &col_rc),
-                                    "Table name collision with different logical columns"
-                                )
-                            }
-                            hash_map::Entry::Vacant(vacant_entry) => {
-                                vacant_entry.insert(col_rc.clone());
-                            }
-                        }
+        // Shared columns keep their nullability.
+        if nullable_side.left() {
+            self.mark_nullable(merged.values());
+        }
 
-                        if let Some(alias) = &alias {
-                            self.refs
-                                .insert(
-                                    QualifiedColumnName::new(alias.to_string(), column_name),
-                                    col_rc,
-                                )
-                                .map_or(Ok(()), |_| {
-                                    Err(Error::AmbiguousAlias(alias.to_string()))
-                                })?;
-                        }
-                    }
+        if nullable_side.right() {
+            for (column_name, column) in columns.iter_mut() {
+                if !merged.contains_key(column_name) {
+                    column.nullable = true;
                 }
             }
-            JoinKind::Using(commons) => {
-                for (column_name, column) in columns.iter() {
-                    if commons.contains(column_name) {
-                        let existing_col_rc = self
-                            .refs
-                            .iter()
-                            .filter_map(|(col_ref, col_rc)| {
-                                if col_ref.name == *column_name {
-                                    Some(col_rc.clone())
-                                } else {
-                                    None
-                                }
-                            })
-                            .exactly_one()
-                            .unwrap();
-
-                        match self
-                            .refs
-                            .entry(QualifiedColumnName::new(&table_name, column_name))
-                        {
-                            hash_map::Entry::Occupied(occupied_entry) => {
-                                assert!(
-                                    Rc::ptr_eq(occupied_entry.get(), &existing_col_rc),
-                                    "Table name collision with different logical columns"
-                                )
-                            }
-                            hash_map::Entry::Vacant(vacant_entry) => {
-                                vacant_entry.insert(existing_col_rc.clone());
-                            }
-                        }
+        }
 
-                        if let Some(alias) = &alias {
-                            self.refs
-                                .insert(
-                                    QualifiedColumnName::new(alias.to_string(), column_name),
-                                    existing_col_rc,
-                                )
-                                .map_or(Ok(()), |_| {
-                                    Err(Error::AmbiguousAlias(alias.to_string()))
-                                })?;
-                        }
-                    } else {
-                        let col_rc = Rc::new(column.clone());
-
-                        match self
-                            .refs
-                            .entry(QualifiedColumnName::new(&table_name, column_name))
-                        {
-                            hash_map::Entry::Occupied(occupied_entry) => {
-                                assert!(
-                                    Rc::ptr_eq(occupied_entry.get(), &col_rc),
-                                    "Table name collision with different logical columns"
-                                )
-                            }
-                            hash_map::Entry::Vacant(vacant_entry) => {
-                                vacant_entry.insert(col_rc.clone());
-                            }
-                        }
+        debug!(
+            table = %table_name,
+            alias = ?alias,
+            columns = ?columns.keys().collect::<Vec<_>>(),
+            "Joining Table"
+        );
+
+        for (column_name, column) in columns.iter() {
+            // The logical column that this one is merged into, if any.
+            let existing_col_rc = match &kind {
+                // A table that is joined again reuses the columns under its name.
+                JoinKind::Cross => self
+                    .refs
+                    .iter()
+                    .find(|(r, _)| r.qualifier == table_name && *r.name == **column_name)
+                    .map(|(_, col_rc)| col_rc.clone()),
+                _ => merged.get(column_name).cloned(),
+            };
 
-                        if let Some(alias) = &alias {
-                            self.refs
-                                .insert(
-                                    QualifiedColumnName::new(alias.to_string(), column_name),
-                                    col_rc,
-                                )
-                                .map_or(Ok(()), |_| {
-                                    Err(Error::AmbiguousAlias(alias.to_string()))
-                                })?;
-                        }
-                    }
-                }
-            }
+            let col_rc = existing_col_rc.unwrap_or_else(|| Rc::new(column.clone()));
+            let column_name = self.intern(column_name);
+
+            self.insert_column(&table_name, alias.as_ref(), &column_name, col_rc)?;
         }
 
         Ok(())
     }
 
+    /// Marks every logical column currently in the context as nullable,
+    /// except for the given logical columns.
+    fn mark_nullable<'a>(&mut self, except: impl IntoIterator<Item = &'a Rc<Column>>) {
+        let except: HashSet<*const Column> = except.into_iter().map(Rc::as_ptr).collect();
+
+        // Keep the old refs alive so that the pointers used for deduplication stay unique.
+        let old_refs = std::mem::take(&mut self.refs);
+        let mut replaced: HashMap<*const Column, Rc<Column>> = HashMap::new();
+
+        for (col_ref, col_rc) in old_refs.iter() {
+            let new_rc = if except.contains(&Rc::as_ptr(col_rc)) {
+                col_rc.clone()
+            } else {
+                replaced
+                    .entry(Rc::as_ptr(col_rc))
+                    .or_insert_with(|| {
+                        let mut column = Column::clone(col_rc);
+                        column.nullable = true;
+                        Rc::new(column)
+                    })
+                    .clone()
+            };
+
+            self.refs.insert(col_ref.clone(), new_rc);
+        }
+    }
+
     pub fn has_qualifier(&self, table: &str) -> bool {
-        self.refs.keys().any(|k| k.qualifier == table)
+        self.refs.keys().any(|k| *k.qualifier == *table)
     }
 
     pub fn get_column(&self, column: &str) -> Result<Option<Column>, Error> {
-        fn match_into_column(
-            join_ctx: &JoinContext,
-            matches: &[(QualifiedColumnName, Rc<Column>)],
-        ) -> Column {
-            matches
-                .first()
-                .as_ref()
-                .and_then(|m| {
-                    join_ctx.refs.iter().find_map(
-                        |(col_ref, col_rc)| {
-                            if col_ref == &m.0 { Some(col_rc) } else { None }
-                        },
-                    )
-                })
-                .map(|c| Column::clone(c))
-                .unwrap()
-        }
-
-        let matches: Vec<(QualifiedColumnName, Rc<Column>)> = self
+        let matches: Vec<&Rc<Column>> = self
             .refs
-            .clone()
-            .into_iter()
-            .filter(|(r, _)| r.name == column)
+            .iter()
+            .filter(|(r, _)| *r.name == *column)
+            .map(|(_, col_rc)| col_rc)
             .collect();
 
-        match matches.len() {
-            0 => Ok(None),
-            1 => Ok(Some(match_into_column(self, &matches))),
-            _ => {
-                // We care if the Rcs are the same, not the underlying value.
-                let same_logical = matches.iter().map(|m| Rc::as_ptr(&m.1)).all_equal();
-                if same_logical {
-                    Ok(Some(match_into_column(self, &matches)))
-                } else {
-                    // It is only ambiguous if they map to different logical columns.
-                    Err(Error::AmbiguousColumn(column.to_string()))
-                }
+        match matches.as_slice() {
+            [] => Ok(None),
+            // We care if the Rcs are the same, not the underlying value.
+            [first, rest @ ..] if rest.iter().all(|c| Rc::ptr_eq(c, first)) => {
+                Ok(Some(Column::clone(first)))
             }
+            // It is only ambiguous if they map to different logical columns.
+            _ => Err(Error::AmbiguousColumn(column.to_string())),
         }
     }
 
+    /// The first qualified name of the logical column that the reference resolves to.
+    pub fn canonical_column(&self, column: &ColumnRef) -> Option<QualifiedColumnName> {
+        let col_rc = self
+            .refs
+            .iter()
+            .filter(|(r, _)| {
+                *r.name == *column.name
+                    && column
+                        .qualifier
+                        .as_ref()
+                        .is_none_or(|q| *r.qualifier == **q)
+            })
+            .map(|(_, col_rc)| Rc::as_ptr(col_rc))
+            .all_equal_value()
+            .ok()?;
+
+        self.refs
+            .iter()
+            .filter(|(_, c)| Rc::as_ptr(c) == col_rc)
+            .map(|(r, _)| r)
+            .min_by(|a, b| a.qualifier.cmp(&b.qualifier))
+            .cloned()
+    }
+
     pub fn get_qualified_column(&self, qualifier: &str, column: &str) -> Result<Column, Error> {
         let matches: Vec<_> = self
             .refs
             .iter()
-            .filter(|(col_ref, _)| col_ref.qualifier == qualifier && col_ref.name == column)
+            .filter(|(col_ref, _)| *col_ref.qualifier == *qualifier && *col_ref.name == *column)
             .collect();
 
         let col = match matches.len() {
@@ -580,12 +560,14 @@ impl JoinContext {
 
 pub struct JoinInferrer<'a> {
     pub join_contexts: &'a [JoinContext],
+    // The scope of the enclosing query, for correlated subqueries.
+    pub outer: Option<&'a dyn ColumnInferrer>,
 }
 
 impl<'a> ColumnInferrer for JoinInferrer<'a> {
     fn infer_unqualified_column(
         &self,
-        _sim: &Simulator,
+        sim: &Simulator,
         column: &str,
     ) -> Result<Option<Column>, Error> {
         let mut found_column: Option<Column> = None;
@@ -599,12 +581,16 @@ impl<'a> ColumnInferrer for JoinInferrer<'a> {
             }
         }
 
-        Ok(found_column)
+        // Columns of this query shadow the ones of the outer query.
+        match (found_column, self.outer) {
+            (None, Some(outer)) => outer.infer_unqualified_column(sim, column),
+            (found_column, _) => Ok(found_column),
+        }
     }
 
     fn infer_qualified_column(
         &self,
-        _sim: &Simulator,
+        sim: &Simulator,
         qualifier: &str,
         column: &str,
     ) -> Result<Column, Error> {
@@ -614,16 +600,35 @@ impl<'a> ColumnInferrer for JoinInferrer<'a> {
             }
         }
 
+        if let Some(outer) = self.outer
+            && !self
+                .join_contexts
+                .iter()
+                .any(|c| c.has_qualifier(qualifier))
+        {
+            return outer.infer_qualified_column(sim, qualifier, column);
+        }
+
         Err(Error::QualifiedColumnDoesntExist {
             qualifier: qualifier.to_string(),
             column: column.to_string(),
         })
     }
+
+    fn canonical_column(&self, column: &ColumnRef) -> Option<ColumnRef> {
+        // Two different tables can have a column with the same name.
+        self.join_contexts
+            .iter()
+            .filter_map(|c| c.canonical_column(column))
+            .exactly_one()
+            .ok()
+            .map(|c| ColumnRef::new(Some(c.qualifier.to_string()), c.name))
+    }
 }
 
 struct JoinContextInferrer<'a> {
     join_ctx: &'a JoinContext,
-    right_table: (&'a str, Option<&'a str>, &'a Table),
+    right: &'a JoinedTable<'a>,
 }
 
 impl<'a> ColumnInferrer for JoinContextInferrer<'a> {
@@ -636,7 +641,7 @@ impl<'a> ColumnInferrer for JoinContextInferrer<'a> {
         let mut found_col = self.join_ctx.get_column(column)?;
 
         // Search Right Table.
-        if let Some(col) = self.right_table.2.get_column(column) {
+        if let Some(col) = self.right.table.get_column(column) {
             match found_col {
                 // Ensure that the unqualified column is unique.
                 Some(_) => return Err(Error::AmbiguousColumn(column.to_string())),
@@ -656,14 +661,14 @@ impl<'a> ColumnInferrer for JoinContextInferrer<'a> {
         if let Ok(col) = self.join_ctx.get_qualified_column(qualifier, column) {
             Ok(col)
         } else {
-            if let Some(right_alias) = self.right_table.1
+            if let Some(right_alias) = self.right.alias
                 && qualifier == right_alias
             {
-                if let Some(col) = self.right_table.2.get_column(column) {
+                if let Some(col) = self.right.table.get_column(column) {
                     return Ok(col.clone());
                 }
-            } else if qualifier == self.right_table.0
-                && let Some(col) = self.right_table.2.get_column(column)
+            } else if qualifier == self.right.name
+                && let Some(col) = self.right.table.get_column(column)
             {
                 return Ok(col.clone());
             }
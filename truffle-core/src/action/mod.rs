@@ -1,7 +1,12 @@
+pub mod alter_table;
+pub mod comment;
 pub mod create_table;
+pub mod create_type;
 pub mod delete;
 pub mod drop;
 pub mod insert;
 pub mod join;
 pub mod query;
+pub mod schema;
+pub mod truncate;
 pub mod update;
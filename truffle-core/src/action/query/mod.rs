@@ -1,17 +1,191 @@
 pub mod select;
+mod table_function;
+mod values;
 
-use sqlparser::ast::{Query, SetExpr};
-use tracing::warn;
+use sqlparser::ast::{Expr, LimitClause, Query, SetExpr, UnaryOperator, Value};
 
-use crate::{Error, Simulator, resolve::ResolvedQuery};
+use crate::{
+    Error, Simulator,
+    column::Column,
+    dialect::DialectKind,
+    expr::{ColumnInferrer, InferConstraints, InferContext, Scope},
+    resolve::ResolvedQuery,
+    ty::SqlType,
+};
 
 impl Simulator {
     pub(crate) fn query(&self, query: &Query) -> Result<ResolvedQuery, Error> {
-        if let SetExpr::Select(_) = *query.body {
-            self.select(query)
-        } else {
-            warn!(query_type = %query.body, "Unsupported Query");
-            Ok(ResolvedQuery::default())
+        self.query_in_scope(query, None)
+    }
+
+    /// Resolves a subquery that can reference the columns of the outer query.
+    pub(crate) fn correlated_query(
+        &self,
+        query: &Query,
+        outer: &dyn ColumnInferrer,
+    ) -> Result<ResolvedQuery, Error> {
+        self.query_in_scope(query, Some(outer))
+    }
+
+    fn query_in_scope(
+        &self,
+        query: &Query,
+        outer: Option<&dyn ColumnInferrer>,
+    ) -> Result<ResolvedQuery, Error> {
+        // Every clause that can hold an expression must be resolved or rejected,
+        // otherwise its placeholders end up untyped.
+        if let Some(with) = &query.with {
+            return Err(Error::Unsupported(format!("WITH clause: {with}")));
+        }
+
+        let mut resolved = match query.body.as_ref() {
+            SetExpr::Select(_) => self.select(query, outer)?,
+            SetExpr::Values(values) => {
+                if let Some(order_by) = &query.order_by {
+                    return Err(Error::Unsupported(format!(
+                        "ORDER BY on VALUES: {order_by}"
+                    )));
+                }
+
+                self.values(values)?
+            }
+            body => return Err(Error::Unsupported(format!("Query: {body}"))),
+        };
+
+        if let Some(limit_clause) = &query.limit_clause {
+            self.limit_clause(limit_clause, &mut resolved)?;
+        }
+
+        if let Some(fetch) = &query.fetch {
+            return Err(Error::Unsupported(format!("FETCH clause: {fetch}")));
+        }
+
+        Ok(resolved)
+    }
+
+    fn limit_clause(
+        &self,
+        limit_clause: &LimitClause,
+        resolved: &mut ResolvedQuery,
+    ) -> Result<(), Error> {
+        // LIMIT and OFFSET are independent of each other.
+        match limit_clause {
+            LimitClause::LimitOffset {
+                limit,
+                offset,
+                limit_by,
+            } => {
+                if !limit_by.is_empty() {
+                    return Err(Error::Unsupported(format!(
+                        "LIMIT BY clause: {limit_clause}"
+                    )));
+                }
+
+                if let Some(limit) = limit {
+                    self.infer_row_count(limit, resolved)?;
+                }
+
+                if let Some(offset) = offset {
+                    self.infer_row_count(&offset.value, resolved)?;
+                }
+            }
+            LimitClause::OffsetCommaLimit { offset, limit } => {
+                self.infer_row_count(offset, resolved)?;
+                self.infer_row_count(limit, resolved)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Infers an expression that is used as a count of rows (LIMIT/OFFSET).
+    ///
+    /// Row counts are `BigInt`, except on SQLite where every integer is an `Integer`.
+    fn infer_row_count(&self, expr: &Expr, resolved: &mut ResolvedQuery) -> Result<(), Error> {
+        let ty = match self.dialect.kind() {
+            DialectKind::Sqlite => SqlType::Integer,
+            _ => SqlType::BigInt,
+        };
+
+        // A negative row count is never meaningful.
+        if let Expr::UnaryOp {
+            op: UnaryOperator::Minus,
+            expr: inner,
+        } = expr
+            && matches!(inner.as_ref(), Expr::Value(v) if matches!(v.value, Value::Number(..)))
+        {
+            return Err(Error::Sql(format!(
+                "Row count must not be negative: {expr}"
+            )));
+        }
+
+        let infer = self.infer_expr_column(
+            expr,
+            InferContext {
+                constraints: InferConstraints {
+                    ty: Some(ty.clone()),
+                    scope: Some(Scope::Literal),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            &LiteralInferrer,
+            resolved,
+        )?;
+
+        if !infer.column.ty.is_integer() {
+            return Err(Error::TypeMismatch {
+                expected: ty,
+                got: infer.column.ty,
+            });
         }
+
+        Ok(())
+    }
+}
+
+/// Used for expressions that can't reference any columns (row counts, table function arguments).
+pub(crate) struct LiteralInferrer;
+
+impl ColumnInferrer for LiteralInferrer {
+    fn infer_unqualified_column(&self, _: &Simulator, _: &str) -> Result<Option<Column>, Error> {
+        Ok(None)
+    }
+
+    fn infer_qualified_column(
+        &self,
+        _: &Simulator,
+        qualifier: &str,
+        column: &str,
+    ) -> Result<Column, Error> {
+        Err(Error::QualifiedColumnDoesntExist {
+            qualifier: qualifier.to_string(),
+            column: column.to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sqlparser::{ast::Statement, dialect::ClickHouseDialect, parser::Parser};
+
+    use crate::{Error, Simulator};
+
+    #[test]
+    fn limit_by_is_unsupported() {
+        let mut sim = Simulator::default();
+        sim.execute("create table person (id int primary key, name text not null)")
+            .unwrap();
+
+        // None of the dialects parse LIMIT BY, so the query is parsed as ClickHouse.
+        let sql = "select * from person limit 1 by name";
+        let Statement::Query(query) = Parser::parse_sql(&ClickHouseDialect {}, sql)
+            .unwrap()
+            .remove(0)
+        else {
+            unreachable!()
+        };
+
+        assert!(matches!(sim.query(&query), Err(Error::Unsupported(_))));
     }
 }
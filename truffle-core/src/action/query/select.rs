@@ -2,20 +2,27 @@ use std::{collections::HashSet, rc::Rc};
 
 use itertools::Itertools;
 use sqlparser::ast::{
-    GroupByExpr, OrderByKind, Query, SelectItem, SelectItemQualifiedWildcardKind, TableFactor,
+    Distinct, Expr, GroupByExpr, OrderByKind, Query, Select, SelectItem,
+    SelectItemQualifiedWildcardKind, TableFactor, Value,
 };
 
 use crate::{
     Error, Simulator,
     action::join::JoinInferrer,
-    expr::{InferConstraints, InferContext, Scope},
+    dialect::DialectKind,
+    expr::{ColumnInferrer, InferConstraints, InferContext, Scope},
     object_name_to_strings,
     resolve::{ColumnRef, ResolvedQuery},
+    table_name,
     ty::SqlType,
 };
 
 impl Simulator {
-    pub(crate) fn select(&self, query: &Query) -> Result<ResolvedQuery, Error> {
+    pub(crate) fn select(
+        &self,
+        query: &Query,
+        outer: Option<&dyn ColumnInferrer>,
+    ) -> Result<ResolvedQuery, Error> {
         let mut contexts = vec![];
         let mut resolved = ResolvedQuery::default();
 
@@ -24,21 +31,30 @@ impl Simulator {
             .as_select()
             .expect("Query must be a SELECT by now.");
 
+        Self::reject_unsupported_clauses(sel)?;
+
         for from in &sel.from {
-            let TableFactor::Table { name, alias, .. } = &from.relation else {
+            let TableFactor::Table {
+                name, alias, args, ..
+            } = &from.relation
+            else {
                 return Err(Error::Unsupported(format!(
-                    "Unsupported Select Relation: {:?}",
+                    "Unsupported Select Relation: {}",
                     from.relation
                 )));
             };
 
-            let from_table_name = &object_name_to_strings(name)[0];
+            let function_table;
+            let from_table_name = &table_name(name);
             let from_table_alias = alias.as_ref().map(|a| &a.name.value);
 
-            // Ensure the table exists.
-            let from_table = self
-                .get_table(from_table_name)
-                .ok_or_else(|| Error::TableDoesntExist(from_table_name.clone()))?;
+            let from_table = if let Some(args) = args {
+                function_table = self.table_function(name, args, alias.as_ref(), &mut resolved)?;
+                &function_table
+            } else {
+                // Ensure the table exists.
+                self.find_table(name)?
+            };
 
             // Ensure that the alias isn't a table name.
             if let Some(alias) = &from_table_alias
@@ -60,6 +76,7 @@ impl Simulator {
 
         let inferrer = JoinInferrer {
             join_contexts: &contexts,
+            outer,
         };
 
         // Validate WHERE clause.
@@ -79,12 +96,54 @@ impl Simulator {
             )?;
         }
 
+        // Validate DISTINCT ON, which only Postgres supports.
+        if let Some(Distinct::On(exprs)) = &sel.distinct {
+            if self.dialect.kind() != DialectKind::Postgres {
+                return Err(Error::Unsupported(format!(
+                    "DISTINCT ON for {}",
+                    self.dialect.kind()
+                )));
+            }
+
+            for expr in exprs {
+                self.infer_expr_column(
+                    expr,
+                    InferContext {
+                        constraints: InferConstraints {
+                            scope: Some(Scope::Row),
+                            ..Default::default()
+                        },
+                        ..Default::default()
+                    },
+                    &inferrer,
+                    &mut resolved,
+                )?;
+            }
+        }
+
         let mut grouped_exprs = Vec::new();
 
         // Validate Group By.
         match &sel.group_by {
             GroupByExpr::Expressions(exprs, ..) => {
                 for expr in exprs {
+                    // `GROUP BY 1` groups on the first projection.
+                    let expr = match Self::ordinal(expr) {
+                        Some(ordinal) => match sel.projection.get(ordinal.wrapping_sub(1)) {
+                            Some(
+                                SelectItem::UnnamedExpr(expr)
+                                | SelectItem::ExprWithAlias { expr, .. },
+                            ) => expr,
+                            Some(_) => {
+                                return Err(Error::Unsupported(format!(
+                                    "GROUP BY {ordinal} referencing a wildcard"
+                                )));
+                            }
+                            None => return Err(Error::OrdinalOutOfRange(ordinal)),
+                        },
+                        None => expr,
+                    };
+
                     let infer = self.infer_expr_column(
                         expr,
                         InferContext {
@@ -111,7 +170,8 @@ impl Simulator {
             _ => todo!("Unsupported GroupByExpr"),
         }
 
-        let mut scope = if grouped_exprs.is_empty() {
+        // A HAVING without a GROUP BY makes the whole query a single group.
+        let mut scope = if grouped_exprs.is_empty() && sel.having.is_none() {
             Scope::Literal
         } else {
             Scope::Group
@@ -198,7 +258,7 @@ impl Simulator {
                             for (col_ref, _) in context
                                 .refs
                                 .iter()
-                                .filter(|r| &r.0.qualifier == qualifier)
+                                .filter(|r| *r.0.qualifier == **qualifier)
                                 .unique_by(|r| Rc::as_ptr(r.1))
                             {
                                 let true_column = context
@@ -206,8 +266,8 @@ impl Simulator {
 
                                 resolved.insert_output(
                                     ColumnRef::new(
-                                        Some(col_ref.qualifier.clone()),
-                                        col_ref.name.clone(),
+                                        Some(col_ref.qualifier.to_string()),
+                                        &col_ref.name,
                                     ),
                                     true_column.clone(),
                                 );
@@ -235,7 +295,7 @@ impl Simulator {
                         // We are about if the Rcs are the same, not the underlying value.
                         for (col_ref, _) in context.refs.iter().unique_by(|r| Rc::as_ptr(r.1)) {
                             let column_name = &col_ref.name;
-                            if all_columns.contains(column_name) {
+                            if all_columns.contains(&**column_name) {
                                 return Err(Error::AmbiguousColumn(column_name.to_string()));
                             } else {
                                 // The existence of this column should've already been confirmed earlier.
@@ -243,8 +303,8 @@ impl Simulator {
                                     .get_qualified_column(&col_ref.qualifier, &col_ref.name)?;
 
                                 let key = ColumnRef::new(
-                                    Some(col_ref.qualifier.clone()),
-                                    col_ref.name.clone(),
+                                    Some(col_ref.qualifier.to_string()),
+                                    &col_ref.name,
                                 );
 
                                 resolved.insert_output(key, column.clone());
@@ -263,6 +323,15 @@ impl Simulator {
             match &order_by.kind {
                 OrderByKind::Expressions(order_by_exprs) => {
                     for order_by_expr in order_by_exprs {
+                        // `ORDER BY 2` orders on the second output column.
+                        if let Some(ordinal) = Self::ordinal(&order_by_expr.expr) {
+                            if ordinal == 0 || ordinal > resolved.outputs.len() {
+                                return Err(Error::OrdinalOutOfRange(ordinal));
+                            }
+
+                            continue;
+                        }
+
                         let col = self.infer_expr_column(
                             &order_by_expr.expr,
                             InferContext {
@@ -279,6 +348,17 @@ impl Simulator {
 
                         // TODO: Ensure type is "comparable".
                         _ = col;
+
+                        if let Some(Distinct::Distinct) = &sel.distinct
+                            && self.requires_distinct_order_by_selected()
+                            && !Self::is_order_by_selected(
+                                &order_by_expr.expr,
+                                &sel.projection,
+                                &inferrer,
+                            )
+                        {
+                            return Err(Error::DistinctOrderBy(order_by_expr.expr.to_string()));
+                        }
                     }
                 }
                 _ => todo!("Unsupported OrderByKind"),
@@ -287,4 +367,93 @@ impl Simulator {
 
         Ok(resolved)
     }
+
+    /// Rejects the SELECT clauses that aren't resolved, as they can hold placeholders.
+    fn reject_unsupported_clauses(sel: &Select) -> Result<(), Error> {
+        let clause = if let Some(top) = &sel.top {
+            format!("TOP clause: {top}")
+        } else if let Some(prewhere) = &sel.prewhere {
+            format!("PREWHERE clause: {prewhere}")
+        } else if let Some(qualify) = &sel.qualify {
+            format!("QUALIFY clause: {qualify}")
+        } else if let Some(connect_by) = &sel.connect_by {
+            format!("CONNECT BY clause: {connect_by}")
+        } else if !sel.named_window.is_empty() {
+            "WINDOW clause".to_string()
+        } else if !sel.lateral_views.is_empty() {
+            "LATERAL VIEW clause".to_string()
+        } else if !(sel.cluster_by.is_empty()
+            && sel.distribute_by.is_empty()
+            && sel.sort_by.is_empty())
+        {
+            "CLUSTER BY/DISTRIBUTE BY/SORT BY clause".to_string()
+        } else {
+            return Ok(());
+        };
+
+        Err(Error::Unsupported(clause))
+    }
+
+    /// Whether ORDER BY must only use selected expressions when the query is DISTINCT.
+    ///
+    /// SQLite allows ordering by anything.
+    fn requires_distinct_order_by_selected(&self) -> bool {
+        matches!(
+            self.dialect.kind(),
+            DialectKind::Postgres | DialectKind::Ansi
+        )
+    }
+
+    /// The 1-based select list position of an integer literal, eg. `ORDER BY 2`.
+    fn ordinal(expr: &Expr) -> Option<usize> {
+        match expr {
+            Expr::Value(value) => match &value.value {
+                Value::Number(number, _) => number.to_string().parse().ok(),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Whether the ORDER BY expression is in the select list.
+    ///
+    /// Column references are compared by the logical column they resolve to,
+    /// so `p.name` is selected by `name`.
+    fn is_order_by_selected<I: ColumnInferrer>(
+        expr: &Expr,
+        projection: &[SelectItem],
+        inferrer: &I,
+    ) -> bool {
+        // Ordinals always refer to the select list.
+        if let Expr::Value(value) = expr
+            && matches!(value.value, Value::Number(..))
+        {
+            return true;
+        }
+
+        let column = Self::canonical_column_of(expr, inferrer);
+        let is_same = |selected: &Expr| {
+            selected == expr
+                || column.is_some() && Self::canonical_column_of(selected, inferrer) == column
+        };
+
+        projection.iter().any(|item| match item {
+            SelectItem::UnnamedExpr(selected) => is_same(selected),
+            SelectItem::ExprWithAlias {
+                expr: selected,
+                alias,
+            } => {
+                is_same(selected)
+                    || matches!(expr, Expr::Identifier(ident) if ident.value == alias.value)
+            }
+            SelectItem::Wildcard(_) => matches!(expr, Expr::Identifier(_)),
+            SelectItem::QualifiedWildcard(SelectItemQualifiedWildcardKind::ObjectName(name), _) => {
+                column.as_ref().is_some_and(|column| {
+                    let qualified = ColumnRef::new(Some(name.to_string()), &column.name);
+                    inferrer.canonical_column(&qualified).as_ref() == Some(column)
+                })
+            }
+            SelectItem::QualifiedWildcard(..) => false,
+        })
+    }
 }
@@ -0,0 +1,104 @@
+use sqlparser::ast::{FunctionArg, FunctionArgExpr, ObjectName, TableAlias, TableFunctionArgs};
+
+use crate::{
+    Error, Simulator,
+    action::query::LiteralInferrer,
+    column::Column,
+    expr::{InferConstraints, InferContext, Scope},
+    object_name_to_strings,
+    resolve::ResolvedQuery,
+    table::Table,
+    ty::SqlType,
+};
+
+impl Simulator {
+    /// Builds a synthetic Table for a function used as a relation in FROM.
+    ///
+    /// The columns come from the column definition list of the alias,
+    /// eg. `json_to_recordset($1) as t(id int, name text)`.
+    pub(crate) fn table_function(
+        &self,
+        name: &ObjectName,
+        args: &TableFunctionArgs,
+        alias: Option<&TableAlias>,
+        resolved: &mut ResolvedQuery,
+    ) -> Result<Table, Error> {
+        let function_name = object_name_to_strings(name)[0].to_lowercase();
+
+        let arg_ty = match function_name.as_str() {
+            "json_to_record" | "json_to_recordset" => {
+                #[cfg(feature = "json")]
+                {
+                    Some(SqlType::Json)
+                }
+                #[cfg(not(feature = "json"))]
+                {
+                    Some(SqlType::Text)
+                }
+            }
+            "jsonb_to_record" | "jsonb_to_recordset" => {
+                #[cfg(feature = "json")]
+                {
+                    Some(SqlType::Jsonb)
+                }
+                #[cfg(not(feature = "json"))]
+                {
+                    Some(SqlType::Text)
+                }
+            }
+            _ => None,
+        };
+
+        for arg in &args.args {
+            let FunctionArg::Unnamed(FunctionArgExpr::Expr(expr)) = arg else {
+                return Err(Error::FunctionCall(format!(
+                    "Unsupported argument to {function_name}: {arg}"
+                )));
+            };
+
+            self.infer_expr_column(
+                expr,
+                InferContext {
+                    constraints: InferConstraints {
+                        ty: arg_ty.clone(),
+                        scope: Some(Scope::Literal),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                &LiteralInferrer,
+                resolved,
+            )?;
+        }
+
+        let Some(alias) = alias.filter(|a| !a.columns.is_empty()) else {
+            return Err(Error::Unsupported(format!(
+                "Table function {function_name} without a column definition list"
+            )));
+        };
+
+        let mut table = Table::default();
+
+        for column_def in &alias.columns {
+            let column_name = column_def.name.value.clone();
+
+            let Some(data_type) = &column_def.data_type else {
+                return Err(Error::Unsupported(format!(
+                    "Table function column '{column_name}' without a type"
+                )));
+            };
+
+            if table.has_column(&column_name) {
+                return Err(Error::ColumnAlreadyExists(column_name));
+            }
+
+            // Records can be missing any of the keys, so every column is nullable.
+            table.columns.insert(
+                column_name,
+                Column::new(self.sql_type(data_type), true, false),
+            );
+        }
+
+        Ok(table)
+    }
+}
@@ -0,0 +1,79 @@
+use sqlparser::ast::Values;
+
+use crate::{
+    Error, Simulator,
+    action::query::LiteralInferrer,
+    column::Column,
+    expr::{InferConstraints, InferContext, is_untyped},
+    resolve::{ColumnRef, ResolvedQuery},
+    ty::SqlType,
+};
+
+impl Simulator {
+    /// Resolves a `VALUES` list used as a query, eg. `values (1, 'a'), (2, 'b')`.
+    ///
+    /// Each column takes the type that all of its rows unify to.
+    pub(crate) fn values(&self, values: &Values) -> Result<ResolvedQuery, Error> {
+        let width = values.rows.first().map(|row| row.len()).unwrap_or_default();
+
+        for row in &values.rows {
+            if row.len() != width {
+                return Err(Error::ColumnCountMismatch {
+                    expected: width,
+                    got: row.len(),
+                });
+            }
+        }
+
+        let mut resolved = ResolvedQuery::default();
+
+        for i in 0..width {
+            // NULLs and placeholders take the type of the other rows.
+            let mut ty: Option<SqlType> = None;
+            for row in &values.rows {
+                if is_untyped(&row[i]) {
+                    continue;
+                }
+
+                let infer = self.infer_expr_column(
+                    &row[i],
+                    InferContext::default(),
+                    &LiteralInferrer,
+                    &mut ResolvedQuery::default(),
+                )?;
+
+                ty = Some(match ty {
+                    Some(ty) => ty.promote(&infer.column.ty).ok_or(Error::TypeMismatch {
+                        expected: ty,
+                        got: infer.column.ty,
+                    })?,
+                    None => infer.column.ty,
+                });
+            }
+
+            let mut nullable = false;
+            for row in &values.rows {
+                let infer = self.infer_expr_column(
+                    &row[i],
+                    InferContext {
+                        constraints: InferConstraints {
+                            ty: ty.clone(),
+                            ..Default::default()
+                        },
+                        ..Default::default()
+                    },
+                    &LiteralInferrer,
+                    &mut resolved,
+                )?;
+
+                nullable |= infer.column.nullable;
+                ty.get_or_insert(infer.column.ty);
+            }
+
+            let key = ColumnRef::new(None, format!("column{}", i + 1));
+            resolved.insert_output(key, Column::new(ty.unwrap(), nullable, false));
+        }
+
+        Ok(resolved)
+    }
+}
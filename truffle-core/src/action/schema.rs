@@ -0,0 +1,47 @@
+use sqlparser::ast::{Ident, SchemaName, Statement};
+use tracing::debug;
+
+use crate::{Error, Simulator, object_name_to_strings, resolve::ResolvedQuery};
+
+impl Simulator {
+    pub(crate) fn create_schema(
+        &mut self,
+        schema_name: &SchemaName,
+        if_not_exists: bool,
+    ) -> Result<ResolvedQuery, Error> {
+        let name = match schema_name {
+            SchemaName::Simple(name) | SchemaName::NamedAuthorization(name, _) => {
+                object_name_to_strings(name).join(".")
+            }
+            // The schema is named after the user.
+            SchemaName::UnnamedAuthorization(authorization) => authorization.value.clone(),
+        };
+
+        if self.schemas.contains(&name) {
+            if if_not_exists {
+                return Ok(ResolvedQuery::default());
+            }
+
+            return Err(Error::SchemaAlreadyExists(name));
+        }
+
+        debug!(name = %name, "Creating Schema");
+        self.schemas.insert(name);
+
+        Ok(ResolvedQuery::default())
+    }
+
+    pub(crate) fn create_extension(&mut self, name: &Ident) -> Result<ResolvedQuery, Error> {
+        // Extensions don't change anything we simulate.
+        debug!(name = %name, "Ignoring Extension");
+
+        Ok(ResolvedQuery::default())
+    }
+
+    /// Transactions and session settings (eg. `SET search_path`) don't change the schema.
+    pub(crate) fn ignore_statement(&self, statement: &Statement) -> Result<ResolvedQuery, Error> {
+        debug!(statement = %statement, "Ignoring Statement");
+
+        Ok(ResolvedQuery::default())
+    }
+}
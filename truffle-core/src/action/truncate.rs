@@ -0,0 +1,36 @@
+use sqlparser::ast::{CascadeOption, TruncateTableTarget};
+use tracing::debug;
+
+use crate::{Error, Simulator, resolve::ResolvedQuery, table::Constraint};
+
+impl Simulator {
+    pub(crate) fn truncate(
+        &self,
+        table_names: &[TruncateTableTarget],
+        cascade: Option<CascadeOption>,
+    ) -> Result<ResolvedQuery, Error> {
+        let mut names = vec![];
+
+        // Ensure that the tables being truncated exist.
+        for target in table_names {
+            names.push(self.resolve_table_key(&target.name)?);
+        }
+
+        // Without CASCADE, tables referencing a truncated table must be truncated with it.
+        if !matches!(cascade, Some(CascadeOption::Cascade)) {
+            for (_, table) in self.tables.iter().filter(|t| !names.contains(t.0)) {
+                for constraint in table.constraints.values().flatten() {
+                    if let Constraint::ForeignKey { foreign_table, .. } = constraint
+                        && names.contains(foreign_table)
+                    {
+                        return Err(Error::ForeignKeyConstraint(foreign_table.to_string()));
+                    }
+                }
+            }
+        }
+
+        debug!(tables = ?names, "Ignoring Truncate");
+
+        Ok(ResolvedQuery::default())
+    }
+}
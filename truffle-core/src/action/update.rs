@@ -6,9 +6,10 @@ use sqlparser::ast::{
 use crate::{
     Error, Simulator,
     action::join::JoinInferrer,
-    expr::{ColumnInferrer, InferConstraints, InferContext},
+    expr::{InferConstraints, InferContext},
     object_name_to_strings,
     resolve::ResolvedQuery,
+    table_name,
     ty::SqlType,
 };
 
@@ -30,19 +31,17 @@ impl Simulator {
             ));
         };
 
-        let table_name = &object_name_to_strings(name)[0];
+        let table_name = &table_name(name);
         let table_alias = alias.as_ref().map(|a| &a.name.value);
 
         // Ensure the table exists.
-        let update_table = self
-            .get_table(table_name)
-            .ok_or_else(|| Error::TableDoesntExist(table_name.clone()))?;
+        let update_table = self.find_table(name)?;
 
         // Ensure that the alias isn't a table name.
-        if let Some(alias) = table_alias {
-            if self.has_table(alias) {
-                return Err(Error::AliasIsTableName(alias.to_string()));
-            }
+        if let Some(alias) = table_alias
+            && self.has_table(alias)
+        {
+            return Err(Error::AliasIsTableName(alias.to_string()));
         }
 
         let mut contexts = Vec::new();
@@ -57,55 +56,28 @@ impl Simulator {
 
         contexts.push(join_ctx);
 
-        if let Some(from) = from {
-            match from {
-                UpdateTableFromKind::BeforeSet(_) => todo!(),
-                UpdateTableFromKind::AfterSet(items) => {
-                    for item in items {
-                        let TableFactor::Table { name, alias, .. } = &item.relation else {
-                            return Err(Error::Unsupported(
-                                "Unsupported table relation".to_string(),
-                            ));
-                        };
-
-                        let join_table_name = &object_name_to_strings(name)[0];
-                        let join_table_alias = alias.as_ref().map(|a| &a.name.value);
-
-                        let join_table = self
-                            .get_table(join_table_name)
-                            .ok_or_else(|| Error::TableDoesntExist(join_table_name.clone()))?;
-
-                        // Ensure that the alias isn't a table name.
-                        if let Some(alias) = table_alias {
-                            if self.has_table(alias) {
-                                return Err(Error::AliasIsTableName(alias.to_string()));
-                            }
-                        }
-
-                        let ctx = self.infer_joins(
-                            join_table,
-                            join_table_name,
-                            join_table_alias,
-                            &table.joins,
-                            &mut resolved,
-                        )?;
-
-                        contexts.push(ctx);
-                    }
-                }
+        // Tables from FROM can be referenced by the assignments, WHERE and RETURNING.
+        if let Some(UpdateTableFromKind::BeforeSet(items) | UpdateTableFromKind::AfterSet(items)) =
+            &from
+        {
+            for item in items {
+                contexts.push(self.infer_table_with_joins(item, &mut resolved)?);
             }
         }
 
         let inferrer = JoinInferrer {
             join_contexts: &contexts,
+            outer: None,
         };
 
         for assignment in assignments {
             match assignment.target {
                 AssignmentTarget::ColumnName(object_name) => {
+                    // Only the columns of the updated table can be assigned.
                     let name = &object_name_to_strings(&object_name)[0];
-                    let update_column = inferrer
-                        .infer_unqualified_column(self, name)?
+                    let update_column = update_table
+                        .get_column(name)
+                        .cloned()
                         .ok_or_else(|| Error::ColumnDoesntExist(name.to_string()))?;
 
                     self.infer_expr_column(
@@ -1,9 +1,12 @@
-use std::fmt::Display;
+use std::{fmt::Display, str::FromStr};
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-use crate::ty::SqlType;
+use crate::{
+    Error,
+    ty::{SqlType, strip_suffix_ignore_case},
+};
 
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -40,3 +43,27 @@ impl Display for Column {
         Ok(())
     }
 }
+
+impl FromStr for Column {
+    type Err = Error;
+
+    /// Parses the format written by [`Display`], eg. `integer not null default`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+
+        let (s, default) = match strip_suffix_ignore_case(s, " default") {
+            Some(rest) => (rest, true),
+            None => (s, false),
+        };
+
+        let (s, nullable) = if let Some(rest) = strip_suffix_ignore_case(s, " not null") {
+            (rest, false)
+        } else if let Some(rest) = strip_suffix_ignore_case(s, " null") {
+            (rest, true)
+        } else {
+            (s, false)
+        };
+
+        Ok(Column::new(s.parse()?, nullable, default))
+    }
+}
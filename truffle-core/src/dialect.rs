@@ -1,10 +1,13 @@
-use std::{fmt::Debug, sync::Arc};
+use std::{
+    fmt::{Debug, Display},
+    sync::Arc,
+};
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use crate::misc::immutable::Immutable;
 
-#[derive(Debug, Copy, Clone, Deserialize)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(rename_all = "lowercase")]
 pub enum DialectKind {
     Generic,
@@ -13,6 +16,19 @@ pub enum DialectKind {
     Postgres,
 }
 
+impl Display for DialectKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            DialectKind::Generic => "generic",
+            DialectKind::Ansi => "ansi",
+            DialectKind::Sqlite => "sqlite",
+            DialectKind::Postgres => "postgres",
+        };
+
+        write!(f, "{name}")
+    }
+}
+
 pub trait Dialect: Debug + 'static {
     fn kind(&self) -> DialectKind;
     fn parser_dialect(&self) -> Immutable<Arc<dyn sqlparser::dialect::Dialect>>;
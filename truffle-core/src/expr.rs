@@ -1,4 +1,4 @@
-use sqlparser::ast::{BinaryOperator, CastKind, Expr, UnaryOperator, Value};
+use sqlparser::ast::{BinaryOperator, CastKind, Expr, Query, UnaryOperator, Value, ValueWithSpan};
 
 #[cfg(feature = "time")]
 use time::{
@@ -12,6 +12,7 @@ use time::{
 use crate::{
     Error, Simulator,
     column::Column,
+    dialect::DialectKind,
     resolve::{ColumnRef, ResolvedQuery},
     ty::SqlType,
 };
@@ -89,6 +90,12 @@ pub trait ColumnInferrer {
         qualifier: &str,
         column: &str,
     ) -> Result<Column, Error>;
+
+    /// The reference that every name of the same logical column resolves to,
+    /// so that `u.dept` and `dept` can be recognized as the same column.
+    fn canonical_column(&self, _column: &ColumnRef) -> Option<ColumnRef> {
+        None
+    }
 }
 
 impl Simulator {
@@ -102,7 +109,7 @@ impl Simulator {
         let mut ctx = context;
 
         // If this expression is grouped, set the constraint and inherited hint.
-        if ctx.grouped.contains(expr) {
+        if Self::is_grouped(expr, ctx.grouped, inferrer) {
             ctx.constraints.scope = Some(Scope::Group);
             ctx.hints.scope = Some(Scope::Group);
         }
@@ -110,7 +117,7 @@ impl Simulator {
         let constraints = ctx.constraints.clone();
 
         let inferred: InferredColumn = match expr {
-            Expr::Value(val) => Self::infer_value_column(&val.value, &ctx, resolved)?,
+            Expr::Value(val) => Self::infer_value_column(val, &ctx, resolved)?,
             Expr::IsTrue(expr) | Expr::IsFalse(expr) => {
                 ctx.constraints.ty = Some(SqlType::Boolean);
 
@@ -160,7 +167,7 @@ impl Simulator {
                 right_ctx.constraints.ty = Some(left_infer.column.ty.clone());
                 let right_infer = self.infer_expr_column(right, right_ctx, inferrer, resolved)?;
 
-                assert_eq!(left_infer.column.ty, right_infer.column.ty);
+                assert!(left_infer.column.ty.is_compatible(&right_infer.column.ty));
 
                 let scope = left_infer.scope.combine(&right_infer.scope)?;
 
@@ -197,7 +204,7 @@ impl Simulator {
                 let mut scope = str_infer.scope;
                 let mut nullable = str_infer.column.nullable;
 
-                from_ctx.constraints.ty = None;
+                from_ctx.constraints.ty = Some(SqlType::Integer);
                 from_ctx.constraints.nullable = Some(nullable);
 
                 // Ensure that the from is an integer.
@@ -213,7 +220,7 @@ impl Simulator {
                     nullable |= from_infer.column.nullable;
                 }
 
-                for_ctx.constraints.ty = None;
+                for_ctx.constraints.ty = Some(SqlType::Integer);
                 for_ctx.constraints.nullable = Some(nullable);
 
                 // Ensure that the for is an integer.
@@ -234,6 +241,62 @@ impl Simulator {
                     scope,
                 }
             }
+            Expr::Trim {
+                expr,
+                trim_what,
+                trim_characters,
+                ..
+            } => {
+                ctx.constraints.ty = Some(SqlType::Text);
+                ctx.constraints.scope = Some(Scope::Row);
+
+                let mut nullable = false;
+                let mut scope = Scope::Literal;
+
+                let trimmed = trim_what
+                    .as_deref()
+                    .into_iter()
+                    .chain(trim_characters.iter().flatten());
+
+                for e in std::iter::once(expr.as_ref()).chain(trimmed) {
+                    let infer = self.infer_expr_column(e, ctx.clone(), inferrer, resolved)?;
+
+                    scope = scope.combine(&infer.scope)?;
+                    nullable |= infer.column.nullable;
+                }
+
+                InferredColumn {
+                    column: Column::new(SqlType::Text, nullable, false),
+                    scope,
+                }
+            }
+            // `ceil(x)` and `floor(x)` are parsed as their own expressions.
+            Expr::Ceil { expr, .. } | Expr::Floor { expr, .. } => {
+                let fallback_ty = match ctx.constraints.ty.take() {
+                    Some(ty) if ty.is_numeric() => ty,
+                    _ => SqlType::Double,
+                };
+                ctx.constraints.nullable = None;
+
+                let infer = self.infer_operand(expr, &fallback_ty, ctx, inferrer, resolved)?;
+
+                if !infer.column.ty.is_numeric() {
+                    return Err(Error::TypeNotNumeric(infer.column.ty));
+                }
+
+                infer
+            }
+            #[cfg(feature = "time")]
+            Expr::Interval(interval) => {
+                ctx.constraints.ty = Some(SqlType::Text);
+
+                let infer = self.infer_expr_column(&interval.value, ctx, inferrer, resolved)?;
+
+                InferredColumn {
+                    column: Column::new(SqlType::Interval, infer.column.nullable, false),
+                    scope: infer.scope,
+                }
+            }
             Expr::Identifier(ident) => {
                 let name = &ident.value;
 
@@ -277,10 +340,12 @@ impl Simulator {
                 let mut list_item_ctx = ctx.clone();
                 let infer = self.infer_expr_column(expr, ctx, inferrer, resolved)?;
 
-                let mut nullable = false;
+                let mut nullable = infer.column.nullable;
                 let mut scope = infer.scope;
 
+                // Items may be NULL, which just makes the result nullable.
                 list_item_ctx.constraints.ty = Some(infer.column.ty.clone());
+                list_item_ctx.constraints.nullable = None;
 
                 for item in list {
                     let inner_infer =
@@ -303,14 +368,20 @@ impl Simulator {
                 data_type,
                 ..
             } => {
-                let ty: SqlType = data_type.clone().into();
+                let ty = self.sql_type(data_type);
 
                 match kind {
                     CastKind::Cast | CastKind::DoubleColon => {
-                        // TODO: Ensure the two types are castable.
                         ctx.constraints.ty = None;
                         let infer = self.infer_expr_column(expr, ctx, inferrer, resolved)?;
 
+                        if !infer.column.ty.is_castable_to(&ty) {
+                            return Err(Error::InvalidCast {
+                                from: infer.column.ty,
+                                to: ty,
+                            });
+                        }
+
                         InferredColumn {
                             column: Column::new(ty, infer.column.nullable, infer.column.default),
                             scope: infer.scope,
@@ -375,36 +446,162 @@ impl Simulator {
                     }
                 }
             },
+            Expr::Array(array) => {
+                // Elements take the type of the expected array, otherwise the type of the first one.
+                let mut elem_ty = match ctx.constraints.ty.as_ref() {
+                    Some(SqlType::Array(inner)) => Some(inner.as_ref().clone()),
+                    _ => None,
+                };
+
+                let mut scope = Scope::Literal;
+                for elem in &array.elem {
+                    let mut elem_ctx = ctx.clone();
+                    elem_ctx.constraints.ty = elem_ty.clone();
+                    elem_ctx.constraints.nullable = None;
+
+                    let infer = self.infer_expr_column(elem, elem_ctx, inferrer, resolved)?;
+
+                    scope = scope.combine(&infer.scope)?;
+                    elem_ty.get_or_insert(infer.column.ty);
+                }
+
+                let Some(elem_ty) = elem_ty else {
+                    return Err(Error::Unsupported(
+                        "Cannot infer the type of an empty ARRAY".to_string(),
+                    ));
+                };
+
+                InferredColumn {
+                    column: Column::new(SqlType::Array(Box::new(elem_ty)), false, false),
+                    scope,
+                }
+            }
+            #[cfg(feature = "time")]
+            Expr::Extract { expr, .. } => self.sql_extract(expr, ctx, inferrer, resolved)?,
             Expr::Function(func) => self.infer_function_column(func, ctx, inferrer, resolved)?,
             Expr::Subquery(query) => {
-                // TODO: Need to be able to take in current inferrer
-                // and use that to resolve columns.
-                //
-                // This is becuase the subquery CAN use aliases from the parent scope.
-                let resolved_query = self.query(query)?;
+                let mut column = self.infer_subquery_column(query, inferrer, resolved)?;
+
+                // A scalar subquery without any rows is NULL.
+                column.nullable = true;
+
+                InferredColumn {
+                    column,
+                    scope: ctx.constraints.scope.unwrap_or(Scope::Literal),
+                }
+            }
+            Expr::InSubquery { expr, subquery, .. } => {
+                let query = Query {
+                    with: None,
+                    body: subquery.clone(),
+                    order_by: None,
+                    limit_clause: None,
+                    fetch: None,
+                    locks: vec![],
+                    for_clause: None,
+                    settings: None,
+                    format_clause: None,
+                    pipe_operators: vec![],
+                };
+
+                let subquery_column = self.infer_subquery_column(&query, inferrer, resolved)?;
+
+                let (expected, subquery_nullable) = match &subquery_column.ty {
+                    SqlType::Tuple(cols) => (cols.len(), cols.iter().any(|c| c.nullable)),
+                    _ => (1, subquery_column.nullable),
+                };
+
+                let got = match expr.as_ref() {
+                    Expr::Tuple(exprs) => exprs.len(),
+                    _ => 1,
+                };
+
+                // The left side must match the subquery columns by position.
+                if expected != got {
+                    return Err(Error::ColumnCountMismatch { expected, got });
+                }
+
+                ctx.constraints.ty = Some(subquery_column.ty);
+                let infer = self.infer_expr_column(expr, ctx, inferrer, resolved)?;
 
-                // Add inputs
-                for input in resolved_query.inputs {
-                    resolved.insert_input("?", input);
+                InferredColumn {
+                    column: Column::new(
+                        SqlType::Boolean,
+                        infer.column.nullable || subquery_nullable,
+                        false,
+                    ),
+                    scope: infer.scope,
+                }
+            }
+            Expr::AnyOp {
+                left,
+                compare_op,
+                right,
+                ..
+            }
+            | Expr::AllOp {
+                left,
+                compare_op,
+                right,
+            } => {
+                if !matches!(
+                    compare_op,
+                    BinaryOperator::Eq
+                        | BinaryOperator::NotEq
+                        | BinaryOperator::Gt
+                        | BinaryOperator::Lt
+                        | BinaryOperator::GtEq
+                        | BinaryOperator::LtEq
+                ) {
+                    return Err(Error::Unsupported(format!(
+                        "Unsupported operator for ANY/ALL: {compare_op}"
+                    )));
                 }
 
-                // Map outputs
-                let column = match resolved_query.outputs.len() {
-                    0 => return Err(Error::SubqueryNoColumns),
-                    1 => resolved_query.outputs.get_index(0).unwrap().1.clone(),
+                ctx.constraints.ty = None;
+                ctx.constraints.nullable = None;
+
+                let (left_infer, right_nullable, right_scope) = match right.as_ref() {
+                    // The subquery yields the values to compare against.
+                    Expr::Subquery(subquery) => {
+                        let subquery_column =
+                            self.infer_subquery_column(subquery, inferrer, resolved)?;
+
+                        if let SqlType::Tuple(cols) = &subquery_column.ty {
+                            return Err(Error::ColumnCountMismatch {
+                                expected: 1,
+                                got: cols.len(),
+                            });
+                        }
+
+                        ctx.constraints.ty = Some(subquery_column.ty);
+                        let left_infer = self.infer_expr_column(left, ctx, inferrer, resolved)?;
+
+                        (left_infer, subquery_column.nullable, Scope::Literal)
+                    }
+                    // Otherwise it is an array of the left side's type.
                     _ => {
-                        let columns: Vec<_> =
-                            resolved_query.outputs.clone().into_values().collect();
-                        Column::new(SqlType::Tuple(columns), false, false)
+                        let mut right_ctx = ctx.clone();
+                        let left_infer = self.infer_expr_column(left, ctx, inferrer, resolved)?;
+
+                        right_ctx.constraints.ty =
+                            Some(SqlType::Array(Box::new(left_infer.column.ty.clone())));
+                        let right_infer =
+                            self.infer_expr_column(right, right_ctx, inferrer, resolved)?;
+
+                        (left_infer, right_infer.column.nullable, right_infer.scope)
                     }
                 };
 
                 InferredColumn {
-                    column,
-                    scope: ctx.constraints.scope.unwrap_or(Scope::Literal),
+                    column: Column::new(
+                        SqlType::Boolean,
+                        left_infer.column.nullable || right_nullable,
+                        false,
+                    ),
+                    scope: left_infer.scope.combine(&right_scope)?,
                 }
             }
-            // TODO: Expr::InSubquery()
             Expr::Between {
                 expr, low, high, ..
             } => {
@@ -434,8 +631,8 @@ impl Simulator {
                 scope = scope.combine(&high_infer.scope)?;
                 nullable |= high_infer.column.nullable;
 
-                assert_eq!(value_infer.column.ty, low_infer.column.ty);
-                assert_eq!(value_infer.column.ty, high_infer.column.ty);
+                assert!(value_infer.column.ty.is_compatible(&low_infer.column.ty));
+                assert!(value_infer.column.ty.is_compatible(&high_infer.column.ty));
 
                 // TODO: Only allow integers, text and dates.
 
@@ -530,12 +727,12 @@ impl Simulator {
                     scope,
                 }
             }
-            _ => return Err(Error::Unsupported(format!("Unsupported Expr: {expr:#?}"))),
+            _ => return Err(Error::Unsupported(format!("Unsupported Expr: {expr}"))),
         };
 
         // Check the type here.
         if let Some(expected_ty) = constraints.ty
-            && expected_ty != inferred.column.ty
+            && !expected_ty.is_compatible(&inferred.column.ty)
         {
             return Err(Error::TypeMismatch {
                 expected: expected_ty,
@@ -556,6 +753,65 @@ impl Simulator {
         Ok(inferred)
     }
 
+    /// Resolves a subquery used as an expression into a single column.
+    ///
+    /// Subqueries with multiple output columns collapse into a Tuple.
+    /// The subquery can reference the columns of the enclosing query.
+    fn infer_subquery_column<I: ColumnInferrer>(
+        &self,
+        query: &Query,
+        inferrer: &I,
+        resolved: &mut ResolvedQuery,
+    ) -> Result<Column, Error> {
+        let resolved_query = self.correlated_query(query, inferrer)?;
+
+        // Map outputs
+        let column = match resolved_query.outputs.len() {
+            0 => return Err(Error::SubqueryNoColumns),
+            1 => resolved_query.outputs.get_index(0).unwrap().1.clone(),
+            _ => {
+                let columns: Vec<_> = resolved_query.outputs.values().cloned().collect();
+                Column::new(SqlType::Tuple(columns), false, false)
+            }
+        };
+
+        // Add inputs, keeping the placeholders they came from.
+        resolved.merge_inputs(resolved_query);
+
+        Ok(column)
+    }
+
+    /// Whether the expression is one of the grouped ones.
+    ///
+    /// Column references are compared by the logical column they resolve to.
+    fn is_grouped<I: ColumnInferrer>(expr: &Expr, grouped: &[Expr], inferrer: &I) -> bool {
+        if grouped.contains(expr) {
+            return true;
+        }
+
+        let Some(column) = Self::canonical_column_of(expr, inferrer) else {
+            return false;
+        };
+
+        grouped
+            .iter()
+            .any(|g| Self::canonical_column_of(g, inferrer).as_ref() == Some(&column))
+    }
+
+    /// The logical column that a column reference resolves to.
+    pub(crate) fn canonical_column_of<I: ColumnInferrer>(
+        expr: &Expr,
+        inferrer: &I,
+    ) -> Option<ColumnRef> {
+        match expr {
+            Expr::Identifier(_) | Expr::CompoundIdentifier(_) => Self::infer_expr_name(expr)
+                .ok()
+                .flatten()
+                .and_then(|name| inferrer.canonical_column(&name)),
+            _ => None,
+        }
+    }
+
     pub(crate) fn infer_expr_name(expr: &Expr) -> Result<Option<ColumnRef>, Error> {
         match expr {
             Expr::Identifier(ident) => Ok(Some(ColumnRef::new(None, ident.value.to_string()))),
@@ -570,11 +826,11 @@ impl Simulator {
     }
 
     pub(crate) fn infer_value_column(
-        value: &Value,
+        value: &ValueWithSpan,
         context: &InferContext,
         resolved: &mut ResolvedQuery,
     ) -> Result<InferredColumn, Error> {
-        match value {
+        match &value.value {
             Value::Number(str, _) => {
                 // Initially, try to use the expected type.
                 if let Some(ref expected_ty) = context.constraints.ty {
@@ -614,6 +870,14 @@ impl Simulator {
                                 None
                             }
                         }
+                        #[cfg(feature = "decimal")]
+                        SqlType::Decimal { .. } => {
+                            if str.parse::<f64>().is_ok() {
+                                Some(expected_ty.clone())
+                            } else {
+                                None
+                            }
+                        }
                         _ => None,
                     };
 
@@ -688,12 +952,31 @@ impl Simulator {
                         SqlType::Date => Date::parse(str, &Iso8601::DEFAULT)
                             .ok()
                             .map(|_| SqlType::Date),
+                        // Interval syntax varies a lot, the database validates it.
+                        #[cfg(feature = "time")]
+                        SqlType::Interval => Some(SqlType::Interval),
                         #[cfg(feature = "uuid")]
                         SqlType::Uuid => uuid::Uuid::parse_str(str).ok().map(|_| SqlType::Uuid),
                         #[cfg(feature = "json")]
-                        SqlType::Json => serde_json::from_str::<serde::de::IgnoredAny>(str)
-                            .ok()
-                            .map(|_| SqlType::Json),
+                        SqlType::Json | SqlType::Jsonb => {
+                            serde_json::from_str::<serde::de::IgnoredAny>(str)
+                                .ok()
+                                .map(|_| expected_ty.clone())
+                        }
+                        #[cfg(feature = "ipnetwork")]
+                        SqlType::Inet | SqlType::Cidr => {
+                            is_network_literal(str).then(|| expected_ty.clone())
+                        }
+                        SqlType::Enum { name, variants } => {
+                            if !variants.contains(str) {
+                                return Err(Error::InvalidEnumVariant {
+                                    name: name.clone(),
+                                    variant: str.clone(),
+                                });
+                            }
+
+                            Some(expected_ty.clone())
+                        }
                         _ => None,
                     }
                 } else {
@@ -720,7 +1003,7 @@ impl Simulator {
 
                     Ok(InferredColumn {
                         column: Column::new(ty.clone(), true, false),
-                        scope: Scope::Row,
+                        scope: Scope::Literal,
                     })
                 } else {
                     Err(Error::Unsupported(
@@ -736,18 +1019,19 @@ impl Simulator {
                         context.hints.default.unwrap_or(false),
                     );
 
-                    resolved.insert_input(placeholder, col.clone());
+                    resolved.insert_input(placeholder, value.span.start, col.clone());
 
+                    // A placeholder is the same value for every row, like a literal.
                     Ok(InferredColumn {
                         column: col,
-                        scope: Scope::Row,
+                        scope: Scope::Literal,
                     })
                 }
                 None => Err(Error::Unsupported(
                     "Cannot infer type of the placeholder".to_string(),
                 )),
             },
-            _ => Err(Error::Unsupported(format!("Unsupported value: {value:?}"))),
+            _ => Err(Error::Unsupported(format!("Unsupported value: {value}"))),
         }
     }
 
@@ -771,18 +1055,70 @@ impl Simulator {
             | BinaryOperator::Multiply
             | BinaryOperator::Divide
             | BinaryOperator::Modulo => {
-                let mut right_ctx = ctx.clone();
-                let left_infer = self.infer_expr_column(left, ctx, inferrer, resolved)?;
-                right_ctx.inherit_constraints_from_inferred_column(&left_infer);
-                let right_infer = self.infer_expr_column(right, right_ctx, inferrer, resolved)?;
+                let expected_ty = ctx.constraints.ty.take();
+
+                let (left_infer, right_infer) = if is_untyped(left) && expected_ty.is_none() {
+                    // The left side needs a type (eg. a placeholder), so it takes the type
+                    // of the right side.
+                    let right_infer =
+                        self.infer_expr_column(right, ctx.clone(), inferrer, resolved)?;
 
-                assert_eq!(left_infer.column.ty, right_infer.column.ty);
+                    let mut left_ctx = ctx;
+                    left_ctx.constraints.ty = Some(right_infer.column.ty.clone());
+                    let left_infer = self.infer_expr_column(left, left_ctx, inferrer, resolved)?;
+
+                    (left_infer, right_infer)
+                } else {
+                    // Or the expected type.
+                    let mut left_ctx = ctx.clone();
+                    if is_untyped(left) {
+                        left_ctx.constraints.ty = expected_ty;
+                    }
+                    let left_infer = self.infer_expr_column(left, left_ctx, inferrer, resolved)?;
+
+                    // Date/time values are offset by an interval.
+                    let right_ty = match &left_infer.column.ty {
+                        #[cfg(feature = "time")]
+                        ty if ty.is_temporal()
+                            && matches!(op, BinaryOperator::Plus | BinaryOperator::Minus) =>
+                        {
+                            SqlType::Interval
+                        }
+                        ty => ty.clone(),
+                    };
+
+                    let right_infer =
+                        self.infer_operand(right, &right_ty, ctx, inferrer, resolved)?;
+
+                    (left_infer, right_infer)
+                };
+
+                let left_ty = &left_infer.column.ty;
+                let right_ty = &right_infer.column.ty;
+
+                // Only numbers are promoted, anything else needs a date/time rule.
+                let ty = temporal_arithmetic(op, left_ty, right_ty)
+                    .or_else(|| {
+                        (left_ty.is_numeric() && right_ty.is_numeric())
+                            .then(|| left_ty.promote(right_ty))
+                            .flatten()
+                    })
+                    .ok_or_else(|| {
+                        if left_ty.is_numeric() || right_ty.is_numeric() || left_ty.is_temporal() {
+                            Error::TypeMismatch {
+                                expected: left_ty.clone(),
+                                got: right_ty.clone(),
+                            }
+                        } else {
+                            Error::TypeNotNumeric(left_ty.clone())
+                        }
+                    })?;
 
                 let nullable = left_infer.column.nullable | right_infer.column.nullable;
                 let scope = left_infer.scope.combine(&right_infer.scope)?;
 
                 Ok(InferredColumn {
-                    column: Column::new(left_infer.column.ty, nullable, false),
+                    column: Column::new(ty, nullable, false),
                     scope,
                 })
             }
@@ -793,13 +1129,9 @@ impl Simulator {
             | BinaryOperator::Eq
             | BinaryOperator::NotEq => {
                 ctx.constraints.ty = None;
-                let mut right_ctx = ctx.clone();
 
-                let left_infer = self.infer_expr_column(left, ctx, inferrer, resolved)?;
-                right_ctx.inherit_constraints_from_inferred_column(&left_infer);
-                let right_infer = self.infer_expr_column(right, right_ctx, inferrer, resolved)?;
-
-                assert_eq!(left_infer.column.ty, right_infer.column.ty);
+                let (left_infer, right_infer) =
+                    self.infer_comparison_operands([left, right], ctx, inferrer, resolved)?;
 
                 // Resulting column is only nullable if either of the two are.
                 let nullable = left_infer.column.nullable | right_infer.column.nullable;
@@ -812,13 +1144,9 @@ impl Simulator {
             }
             BinaryOperator::Spaceship => {
                 ctx.constraints.ty = None;
-                let mut right_ctx = ctx.clone();
-
-                let left_infer = self.infer_expr_column(left, ctx, inferrer, resolved)?;
-                right_ctx.inherit_constraints_from_inferred_column(&left_infer);
-                let right_infer = self.infer_expr_column(right, right_ctx, inferrer, resolved)?;
 
-                assert_eq!(left_infer.column.ty, right_infer.column.ty);
+                let (left_infer, right_infer) =
+                    self.infer_comparison_operands([left, right], ctx, inferrer, resolved)?;
 
                 let scope = left_infer.scope.combine(&right_infer.scope)?;
 
@@ -831,15 +1159,164 @@ impl Simulator {
                     scope,
                 })
             }
+            // Under three-valued logic `false AND NULL` is false and `true OR NULL` is true,
+            // but we don't track values so the result is nullable if either side is.
             BinaryOperator::And | BinaryOperator::Or | BinaryOperator::Xor => {
+                ctx.constraints.ty = Some(SqlType::Boolean);
+                ctx.constraints.nullable = None;
+                let right_ctx = ctx.clone();
+
+                let left_infer = self.infer_expr_column(left, ctx, inferrer, resolved)?;
+                let right_infer = self.infer_expr_column(right, right_ctx, inferrer, resolved)?;
+
+                let nullable = left_infer.column.nullable | right_infer.column.nullable;
+                let scope = left_infer.scope.combine(&right_infer.scope)?;
+
+                Ok(InferredColumn {
+                    column: Column::new(SqlType::Boolean, nullable, false),
+                    scope,
+                })
+            }
+            // `~~`, `~~*`, `!~~` and `!~~*` are the operator forms of (NOT) LIKE/ILIKE.
+            BinaryOperator::PGLikeMatch
+            | BinaryOperator::PGILikeMatch
+            | BinaryOperator::PGNotLikeMatch
+            | BinaryOperator::PGNotILikeMatch => {
+                ctx.constraints.ty = Some(SqlType::Text);
+                ctx.constraints.nullable = None;
+                let right_ctx = ctx.clone();
+
+                let left_infer = self.infer_expr_column(left, ctx, inferrer, resolved)?;
+                let right_infer = self.infer_expr_column(right, right_ctx, inferrer, resolved)?;
+
+                let nullable = left_infer.column.nullable | right_infer.column.nullable;
+                let scope = left_infer.scope.combine(&right_infer.scope)?;
+
+                Ok(InferredColumn {
+                    column: Column::new(SqlType::Boolean, nullable, false),
+                    scope,
+                })
+            }
+            BinaryOperator::PGBitwiseShiftLeft | BinaryOperator::PGBitwiseShiftRight => {
                 ctx.constraints.ty = None;
-                let mut right_ctx = ctx.clone();
+                ctx.constraints.nullable = None;
+
+                // A placeholder takes the type of the other side.
+                let (first, second) = if is_untyped(left) {
+                    (right, left)
+                } else {
+                    (left, right)
+                };
+
+                let first_infer = self.infer_expr_column(first, ctx.clone(), inferrer, resolved)?;
+                let second_ty = if first_infer.column.ty.is_network() {
+                    first_infer.column.ty.clone()
+                } else {
+                    SqlType::Integer
+                };
+                let second_infer =
+                    self.infer_operand(second, &second_ty, ctx, inferrer, resolved)?;
+
+                let (left_infer, right_infer) = if is_untyped(left) {
+                    (second_infer, first_infer)
+                } else {
+                    (first_infer, second_infer)
+                };
+
+                let nullable = left_infer.column.nullable | right_infer.column.nullable;
+                let scope = left_infer.scope.combine(&right_infer.scope)?;
+
+                // Network containment in Postgres, `<<` is contained by and `>>` contains.
+                #[cfg(feature = "ipnetwork")]
+                if matches!(self.dialect.kind(), DialectKind::Postgres)
+                    && (left_infer.column.ty.is_network() || right_infer.column.ty.is_network())
+                {
+                    for infer in [&left_infer, &right_infer] {
+                        if !infer.column.ty.is_network() {
+                            return Err(Error::TypeMismatch {
+                                expected: SqlType::Inet,
+                                got: infer.column.ty.clone(),
+                            });
+                        }
+                    }
 
+                    return Ok(InferredColumn {
+                        column: Column::new(SqlType::Boolean, nullable, false),
+                        scope,
+                    });
+                }
+
+                for infer in [&left_infer, &right_infer] {
+                    if !infer.column.ty.is_integer() {
+                        return Err(Error::TypeMismatch {
+                            expected: SqlType::Integer,
+                            got: infer.column.ty.clone(),
+                        });
+                    }
+                }
+
+                Ok(InferredColumn {
+                    column: Column::new(left_infer.column.ty, nullable, false),
+                    scope,
+                })
+            }
+            // Array and JSONB containment.
+            BinaryOperator::AtArrow | BinaryOperator::ArrowAt => {
+                ctx.constraints.ty = None;
+                let mut right_ctx = ctx.clone();
                 let left_infer = self.infer_expr_column(left, ctx, inferrer, resolved)?;
+
+                match &left_infer.column.ty {
+                    SqlType::Array(_) => {}
+                    #[cfg(feature = "json")]
+                    SqlType::Jsonb => {}
+                    #[cfg(feature = "json")]
+                    SqlType::Json => {
+                        return Err(Error::TypeMismatch {
+                            expected: SqlType::Jsonb,
+                            got: SqlType::Json,
+                        });
+                    }
+                    ty => {
+                        return Err(Error::TypeMismatch {
+                            expected: SqlType::Array(Box::new(ty.clone())),
+                            got: ty.clone(),
+                        });
+                    }
+                }
+
                 right_ctx.inherit_constraints_from_inferred_column(&left_infer);
                 let right_infer = self.infer_expr_column(right, right_ctx, inferrer, resolved)?;
 
-                assert_eq!(left_infer.column.ty, right_infer.column.ty);
+                let nullable = left_infer.column.nullable | right_infer.column.nullable;
+                let scope = left_infer.scope.combine(&right_infer.scope)?;
+
+                Ok(InferredColumn {
+                    column: Column::new(SqlType::Boolean, nullable, false),
+                    scope,
+                })
+            }
+            // JSONB key existence and jsonpath matching.
+            #[cfg(feature = "json")]
+            BinaryOperator::Question
+            | BinaryOperator::QuestionPipe
+            | BinaryOperator::QuestionAnd
+            | BinaryOperator::AtQuestion
+            | BinaryOperator::AtAt => {
+                ctx.constraints.ty = Some(SqlType::Jsonb);
+                ctx.constraints.nullable = None;
+                let mut right_ctx = ctx.clone();
+
+                let left_infer = self.infer_expr_column(left, ctx, inferrer, resolved)?;
+
+                // `?|` and `?&` take an array of keys.
+                right_ctx.constraints.ty = match op {
+                    BinaryOperator::QuestionPipe | BinaryOperator::QuestionAnd => {
+                        Some(SqlType::Array(Box::new(SqlType::Text)))
+                    }
+                    _ => Some(SqlType::Text),
+                };
+                let right_infer = self.infer_expr_column(right, right_ctx, inferrer, resolved)?;
 
                 let nullable = left_infer.column.nullable | right_infer.column.nullable;
                 let scope = left_infer.scope.combine(&right_infer.scope)?;
@@ -849,6 +1326,54 @@ impl Simulator {
                     scope,
                 })
             }
+            // JSON field access by key or index, `->>` extracts it as text.
+            #[cfg(feature = "json")]
+            BinaryOperator::Arrow | BinaryOperator::LongArrow => {
+                // Only Postgres and SQLite (3.38+) have the JSON arrow operators.
+                if !matches!(
+                    self.dialect.kind(),
+                    DialectKind::Postgres | DialectKind::Sqlite
+                ) {
+                    return Err(Error::Unsupported(format!(
+                        "JSON operator {op} is not supported by this dialect"
+                    )));
+                }
+
+                ctx.constraints.ty = None;
+                ctx.constraints.nullable = None;
+                let right_ctx = ctx.clone();
+
+                let left_infer =
+                    self.infer_operand(left, &SqlType::Json, ctx, inferrer, resolved)?;
+
+                if !left_infer.column.ty.is_json() {
+                    return Err(Error::TypeMismatch {
+                        expected: SqlType::Json,
+                        got: left_infer.column.ty,
+                    });
+                }
+
+                let right_infer =
+                    self.infer_operand(right, &SqlType::Text, right_ctx, inferrer, resolved)?;
+
+                if !(right_infer.column.ty.is_text() || right_infer.column.ty.is_integer()) {
+                    return Err(Error::TypeMismatch {
+                        expected: SqlType::Text,
+                        got: right_infer.column.ty,
+                    });
+                }
+
+                let ty = match op {
+                    BinaryOperator::Arrow => left_infer.column.ty.clone(),
+                    _ => SqlType::Text,
+                };
+
+                // The key might not exist.
+                Ok(InferredColumn {
+                    column: Column::new(ty, true, false),
+                    scope: left_infer.scope.combine(&right_infer.scope)?,
+                })
+            }
             BinaryOperator::BitwiseOr | BinaryOperator::BitwiseAnd | BinaryOperator::BitwiseXor => {
                 let mut right_ctx = ctx.clone();
                 let left_infer = self.infer_expr_column(left, ctx, inferrer, resolved)?;
@@ -863,7 +1388,7 @@ impl Simulator {
                 right_ctx.inherit_constraints_from_inferred_column(&left_infer);
                 let right_infer = self.infer_expr_column(right, right_ctx, inferrer, resolved)?;
 
-                assert_eq!(left_infer.column.ty, right_infer.column.ty);
+                assert!(left_infer.column.ty.is_compatible(&right_infer.column.ty));
 
                 let nullable = left_infer.column.nullable | right_infer.column.nullable;
                 let scope = left_infer.scope.combine(&right_infer.scope)?;
@@ -874,27 +1399,129 @@ impl Simulator {
                 })
             }
             BinaryOperator::StringConcat => {
-                ctx.constraints.ty = Some(SqlType::Text);
-                let mut right_ctx = ctx.clone();
+                // Postgres and SQLite implicitly convert the operands to text.
+                let (left_infer, right_infer) = if matches!(
+                    self.dialect.kind(),
+                    DialectKind::Postgres | DialectKind::Sqlite | DialectKind::Generic
+                ) {
+                    ctx.constraints.ty = None;
 
-                let left_infer = self.infer_expr_column(left, ctx, inferrer, resolved)?;
-                right_ctx.inherit_constraints_from_inferred_column(&left_infer);
-                let right_infer = self.infer_expr_column(right, right_ctx, inferrer, resolved)?;
+                    let left_infer =
+                        self.infer_operand(left, &SqlType::Text, ctx.clone(), inferrer, resolved)?;
+
+                    // A placeholder concatenated to an array or jsonb takes the same type.
+                    let fallback_ty = match &left_infer.column.ty {
+                        ty @ SqlType::Array(_) => ty.clone(),
+                        #[cfg(feature = "json")]
+                        SqlType::Jsonb => SqlType::Jsonb,
+                        _ => SqlType::Text,
+                    };
+                    let right_infer =
+                        self.infer_operand(right, &fallback_ty, ctx, inferrer, resolved)?;
+
+                    for infer in [&left_infer, &right_infer] {
+                        if let SqlType::Tuple(_) = infer.column.ty {
+                            return Err(Error::TypeMismatch {
+                                expected: SqlType::Text,
+                                got: infer.column.ty.clone(),
+                            });
+                        }
+                    }
+
+                    (left_infer, right_infer)
+                } else {
+                    ctx.constraints.ty = Some(SqlType::Text);
+                    let mut right_ctx = ctx.clone();
+
+                    let left_infer = self.infer_expr_column(left, ctx, inferrer, resolved)?;
+                    right_ctx.inherit_constraints_from_inferred_column(&left_infer);
+                    let right_infer =
+                        self.infer_expr_column(right, right_ctx, inferrer, resolved)?;
+
+                    (left_infer, right_infer)
+                };
+
+                let ty = match self.dialect.kind() {
+                    DialectKind::Postgres => {
+                        concat_type(&left_infer.column.ty, &right_infer.column.ty)?
+                    }
+                    _ => SqlType::Text,
+                };
 
                 let nullable = left_infer.column.nullable | right_infer.column.nullable;
                 let scope = left_infer.scope.combine(&right_infer.scope)?;
 
                 Ok(InferredColumn {
-                    column: Column::new(SqlType::Text, nullable, false),
+                    column: Column::new(ty, nullable, false),
                     scope,
                 })
             }
             _ => Err(Error::Unsupported(format!(
-                "Unsupported binary operator: {op:?}"
+                "Unsupported binary operator: {op}"
             ))),
         }
     }
 
+    /// Infers both sides of a comparison, the right side takes the type of the left side.
+    ///
+    /// An integer literal on the left is widened to the numeric type of the right side,
+    /// so `5 = big` resolves the same as `big = 5`.
+    fn infer_comparison_operands<I: ColumnInferrer>(
+        &self,
+        exprs: [&Expr; 2],
+        context: InferContext,
+        inferrer: &I,
+        resolved: &mut ResolvedQuery,
+    ) -> Result<(InferredColumn, InferredColumn), Error> {
+        let [left, right] = exprs;
+        let mut right_ctx = context.clone();
+
+        let mut left_infer = self.infer_expr_column(left, context, inferrer, resolved)?;
+
+        if left_infer.scope == Scope::Literal
+            && left_infer.column.ty.is_integer()
+            && !is_untyped(left)
+            && !is_untyped(right)
+        {
+            let right_infer = self.infer_expr_column(right, right_ctx, inferrer, resolved)?;
+
+            match left_infer.column.ty.promote(&right_infer.column.ty) {
+                Some(ty) => left_infer.column.ty = ty,
+                None => {
+                    return Err(Error::TypeMismatch {
+                        expected: left_infer.column.ty,
+                        got: right_infer.column.ty,
+                    });
+                }
+            }
+
+            return Ok((left_infer, right_infer));
+        }
+
+        right_ctx.inherit_constraints_from_inferred_column(&left_infer);
+        let right_infer = self.infer_expr_column(right, right_ctx, inferrer, resolved)?;
+
+        Ok((left_infer, right_infer))
+    }
+
+    /// Infers an operand using its own type if it has one,
+    /// only falling back to the given type when it needs it (eg. a placeholder).
+    pub(crate) fn infer_operand<I: ColumnInferrer>(
+        &self,
+        expr: &Expr,
+        fallback_ty: &SqlType,
+        context: InferContext,
+        inferrer: &I,
+        resolved: &mut ResolvedQuery,
+    ) -> Result<InferredColumn, Error> {
+        let mut ctx = context;
+        if is_untyped(expr) {
+            ctx.constraints.ty = Some(fallback_ty.clone());
+        }
+
+        self.infer_expr_column(expr, ctx, inferrer, resolved)
+    }
+
     fn infer_unary_op_column<I: ColumnInferrer>(
         &self,
         expr: &Expr,
@@ -922,8 +1549,108 @@ impl Simulator {
                 Ok(infer)
             }
             _ => Err(Error::Unsupported(format!(
-                "Unsupported unary operator: {op:?}"
+                "Unsupported unary operator: {op}"
             ))),
         }
     }
 }
+
+/// The result type of adding or subtracting date/time values, which aren't numeric.
+fn temporal_arithmetic(op: &BinaryOperator, left: &SqlType, right: &SqlType) -> Option<SqlType> {
+    #[cfg(feature = "time")]
+    {
+        match (op, left, right) {
+            (
+                BinaryOperator::Plus | BinaryOperator::Minus,
+                SqlType::Timestamp | SqlType::TimestampTz | SqlType::Time,
+                SqlType::Interval,
+            ) => Some(left.clone()),
+            (
+                BinaryOperator::Plus,
+                SqlType::Interval,
+                SqlType::Timestamp | SqlType::TimestampTz | SqlType::Time,
+            ) => Some(right.clone()),
+            (BinaryOperator::Plus | BinaryOperator::Minus, SqlType::Date, SqlType::Interval)
+            | (BinaryOperator::Plus, SqlType::Interval, SqlType::Date) => Some(SqlType::Timestamp),
+            (BinaryOperator::Minus, SqlType::Timestamp, SqlType::Timestamp)
+            | (BinaryOperator::Minus, SqlType::TimestampTz, SqlType::TimestampTz) => {
+                Some(SqlType::Interval)
+            }
+            (BinaryOperator::Minus, SqlType::Date, SqlType::Date) => Some(SqlType::Integer),
+            // Dates are offset by a number of days.
+            (BinaryOperator::Plus | BinaryOperator::Minus, SqlType::Date, days)
+            | (BinaryOperator::Plus, days, SqlType::Date)
+                if days.is_integer() =>
+            {
+                Some(SqlType::Date)
+            }
+            (BinaryOperator::Minus, SqlType::Time, SqlType::Time) => Some(SqlType::Interval),
+            (
+                BinaryOperator::Plus | BinaryOperator::Minus,
+                SqlType::Interval,
+                SqlType::Interval,
+            ) => Some(SqlType::Interval),
+            _ => None,
+        }
+    }
+    #[cfg(not(feature = "time"))]
+    {
+        _ = (op, left, right);
+        None
+    }
+}
+
+/// Whether the literal is an IP address with an optional prefix length (eg. `10.0.0.0/8`).
+#[cfg(feature = "ipnetwork")]
+fn is_network_literal(literal: &str) -> bool {
+    let (address, prefix) = match literal.split_once('/') {
+        Some((address, prefix)) => (address, Some(prefix)),
+        None => (literal, None),
+    };
+
+    let Ok(address) = address.parse::<std::net::IpAddr>() else {
+        return false;
+    };
+
+    let max = if address.is_ipv4() { 32 } else { 128 };
+    prefix.is_none_or(|prefix| prefix.parse::<u8>().is_ok_and(|prefix| prefix <= max))
+}
+
+/// NULLs and placeholders only get a type from their surroundings.
+pub(crate) fn is_untyped(expr: &Expr) -> bool {
+    match expr {
+        Expr::Value(ValueWithSpan {
+            value: Value::Null | Value::Placeholder(_),
+            ..
+        }) => true,
+        Expr::Nested(expr) | Expr::UnaryOp { expr, .. } => is_untyped(expr),
+        _ => false,
+    }
+}
+
+/// Result type of `||` in Postgres.
+///
+/// Arrays concatenate with arrays or elements, jsonb with jsonb, and anything else
+/// needs at least one text operand to be converted to text.
+fn concat_type(left: &SqlType, right: &SqlType) -> Result<SqlType, Error> {
+    match (left, right) {
+        (SqlType::Array(_), SqlType::Array(_)) if left.is_compatible(right) => Ok(left.clone()),
+        (SqlType::Array(elem), other) | (other, SqlType::Array(elem)) => {
+            if elem.is_compatible(other) {
+                Ok(SqlType::Array(elem.clone()))
+            } else {
+                Err(Error::TypeMismatch {
+                    expected: *elem.clone(),
+                    got: other.clone(),
+                })
+            }
+        }
+        #[cfg(feature = "json")]
+        (SqlType::Jsonb, SqlType::Jsonb) => Ok(SqlType::Jsonb),
+        _ if left.is_text() || right.is_text() => Ok(SqlType::Text),
+        _ => Err(Error::TypeMismatch {
+            expected: SqlType::Text,
+            got: left.clone(),
+        }),
+    }
+}
@@ -1,13 +1,65 @@
-use sqlparser::ast::{Expr, Function, FunctionArg, FunctionArgExpr, FunctionArguments};
+use std::{collections::HashMap, ops::RangeInclusive};
+
+use sqlparser::ast::{
+    DuplicateTreatment, Expr, Function, FunctionArg, FunctionArgExpr, FunctionArguments, WindowType,
+};
 
 use crate::{
     Error, Simulator,
     column::Column,
-    expr::{ColumnInferrer, InferContext, InferredColumn, Scope},
+    dialect::DialectKind,
+    expr::{ColumnInferrer, InferConstraints, InferContext, InferredColumn, Scope, is_untyped},
     resolve::ResolvedQuery,
     ty::SqlType,
 };
 
+/// The arguments that a function accepts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Arity {
+    /// Exactly these arguments.
+    Fixed(Vec<SqlType>),
+    /// The required arguments, followed by any number of the optional ones.
+    Optional {
+        required: Vec<SqlType>,
+        optional: Vec<SqlType>,
+    },
+    /// At least `min` arguments, each castable to the type.
+    Variadic { ty: SqlType, min: usize },
+}
+
+/// When the result of a function is NULL.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Nullability {
+    NotNull,
+    Nullable,
+    /// Only when one of the arguments is NULL.
+    Arguments,
+}
+
+/// The signature of a function that isn't inferred by hand.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FunctionSignature {
+    pub arity: Arity,
+    pub returns: SqlType,
+    pub nullability: Nullability,
+}
+
+/// The functions that can be described by a signature alone.
+pub(crate) fn builtin_functions() -> HashMap<String, FunctionSignature> {
+    HashMap::from([(
+        // NULL arguments are ignored.
+        "concat".to_string(),
+        FunctionSignature {
+            arity: Arity::Variadic {
+                ty: SqlType::Text,
+                min: 1,
+            },
+            returns: SqlType::Text,
+            nullability: Nullability::NotNull,
+        },
+    )])
+}
+
 impl Simulator {
     pub(crate) fn infer_function_column<I: ColumnInferrer>(
         &self,
@@ -18,69 +70,631 @@ impl Simulator {
     ) -> Result<InferredColumn, Error> {
         let func_name = func.name.0.first().unwrap().to_string().to_lowercase();
 
-        match func_name.as_str() {
+        let infer = match func_name.as_str() {
             "count" => self.sql_count(&func.args, context, inferrer, resolved),
             "coalesce" => self.sql_coalesce(&func.args, context, inferrer, resolved),
-            "avg" => self.sql_avg(&func.args, context, inferrer, resolved),
+            "greatest" | "least" => {
+                self.sql_greatest_least(&func_name, &func.args, context, inferrer, resolved)
+            }
+            "sum" | "avg" => self.sql_sum_avg(&func.args, context, inferrer, resolved),
             "min" | "max" => self.sql_min_max(&func.args, context, inferrer, resolved),
-            _ => Err(Error::FunctionDoesntExist(func_name)),
+            "string_agg" | "group_concat" => {
+                self.sql_string_agg(&func_name, &func.args, context, inferrer, resolved)
+            }
+            "row_number" | "rank" | "dense_rank" => self.sql_rank(&func_name, &func.args),
+            "upper" | "lower" => self.sql_string(
+                &func_name,
+                &func.args,
+                1..=1,
+                SqlType::Text,
+                context,
+                inferrer,
+                resolved,
+            ),
+            "trim" | "ltrim" | "rtrim" => self.sql_string(
+                &func_name,
+                &func.args,
+                1..=2,
+                SqlType::Text,
+                context,
+                inferrer,
+                resolved,
+            ),
+            "abs" | "ceil" | "ceiling" | "floor" | "round" | "mod" | "power" | "pow" | "sqrt" => {
+                self.sql_math(&func_name, &func.args, context, inferrer, resolved)
+            }
+            "length" | "char_length" | "character_length" => self.sql_string(
+                &func_name,
+                &func.args,
+                1..=1,
+                SqlType::Integer,
+                context,
+                inferrer,
+                resolved,
+            ),
+            #[cfg(feature = "time")]
+            "age" => self.sql_age(&func.args, context, inferrer, resolved),
+            #[cfg(feature = "time")]
+            "date_part" => self.sql_date_part(&func.args, context, inferrer, resolved),
+            #[cfg(feature = "time")]
+            "now" | "current_timestamp" | "localtimestamp" | "current_date" | "current_time"
+            | "localtime" => self.sql_current_time(&func_name, &func.args, context),
+            _ => match self.functions.get(&func_name) {
+                Some(signature) => self.infer_signature(
+                    &func_name, signature, &func.args, context, inferrer, resolved,
+                ),
+                None => Err(Error::FunctionDoesntExist(func_name.clone())),
+            },
+        }?;
+
+        let is_aggregate = infer.scope == Scope::Group;
+
+        // DISTINCT only changes which rows are aggregated, not the resulting type.
+        if let FunctionArguments::List(list) = &func.args
+            && matches!(list.duplicate_treatment, Some(DuplicateTreatment::Distinct))
+            && !is_aggregate
+        {
+            return Err(Error::FunctionCall(format!(
+                "DISTINCT used on non-aggregate function {func_name}"
+            )));
+        }
+
+        // FILTER (WHERE ...) is evaluated per row before aggregation.
+        if let Some(filter) = &func.filter {
+            if !is_aggregate {
+                return Err(Error::FunctionCall(format!(
+                    "FILTER used on non-aggregate function {func_name}"
+                )));
+            }
+
+            self.infer_expr_column(
+                filter,
+                InferContext {
+                    constraints: InferConstraints {
+                        ty: Some(SqlType::Boolean),
+                        scope: Some(Scope::Row),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                inferrer,
+                resolved,
+            )?;
+        }
+
+        // OVER (...) computes the aggregate per row instead of collapsing the group.
+        if let Some(over) = &func.over {
+            if !is_aggregate {
+                return Err(Error::FunctionCall(format!(
+                    "OVER used on non-aggregate function {func_name}"
+                )));
+            }
+
+            self.infer_window(over, inferrer, resolved)?;
+
+            return Ok(InferredColumn {
+                column: infer.column,
+                scope: Scope::Row,
+            });
         }
+
+        if matches!(func_name.as_str(), "row_number" | "rank" | "dense_rank") {
+            return Err(Error::FunctionCall(format!(
+                "{func_name} requires an OVER clause"
+            )));
+        }
+
+        Ok(infer)
+    }
+
+    /// Type checks the PARTITION BY and ORDER BY of a window as row expressions.
+    fn infer_window<I: ColumnInferrer>(
+        &self,
+        over: &WindowType,
+        inferrer: &I,
+        resolved: &mut ResolvedQuery,
+    ) -> Result<(), Error> {
+        let WindowType::WindowSpec(spec) = over else {
+            return Err(Error::Unsupported(format!("Named window {over}")));
+        };
+
+        let exprs = spec
+            .partition_by
+            .iter()
+            .chain(spec.order_by.iter().map(|o| &o.expr));
+
+        for expr in exprs {
+            self.infer_expr_column(
+                expr,
+                InferContext {
+                    constraints: InferConstraints {
+                        scope: Some(Scope::Row),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                inferrer,
+                resolved,
+            )?;
+        }
+
+        Ok(())
     }
 
     fn sql_count<I: ColumnInferrer>(
         &self,
         args: &FunctionArguments,
-        _: InferContext,
+        context: InferContext,
+        inferrer: &I,
+        resolved: &mut ResolvedQuery,
+    ) -> Result<InferredColumn, Error> {
+        let count_column = Column::new(SqlType::BigInt, false, false);
+
+        let FunctionArguments::List(list) = args else {
+            return Err(Error::FunctionCall(
+                "Invalid arguments for COUNT".to_string(),
+            ));
+        };
+
+        // COUNT can only take in one argument.
+        if list.args.len() != 1 {
+            return Err(Error::FunctionArgumentCount {
+                expected: 1,
+                got: list.args.len(),
+            });
+        }
+
+        let FunctionArg::Unnamed(arg_expr) = list.args.first().unwrap() else {
+            return Err(Error::FunctionCall(
+                "COUNT operates only on individual rows/values.".to_string(),
+            ));
+        };
+
+        match arg_expr {
+            FunctionArgExpr::Expr(expr) => {
+                let mut ctx = context.clone();
+                ctx.constraints.ty = None;
+                ctx.constraints.nullable = None;
+                ctx.constraints.scope = Some(Scope::Row);
+
+                self.infer_expr_column(expr, ctx, inferrer, resolved)?;
+            }
+            FunctionArgExpr::QualifiedWildcard(_) => {
+                // TODO: Must be able to look up the qualifier with the Inferrer and be able to
+                // determine if it is a valid table or not.
+            }
+            FunctionArgExpr::Wildcard => {
+                // Every row is distinct, so there is nothing to deduplicate.
+                if matches!(list.duplicate_treatment, Some(DuplicateTreatment::Distinct)) {
+                    return Err(Error::FunctionCall(
+                        "COUNT(DISTINCT *) is not allowed".to_string(),
+                    ));
+                }
+            }
+        }
+
+        Ok(InferredColumn {
+            column: count_column,
+            scope: Scope::Group,
+        })
+    }
+
+    fn sql_rank(&self, func_name: &str, args: &FunctionArguments) -> Result<InferredColumn, Error> {
+        let got = match args {
+            FunctionArguments::None => 0,
+            FunctionArguments::List(list) => list.args.len(),
+            FunctionArguments::Subquery(_) => {
+                return Err(Error::FunctionCall(format!(
+                    "Invalid arguments for {func_name}"
+                )));
+            }
+        };
+
+        // Ranking functions only look at the position within the window.
+        if got != 0 {
+            return Err(Error::FunctionArgumentCount { expected: 0, got });
+        }
+
+        Ok(InferredColumn {
+            column: Column::new(SqlType::BigInt, false, false),
+            scope: Scope::Group,
+        })
+    }
+
+    /// `age(ts)` and `age(ts1, ts2)` return the Interval between the timestamps.
+    #[cfg(feature = "time")]
+    fn sql_age<I: ColumnInferrer>(
+        &self,
+        args: &FunctionArguments,
+        context: InferContext,
+        inferrer: &I,
+        resolved: &mut ResolvedQuery,
+    ) -> Result<InferredColumn, Error> {
+        let FunctionArguments::List(list) = args else {
+            return Err(Error::FunctionCall("Invalid arguments for AGE".to_string()));
+        };
+
+        let got = list.args.len();
+        if !(1..=2).contains(&got) {
+            return Err(Error::FunctionArgumentCount {
+                expected: got.clamp(1, 2),
+                got,
+            });
+        }
+
+        let mut ctx = context;
+        ctx.constraints.ty = None;
+        ctx.constraints.nullable = None;
+
+        let mut nullable = false;
+        let mut scope = Scope::Literal;
+
+        for arg in &list.args {
+            let FunctionArg::Unnamed(FunctionArgExpr::Expr(expr)) = arg else {
+                return Err(Error::FunctionCall(
+                    "AGE operates only on individual rows/values.".to_string(),
+                ));
+            };
+
+            let infer =
+                self.infer_operand(expr, &SqlType::Timestamp, ctx.clone(), inferrer, resolved)?;
+
+            if !matches!(
+                infer.column.ty,
+                SqlType::Date | SqlType::Timestamp | SqlType::TimestampTz
+            ) {
+                return Err(Error::TypeMismatch {
+                    expected: SqlType::Timestamp,
+                    got: infer.column.ty,
+                });
+            }
+
+            nullable |= infer.column.nullable;
+            scope = scope.combine(&infer.scope)?;
+        }
+
+        Ok(InferredColumn {
+            column: Column::new(SqlType::Interval, nullable, false),
+            scope,
+        })
+    }
+
+    /// String functions that only take Text arguments, eg. `upper(name)` or `ltrim(name, ' ')`.
+    ///
+    /// The result is nullable if any argument is.
+    #[allow(clippy::too_many_arguments)]
+    fn sql_string<I: ColumnInferrer>(
+        &self,
+        func_name: &str,
+        args: &FunctionArguments,
+        arity: RangeInclusive<usize>,
+        return_ty: SqlType,
+        context: InferContext,
+        inferrer: &I,
+        resolved: &mut ResolvedQuery,
+    ) -> Result<InferredColumn, Error> {
+        let exprs = Self::function_args(func_name, args, arity)?;
+
+        let mut ctx = context;
+        ctx.constraints.ty = Some(SqlType::Text);
+        ctx.constraints.nullable = None;
+
+        let mut nullable = false;
+        let mut scope = Scope::Literal;
+
+        for expr in exprs {
+            let infer = self.infer_expr_column(expr, ctx.clone(), inferrer, resolved)?;
+
+            nullable |= infer.column.nullable;
+            scope = scope.combine(&infer.scope)?;
+        }
+
+        Ok(InferredColumn {
+            column: Column::new(return_ty, nullable, false),
+            scope,
+        })
+    }
+
+    /// Math functions on numeric arguments, eg. `abs(balance)` or `round(price, 2)`.
+    fn sql_math<I: ColumnInferrer>(
+        &self,
+        func_name: &str,
+        args: &FunctionArguments,
+        context: InferContext,
+        inferrer: &I,
+        resolved: &mut ResolvedQuery,
+    ) -> Result<InferredColumn, Error> {
+        let arity = match func_name {
+            "round" => 1..=2,
+            "mod" | "power" | "pow" => 2..=2,
+            _ => 1..=1,
+        };
+
+        let exprs = Self::function_args(func_name, args, arity)?;
+
+        let mut ctx = context;
+        ctx.constraints.nullable = None;
+
+        // Placeholders take the expected numeric type, otherwise a Double.
+        let fallback_ty = match ctx.constraints.ty.take() {
+            Some(ty) if ty.is_numeric() => ty,
+            _ => SqlType::Double,
+        };
+
+        let infer = self.infer_operand(exprs[0], &fallback_ty, ctx.clone(), inferrer, resolved)?;
+
+        if !infer.column.ty.is_numeric() {
+            return Err(Error::TypeNotNumeric(infer.column.ty));
+        }
+
+        let mut ty = infer.column.ty;
+        let mut nullable = infer.column.nullable;
+        let mut scope = infer.scope;
+
+        if let Some(second) = exprs.get(1) {
+            let second_ty = if func_name == "round" {
+                SqlType::Integer
+            } else {
+                ty.clone()
+            };
+
+            let second_infer = self.infer_operand(second, &second_ty, ctx, inferrer, resolved)?;
+
+            if func_name == "round" && !second_infer.column.ty.is_integer() {
+                return Err(Error::TypeMismatch {
+                    expected: SqlType::Integer,
+                    got: second_infer.column.ty,
+                });
+            } else if !second_infer.column.ty.is_numeric() {
+                return Err(Error::TypeNotNumeric(second_infer.column.ty));
+            }
+
+            nullable |= second_infer.column.nullable;
+            scope = scope.combine(&second_infer.scope)?;
+
+            ty = match func_name {
+                // Postgres only rounds to a precision on exact numerics.
+                "round" if self.dialect.kind() == DialectKind::Postgres && !ty.is_decimal() => {
+                    SqlType::Double
+                }
+                "round" => ty,
+                "mod" => {
+                    ty.promote(&second_infer.column.ty)
+                        .ok_or_else(|| Error::TypeMismatch {
+                            expected: ty.clone(),
+                            got: second_infer.column.ty.clone(),
+                        })?
+                }
+                _ => SqlType::Double,
+            };
+        } else if func_name == "sqrt" {
+            ty = SqlType::Double;
+        }
+
+        Ok(InferredColumn {
+            column: Column::new(ty, nullable, false),
+            scope,
+        })
+    }
+
+    /// Infers a call to a function with a known signature.
+    fn infer_signature<I: ColumnInferrer>(
+        &self,
+        func_name: &str,
+        signature: &FunctionSignature,
+        args: &FunctionArguments,
+        context: InferContext,
         inferrer: &I,
-        _: &mut ResolvedQuery,
+        resolved: &mut ResolvedQuery,
     ) -> Result<InferredColumn, Error> {
-        let count_column = Column::new(SqlType::Integer, false, false);
+        let exprs = match args {
+            FunctionArguments::None => vec![],
+            args => Self::function_args(func_name, args, 0..=usize::MAX)?,
+        };
 
-        match args {
-            FunctionArguments::List(list) => {
-                // COUNT can only take in one argument.
-                if list.args.len() != 1 {
+        let got = exprs.len();
+        let arg_tys: Vec<SqlType> = match &signature.arity {
+            Arity::Fixed(tys) => {
+                if got != tys.len() {
                     return Err(Error::FunctionArgumentCount {
-                        expected: 1,
-                        got: list.args.len(),
+                        expected: tys.len(),
+                        got,
                     });
                 }
 
-                let arg = list.args.first().unwrap();
-                match arg {
-                    FunctionArg::Unnamed(arg_expr) => match arg_expr {
-                        FunctionArgExpr::Expr(expr) => match expr {
-                            Expr::Identifier(ident) => {
-                                let column_name = ident.value.clone();
-
-                                inferrer
-                                    .infer_unqualified_column(self, &column_name)?
-                                    .ok_or_else(|| Error::ColumnDoesntExist(column_name.clone()))?;
-                            }
-                            Expr::CompoundIdentifier(idents) => {
-                                let qualifier = &idents.first().unwrap().value;
-                                let column_name = &idents.get(1).unwrap().value;
-
-                                inferrer.infer_qualified_column(self, qualifier, column_name)?;
-                            }
-                            _ => todo!(),
-                        },
-                        FunctionArgExpr::QualifiedWildcard(_) => {
-                            // TODO: Must be able to look up the qualifier with the Inferrer and be able to
-                            // determine if it is a valid table or not.
-                        }
-                        FunctionArgExpr::Wildcard => {}
-                    },
-                    _ => todo!(),
+                tys.clone()
+            }
+            Arity::Optional { required, optional } => {
+                let max = required.len() + optional.len();
+                if got < required.len() || got > max {
+                    return Err(Error::FunctionArgumentCount {
+                        expected: got.clamp(required.len(), max),
+                        got,
+                    });
+                }
+
+                required.iter().chain(optional).take(got).cloned().collect()
+            }
+            Arity::Variadic { ty, min } => {
+                if got < *min {
+                    return Err(Error::FunctionArgumentCount {
+                        expected: *min,
+                        got,
+                    });
                 }
 
-                Ok(InferredColumn {
-                    column: count_column,
-                    scope: Scope::Group,
-                })
+                vec![ty.clone(); got]
+            }
+        };
+
+        let mut ctx = context;
+        ctx.constraints.nullable = None;
+
+        let mut nullable = false;
+        let mut scope = Scope::Literal;
+
+        for (expr, ty) in exprs.into_iter().zip(arg_tys) {
+            let infer = if let Arity::Variadic { .. } = signature.arity {
+                // Variadic arguments only need to be castable to the type.
+                ctx.constraints.ty = None;
+                let infer = self.infer_operand(expr, &ty, ctx.clone(), inferrer, resolved)?;
+
+                if !infer.column.ty.is_castable_to(&ty) {
+                    return Err(Error::TypeMismatch {
+                        expected: ty,
+                        got: infer.column.ty,
+                    });
+                }
+
+                infer
+            } else {
+                ctx.constraints.ty = Some(ty);
+                self.infer_expr_column(expr, ctx.clone(), inferrer, resolved)?
+            };
+
+            nullable |= infer.column.nullable;
+            scope = scope.combine(&infer.scope)?;
+        }
+
+        let nullable = match signature.nullability {
+            Nullability::NotNull => false,
+            Nullability::Nullable => true,
+            Nullability::Arguments => nullable,
+        };
+
+        Ok(InferredColumn {
+            column: Column::new(signature.returns.clone(), nullable, false),
+            scope,
+        })
+    }
+
+    /// Gets the expression arguments of a function, ensuring the count is within the arity.
+    fn function_args<'a>(
+        func_name: &str,
+        args: &'a FunctionArguments,
+        arity: RangeInclusive<usize>,
+    ) -> Result<Vec<&'a Expr>, Error> {
+        let FunctionArguments::List(list) = args else {
+            return Err(Error::FunctionCall(format!(
+                "Invalid arguments for {func_name}"
+            )));
+        };
+
+        if !arity.contains(&list.args.len()) {
+            return Err(Error::FunctionArgumentCount {
+                expected: *arity.end(),
+                got: list.args.len(),
+            });
+        }
+
+        list.args
+            .iter()
+            .map(|arg| match arg {
+                FunctionArg::Unnamed(FunctionArgExpr::Expr(expr)) => Ok(expr),
+                _ => Err(Error::FunctionCall(format!(
+                    "{func_name} operates only on individual rows/values."
+                ))),
+            })
+            .collect()
+    }
+
+    /// `date_part('year', created_at)`, the function form of EXTRACT.
+    #[cfg(feature = "time")]
+    fn sql_date_part<I: ColumnInferrer>(
+        &self,
+        args: &FunctionArguments,
+        context: InferContext,
+        inferrer: &I,
+        resolved: &mut ResolvedQuery,
+    ) -> Result<InferredColumn, Error> {
+        let args = Self::function_args("date_part", args, 2..=2)?;
+
+        let mut field_ctx = context.clone();
+        field_ctx.constraints.ty = Some(SqlType::Text);
+        field_ctx.constraints.nullable = None;
+
+        let field_infer = self.infer_expr_column(args[0], field_ctx, inferrer, resolved)?;
+        let mut infer = self.sql_extract(args[1], context, inferrer, resolved)?;
+
+        infer.column.nullable |= field_infer.column.nullable;
+        infer.scope = infer.scope.combine(&field_infer.scope)?;
+
+        Ok(infer)
+    }
+
+    /// Extracts a field (eg. the year) out of a date/time or interval value.
+    ///
+    /// Postgres returns the field as a double, the others as an integer.
+    #[cfg(feature = "time")]
+    pub(crate) fn sql_extract<I: ColumnInferrer>(
+        &self,
+        source: &Expr,
+        context: InferContext,
+        inferrer: &I,
+        resolved: &mut ResolvedQuery,
+    ) -> Result<InferredColumn, Error> {
+        let mut ctx = context;
+        ctx.constraints.ty = None;
+        ctx.constraints.nullable = None;
+
+        let infer = self.infer_operand(source, &SqlType::Timestamp, ctx, inferrer, resolved)?;
+
+        if !(infer.column.ty.is_temporal() || infer.column.ty == SqlType::Interval) {
+            return Err(Error::TypeMismatch {
+                expected: SqlType::Timestamp,
+                got: infer.column.ty,
+            });
+        }
+
+        let ty = match self.dialect.kind() {
+            DialectKind::Postgres => SqlType::Double,
+            _ => SqlType::Integer,
+        };
+
+        Ok(InferredColumn {
+            column: Column::new(ty, infer.column.nullable, false),
+            scope: infer.scope,
+        })
+    }
+
+    /// The current date/time functions, eg. `now()` or `current_date`.
+    ///
+    /// Timestamps take the expected timestamp type, as they are implicitly converted.
+    #[cfg(feature = "time")]
+    fn sql_current_time(
+        &self,
+        func_name: &str,
+        args: &FunctionArguments,
+        context: InferContext,
+    ) -> Result<InferredColumn, Error> {
+        let got = match args {
+            FunctionArguments::None => 0,
+            FunctionArguments::List(list) => list.args.len(),
+            FunctionArguments::Subquery(_) => {
+                return Err(Error::FunctionCall(format!(
+                    "Invalid arguments for {func_name}"
+                )));
             }
-            _ => todo!(),
+        };
+
+        if got != 0 {
+            return Err(Error::FunctionArgumentCount { expected: 0, got });
         }
+
+        let ty = match func_name {
+            "current_date" => SqlType::Date,
+            "current_time" | "localtime" => SqlType::Time,
+            _ => match context.constraints.ty {
+                Some(ty @ (SqlType::Timestamp | SqlType::TimestampTz)) => ty,
+                _ if func_name == "localtimestamp" => SqlType::Timestamp,
+                _ => SqlType::TimestampTz,
+            },
+        };
+
+        Ok(InferredColumn {
+            column: Column::new(ty, false, false),
+            scope: Scope::Literal,
+        })
     }
 
     fn sql_coalesce<I: ColumnInferrer>(
@@ -98,6 +712,9 @@ impl Simulator {
 
         let mut ty: Option<SqlType> = None;
 
+        // The first pass must not register any inputs, the second pass does that.
+        let mut scratch = ResolvedQuery::default();
+
         // First type pass, this gets the type to use.
         for arg in &list.args {
             let FunctionArg::Unnamed(FunctionArgExpr::Expr(expr)) = arg else {
@@ -109,10 +726,10 @@ impl Simulator {
             let mut first_ctx = context.clone();
             first_ctx.constraints.ty = ty.clone();
 
-            if let Ok(infer) = self.infer_expr_column(expr, first_ctx, inferrer, resolved) {
+            if let Ok(infer) = self.infer_expr_column(expr, first_ctx, inferrer, &mut scratch) {
                 match ty {
                     Some(ref ty) => {
-                        if &infer.column.ty != ty {
+                        if !ty.is_compatible(&infer.column.ty) {
                             return Err(Error::TypeMismatch {
                                 expected: ty.clone(),
                                 got: infer.column.ty,
@@ -160,7 +777,73 @@ impl Simulator {
         }
     }
 
-    fn sql_avg<I: ColumnInferrer>(
+    /// `greatest(a, b, ...)` and `least(a, b, ...)`.
+    ///
+    /// The arguments are unified into one type, numeric arguments are promoted.
+    fn sql_greatest_least<I: ColumnInferrer>(
+        &self,
+        func_name: &str,
+        args: &FunctionArguments,
+        context: InferContext,
+        inferrer: &I,
+        resolved: &mut ResolvedQuery,
+    ) -> Result<InferredColumn, Error> {
+        let args = Self::function_args(func_name, args, 0..=usize::MAX)?;
+
+        if args.is_empty() {
+            return Err(Error::FunctionArgumentCount {
+                expected: 1,
+                got: 0,
+            });
+        }
+
+        let mut ctx = context;
+        ctx.constraints.ty = None;
+        ctx.constraints.nullable = None;
+
+        let mut ty: Option<SqlType> = None;
+        let mut nullable = false;
+        let mut scope = Scope::Literal;
+
+        // NULLs and placeholders take the type of the other arguments.
+        let (untyped, typed): (Vec<_>, Vec<_>) = args.into_iter().partition(|e| is_untyped(e));
+
+        for expr in typed {
+            let infer = self.infer_expr_column(expr, ctx.clone(), inferrer, resolved)?;
+
+            ty = Some(match ty {
+                Some(ty) => ty.promote(&infer.column.ty).ok_or(Error::TypeMismatch {
+                    expected: ty,
+                    got: infer.column.ty,
+                })?,
+                None => infer.column.ty,
+            });
+            nullable |= infer.column.nullable;
+            scope = scope.combine(&infer.scope)?;
+        }
+
+        let Some(ty) = ty else {
+            return Err(Error::FunctionCall(format!(
+                "Cannot infer the type of {func_name}"
+            )));
+        };
+
+        ctx.constraints.ty = Some(ty.clone());
+
+        for expr in untyped {
+            let infer = self.infer_expr_column(expr, ctx.clone(), inferrer, resolved)?;
+
+            nullable |= infer.column.nullable;
+            scope = scope.combine(&infer.scope)?;
+        }
+
+        Ok(InferredColumn {
+            column: Column::new(ty, nullable, false),
+            scope,
+        })
+    }
+
+    fn sql_sum_avg<I: ColumnInferrer>(
         &self,
         args: &FunctionArguments,
         context: InferContext,
@@ -168,10 +851,12 @@ impl Simulator {
         resolved: &mut ResolvedQuery,
     ) -> Result<InferredColumn, Error> {
         let FunctionArguments::List(list) = args else {
-            return Err(Error::FunctionCall("Invalid arguments for AVG".to_string()));
+            return Err(Error::FunctionCall(
+                "Invalid arguments for SUM/AVG".to_string(),
+            ));
         };
 
-        // AVG can only take in one argument.
+        // SUM/AVG can only take in one argument.
         if list.args.len() != 1 {
             return Err(Error::FunctionArgumentCount {
                 expected: 1,
@@ -182,7 +867,7 @@ impl Simulator {
         let arg = list.args.first().unwrap();
         let FunctionArg::Unnamed(FunctionArgExpr::Expr(expr)) = arg else {
             return Err(Error::FunctionCall(
-                "AVG operates only on individual rows/values.".to_string(),
+                "SUM/AVG operates only on individual rows/values.".to_string(),
             ));
         };
 
@@ -196,8 +881,9 @@ impl Simulator {
             return Err(Error::TypeNotNumeric(infer.column.ty));
         }
 
+        // Aggregating over an empty set returns NULL.
         Ok(InferredColumn {
-            column: infer.column,
+            column: Column::new(infer.column.ty, true, false),
             scope: Scope::Group,
         })
     }
@@ -236,8 +922,36 @@ impl Simulator {
 
         let infer = self.infer_expr_column(expr, ctx, inferrer, resolved)?;
 
+        // Aggregating over an empty set returns NULL.
+        Ok(InferredColumn {
+            column: Column::new(infer.column.ty, true, false),
+            scope: Scope::Group,
+        })
+    }
+
+    /// `string_agg(name, ', ')` and `group_concat(name)` join the Text values of a group.
+    fn sql_string_agg<I: ColumnInferrer>(
+        &self,
+        func_name: &str,
+        args: &FunctionArguments,
+        context: InferContext,
+        inferrer: &I,
+        resolved: &mut ResolvedQuery,
+    ) -> Result<InferredColumn, Error> {
+        let exprs = Self::function_args(func_name, args, 1..=2)?;
+
+        let mut ctx = context;
+        ctx.constraints.ty = Some(SqlType::Text);
+        ctx.constraints.nullable = None;
+        ctx.constraints.scope = Some(Scope::Row);
+
+        for expr in exprs {
+            self.infer_expr_column(expr, ctx.clone(), inferrer, resolved)?;
+        }
+
+        // Aggregating over an empty set returns NULL.
         Ok(InferredColumn {
-            column: infer.column,
+            column: Column::new(SqlType::Text, true, false),
             scope: Scope::Group,
         })
     }
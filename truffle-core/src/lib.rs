@@ -10,18 +10,24 @@ mod table;
 pub mod ty;
 
 pub use dialect::*;
-pub use misc::config::Config;
-use misc::immutable::Immutable;
+pub use func::{Arity, FunctionSignature, Nullability};
+pub use misc::config::{Config, FunctionConfig};
+use misc::{immutable::Immutable, split::split_statements};
+pub use table::{Constraint, OnAction, Table};
 
-use resolve::ResolvedQuery;
+use func::builtin_functions;
+use resolve::{Placeholder, ResolvedQuery};
 use sqlparser::{
-    ast::{ObjectName, Statement},
+    ast::{ArrayElemTypeDef, DataType, ObjectName, Statement},
     parser::Parser,
+    tokenizer::{Location, Token, TokenWithSpan, Tokenizer},
 };
 use ty::SqlType;
 
-use std::{collections::HashMap, sync::Arc};
-use table::Table;
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
 
 #[derive(Debug, PartialEq, Eq, thiserror::Error)]
 pub enum Error {
@@ -31,12 +37,18 @@ pub enum Error {
     Sql(String),
     #[error("Table '{0}' already exists")]
     TableAlreadyExists(String),
+    #[error("Schema '{0}' already exists")]
+    SchemaAlreadyExists(String),
     #[error("Column '{0}' already exists")]
     ColumnAlreadyExists(String),
+    #[error("Schema '{0}' doesn't exist")]
+    SchemaDoesntExist(String),
     #[error("Table '{0}' doesn't exist")]
     TableDoesntExist(String),
     #[error("Column '{0}' doesn't exist")]
     ColumnDoesntExist(String),
+    #[error("Column '{0}' is used by a CHECK constraint or generated column")]
+    ColumnInUse(String),
     #[error("Ambiguous Column: {0}")]
     AmbiguousColumn(String),
     #[error("Ambiguous Alias: {0}")]
@@ -53,8 +65,20 @@ pub enum Error {
     ForeignKeyConstraint(String),
     #[error("Type Mismatch: expected {expected} and got {got}")]
     TypeMismatch { expected: SqlType, got: SqlType },
+    #[error("Invalid Type: '{0}'")]
+    InvalidType(String),
+    #[error("Type '{0}' already exists")]
+    TypeAlreadyExists(String),
+    #[error("Type '{0}' doesn't exist")]
+    TypeDoesntExist(String),
+    #[error("Type '{name}' is used by Column '{column}'")]
+    TypeInUse { name: String, column: String },
+    #[error("'{variant}' is not a variant of enum '{name}'")]
+    InvalidEnumVariant { name: String, variant: String },
     #[error("Type Not Numeric: got {0}")]
     TypeNotNumeric(SqlType),
+    #[error("Invalid Cast: cannot cast {from} to {to}")]
+    InvalidCast { from: SqlType, to: SqlType },
     #[error("Cannot set not null column '{0}' to null")]
     NullOnNotNullColumn(String),
     #[error("Cannot set not default column '{0}' to default value")]
@@ -65,10 +89,14 @@ pub enum Error {
     ColumnCountMismatch { expected: usize, got: usize },
     #[error("Required column missing for '{0}'")]
     RequiredColumnMissing(String),
+    #[error("ON CONFLICT target '({0})' is not a unique or primary key")]
+    ConflictTargetNotUnique(String),
     #[error("No common column")]
     NoCommonColumn,
     #[error("Missing placeholder '${0}'")]
     MissingPlaceholder(usize),
+    #[error("Cannot mix '?' and numbered placeholders in one statement")]
+    MixedPlaceholderStyles,
     #[error("Function '${0}' doesn't exist")]
     FunctionDoesntExist(String),
     #[error("Function argument count mismatch: expected {expected} and got {got}")]
@@ -79,6 +107,14 @@ pub enum Error {
     IncompatibleScope,
     #[error("Subquery returns no columns")]
     SubqueryNoColumns,
+    #[error("ORDER BY expression '{0}' must appear in the select list when using DISTINCT")]
+    DistinctOrderBy(String),
+    #[error("Position {0} is not in the select list")]
+    OrdinalOutOfRange(usize),
+    #[error("'{0}' changes the schema, which can't be done while resolving")]
+    MutationInReadOnly(String),
+    #[error("Cache: {0}")]
+    Cache(String),
     #[error("'{0}' is currently unsupported")]
     Unsupported(String),
 }
@@ -86,7 +122,15 @@ pub enum Error {
 #[derive(Debug, Clone)]
 pub struct Simulator {
     pub dialect: Immutable<Arc<dyn Dialect>>,
-    pub tables: HashMap<String, Table>,
+    /// Shared between clones, it is only copied once a clone changes the schema.
+    pub tables: Arc<HashMap<String, Table>>,
+    pub schemas: HashSet<String>,
+    /// The schema of unqualified table names, eg. `public`.
+    pub default_schema: String,
+    /// User-defined type names and the type they resolve to.
+    pub types: HashMap<String, SqlType>,
+    /// Functions that are inferred from their signature.
+    pub functions: HashMap<String, FunctionSignature>,
 }
 
 fn object_name_to_strings(name: &ObjectName) -> Vec<String> {
@@ -96,11 +140,29 @@ fn object_name_to_strings(name: &ObjectName) -> Vec<String> {
         .collect()
 }
 
+/// The unqualified name of a table, which is what its columns are qualified by.
+fn table_name(name: &ObjectName) -> String {
+    object_name_to_strings(name).pop().unwrap()
+}
+
+/// The schema that unqualified table names belong to.
+fn default_schema(kind: DialectKind) -> String {
+    match kind {
+        DialectKind::Sqlite => "main",
+        _ => "public",
+    }
+    .to_string()
+}
+
 impl Default for Simulator {
     fn default() -> Self {
         Self {
             dialect: Immutable::new(Arc::new(SqliteDialect::default())),
-            tables: HashMap::new(),
+            tables: Arc::default(),
+            schemas: HashSet::new(),
+            default_schema: default_schema(DialectKind::Sqlite),
+            types: HashMap::new(),
+            functions: builtin_functions(),
         }
     }
 }
@@ -109,8 +171,12 @@ impl Simulator {
     /// Construct a new Simulator with the given SQL Dialect.
     fn create<D: Dialect>(dialect: D) -> Self {
         Self {
+            default_schema: default_schema(dialect.kind()),
             dialect: Immutable::new(Arc::new(dialect)),
-            tables: HashMap::new(),
+            tables: Arc::default(),
+            schemas: HashSet::new(),
+            types: HashMap::new(),
+            functions: builtin_functions(),
         }
     }
 
@@ -125,56 +191,428 @@ impl Simulator {
         }
     }
 
+    /// Construct a new Simulator with the dialect, types and functions of the Config.
+    pub fn with_config(config: &Config) -> Result<Self, Error> {
+        let mut sim = Simulator::with_dialect(config.dialect);
+        sim.apply_config(config)?;
+
+        Ok(sim)
+    }
+
+    /// Registers the types and functions of the Config.
+    pub fn apply_config(&mut self, config: &Config) -> Result<(), Error> {
+        if let Some(schema) = &config.default_schema {
+            self.default_schema = schema.clone();
+        }
+
+        self.add_types(&config.types)?;
+
+        for (name, function) in &config.functions {
+            self.register_function(name, function.signature()?);
+        }
+
+        Ok(())
+    }
+
+    /// Registers a function so calls to it are inferred from its signature.
+    ///
+    /// Functions that are inferred by hand (eg. `count`) can't be replaced.
+    pub fn register_function(&mut self, name: impl AsRef<str>, signature: FunctionSignature) {
+        self.functions
+            .insert(name.as_ref().to_lowercase(), signature);
+    }
+
+    /// Registers user-defined type names, each resolving to the named base type.
+    pub fn add_types<'a>(
+        &mut self,
+        types: impl IntoIterator<Item = (&'a String, &'a String)>,
+    ) -> Result<(), Error> {
+        for (name, base) in types {
+            let ty: SqlType = base.parse()?;
+            self.types.insert(name.to_lowercase(), ty);
+        }
+
+        Ok(())
+    }
+
+    /// Converts a parsed DataType, resolving user-defined type names (also within arrays).
+    pub(crate) fn sql_type(&self, data_type: &DataType) -> SqlType {
+        match data_type {
+            DataType::Custom(name, _) => {
+                if let Some(ty) = self.find_type(name) {
+                    return ty.clone();
+                }
+            }
+            DataType::Array(
+                ArrayElemTypeDef::AngleBracket(inner)
+                | ArrayElemTypeDef::SquareBracket(inner, _)
+                | ArrayElemTypeDef::Parenthesis(inner),
+            ) => return SqlType::Array(Box::new(self.sql_type(inner))),
+            _ => {}
+        }
+
+        data_type.clone().into()
+    }
+
+    /// Finds a user-defined type by its (possibly schema-qualified) name.
+    ///
+    /// Types from the Config are registered in lowercase, so unquoted names also match them
+    /// in dialects that don't fold identifiers.
+    pub(crate) fn find_type(&self, name: &ObjectName) -> Option<&SqlType> {
+        let key = self.type_key(name).ok()?;
+        let quoted = name.0.iter().any(|part| {
+            part.as_ident()
+                .is_some_and(|ident| ident.quote_style.is_some())
+        });
+
+        self.types.get(&key).or_else(|| {
+            (!quoted)
+                .then(|| self.types.get(&key.to_lowercase()))
+                .flatten()
+        })
+    }
+
     /// Get a Table that exists within the Simulator.
     pub fn get_table(&self, name: &str) -> Option<&Table> {
         self.tables.get(name)
     }
 
+    /// The key that a table is stored under, eg. `my_schema.users`.
+    ///
+    /// Tables in the default schema are stored under their unqualified name.
+    pub(crate) fn table_key(&self, name: &ObjectName) -> Result<String, Error> {
+        self.schema_key(name)
+            .ok_or_else(|| Error::Unsupported(format!("Table name {name}")))
+    }
+
+    /// The key that a user-defined type is stored under, like a table.
+    pub(crate) fn type_key(&self, name: &ObjectName) -> Result<String, Error> {
+        self.schema_key(name)
+            .ok_or_else(|| Error::Unsupported(format!("Type name {name}")))
+    }
+
+    fn schema_key(&self, name: &ObjectName) -> Option<String> {
+        match object_name_to_strings(name).as_slice() {
+            [object] => Some(object.clone()),
+            [schema, object] if schema == &self.default_schema => Some(object.clone()),
+            [schema, object] => Some(format!("{schema}.{object}")),
+            _ => None,
+        }
+    }
+
+    /// Finds the key of an existing table.
+    ///
+    /// Unqualified names are only looked up in the default schema.
+    pub(crate) fn resolve_table_key(&self, name: &ObjectName) -> Result<String, Error> {
+        let key = self.table_key(name)?;
+
+        if self.tables.contains_key(&key) {
+            return Ok(key);
+        }
+
+        Err(Error::TableDoesntExist(
+            object_name_to_strings(name).join("."),
+        ))
+    }
+
+    /// Finds an existing table by its (possibly schema-qualified) name.
+    pub(crate) fn find_table(&self, name: &ObjectName) -> Result<&Table, Error> {
+        let key = self.resolve_table_key(name)?;
+        Ok(&self.tables[&key])
+    }
+
     pub fn get_tables(&self) -> &HashMap<String, Table> {
         &self.tables
     }
 
+    /// The tables to change, copying them first if they are shared with a clone.
+    pub fn get_tables_mut(&mut self) -> &mut HashMap<String, Table> {
+        Arc::make_mut(&mut self.tables)
+    }
+
     pub fn has_table(&self, name: &str) -> bool {
         self.tables.contains_key(name)
     }
 
+    pub fn has_schema(&self, name: &str) -> bool {
+        self.schemas.contains(name)
+    }
+
     /// Executes the given SQL in the Simulator and updates the state.
     /// Returns the resolved query for the last statement ran.
     pub fn execute(&mut self, sql: impl AsRef<str>) -> Result<ResolvedQuery, Error> {
-        let dialect = &**self.dialect.parser_dialect();
-        let parser = Parser::new(dialect);
-        let statements = parser.try_with_sql(sql.as_ref())?.parse_statements()?;
+        let tokens = self.tokenize(sql.as_ref())?;
+
+        check_placeholder_styles(&tokens)?;
+
+        let dialect = self.dialect.parser_dialect();
+        let statements = Parser::new(&**dialect)
+            .with_tokens_with_locations(tokens)
+            .parse_statements()?;
 
         let mut resolved = ResolvedQuery::default();
 
         for statement in statements {
-            resolved = match statement {
-                Statement::CreateTable(create_table) => self.create_table(create_table)?,
-                // TODO: Support Alter Table
-                Statement::Query(query) => self.query(&query)?,
-                Statement::Update {
-                    table,
-                    assignments,
-                    from,
-                    selection,
-                    returning,
-                    or,
-                } => self.update(table, assignments, from, selection, returning, or)?,
-                Statement::Insert(insert) => self.insert(insert)?,
-                Statement::Delete(delete) => self.delete(delete)?,
-                Statement::Drop {
-                    object_type, names, ..
-                } => self.drop(&object_type, names)?,
-                _ => return Err(Error::Unsupported(statement.to_string())),
+            resolved = self.execute_statement(statement)?;
+        }
+
+        Ok(resolved)
+    }
+
+    /// Lazily executes the given SQL in the Simulator, one statement at a time.
+    ///
+    /// Each statement is only parsed and resolved once the iterator reaches it,
+    /// so the Simulator state is updated as the iterator is consumed.
+    pub fn execute_iter<'a>(
+        &'a mut self,
+        sql: &'a str,
+    ) -> impl Iterator<Item = Result<ResolvedQuery, Error>> + use<'a> {
+        self.execute_iter_with_sql(sql).map(|(_, result)| result)
+    }
+
+    /// Like [`Simulator::execute_iter`], but also yields the SQL of each statement.
+    ///
+    /// The SQL is split on its semicolons first, and each statement is only tokenized
+    /// once it's reached, so a large script is never held in memory as tokens.
+    /// A statement that fails leaves the Simulator unchanged,
+    /// so the statements after it resolve against the last valid schema.
+    pub fn execute_iter_with_sql<'a>(
+        &'a mut self,
+        sql: &'a str,
+    ) -> impl Iterator<Item = (String, Result<ResolvedQuery, Error>)> + use<'a> {
+        split_statements(sql, self.dialect.kind()).filter_map(move |(statement, start)| {
+            let text = statement.trim().to_string();
+
+            let tokens = match self.tokenize_at(statement, start) {
+                Ok(tokens) => tokens,
+                Err(e) => return Some((text, Err(e))),
             };
 
-            for (i, col) in resolved.inputs.iter().enumerate() {
-                if matches!(col.ty, SqlType::Unknown(_)) {
-                    return Err(Error::MissingPlaceholder(i));
-                }
+            // Empty statements (only whitespace) are skipped.
+            if tokens
+                .iter()
+                .all(|t| matches!(t.token, Token::Whitespace(_)))
+            {
+                return None;
+            }
+
+            let styles = check_placeholder_styles(&tokens);
+
+            let dialect = self.dialect.parser_dialect();
+            let mut parser = Parser::new(&**dialect).with_tokens_with_locations(tokens);
+
+            let result = styles
+                .and_then(|_| {
+                    let statement = parser.parse_statement()?;
+                    parser.expect_token(&Token::EOF)?;
+                    Ok(statement)
+                })
+                .and_then(|statement| self.execute_statement(statement));
+
+            Some((text, result))
+        })
+    }
+
+    /// Tokenizes the SQL, folding unquoted identifiers the way the dialect does.
+    ///
+    /// Postgres lowercases them, while quoted identifiers always keep their case.
+    fn tokenize(&self, sql: &str) -> Result<Vec<TokenWithSpan>, Error> {
+        self.tokenize_at(sql, Location { line: 1, column: 1 })
+    }
+
+    /// Tokenizes SQL that starts at the location, eg. a statement within a larger script.
+    fn tokenize_at(&self, sql: &str, start: Location) -> Result<Vec<TokenWithSpan>, Error> {
+        // Locations on the first line are offset by the column the SQL starts at.
+        let offset = |location: &mut Location| {
+            if location.line == 1 {
+                location.column += start.column - 1;
+            }
+            // Tokens without a location (eg. EOF) are left as they are.
+            if location.line > 0 {
+                location.line += start.line - 1;
+            }
+        };
+
+        let dialect = self.dialect.parser_dialect();
+        let mut tokens = Tokenizer::new(&**dialect, sql)
+            .tokenize_with_location()
+            .map_err(|mut e| {
+                offset(&mut e.location);
+                Error::Parsing(e.into())
+            })?;
+
+        for token in &mut tokens {
+            offset(&mut token.span.start);
+            offset(&mut token.span.end);
+
+            if self.dialect.kind() == DialectKind::Postgres
+                && let Token::Word(word) = &mut token.token
+                && word.quote_style.is_none()
+            {
+                word.value = word.value.to_lowercase();
             }
         }
 
+        Ok(tokens)
+    }
+
+    fn execute_statement(&mut self, statement: Statement) -> Result<ResolvedQuery, Error> {
+        let mut resolved = match statement {
+            Statement::CreateTable(create_table) => self.create_table(create_table)?,
+            Statement::AlterTable {
+                name,
+                if_exists,
+                operations,
+                ..
+            } => self.alter_table(&name, if_exists, operations)?,
+            Statement::Drop {
+                object_type,
+                names,
+                if_exists,
+                cascade,
+                ..
+            } => self.drop(&object_type, names, if_exists, cascade)?,
+            Statement::CreateSchema {
+                schema_name,
+                if_not_exists,
+                ..
+            } => self.create_schema(&schema_name, if_not_exists)?,
+            Statement::CreateExtension { name, .. } => self.create_extension(&name)?,
+            Statement::CreateType {
+                name,
+                representation,
+            } => self.create_type(&name, representation)?,
+            Statement::Comment {
+                object_type,
+                object_name,
+                comment,
+                if_exists,
+                ..
+            } => self.comment(&object_type, &object_name, comment, if_exists)?,
+            statement => return self.resolve_statement(statement),
+        };
+
+        check_inputs(&mut resolved)?;
+
         Ok(resolved)
     }
+
+    /// Resolves a single query without changing the Simulator.
+    ///
+    /// Unlike [`Simulator::execute`], this only borrows the Simulator,
+    /// so statements that change the schema (eg. CREATE TABLE) are rejected.
+    /// So are TRUNCATE and INSERT/UPDATE/DELETE without a RETURNING clause.
+    pub fn resolve(&self, sql: impl AsRef<str>) -> Result<ResolvedQuery, Error> {
+        let tokens = self.tokenize(sql.as_ref())?;
+        check_placeholder_styles(&tokens)?;
+
+        let dialect = self.dialect.parser_dialect();
+        let mut parser = Parser::new(&**dialect).with_tokens_with_locations(tokens);
+
+        let statement = parser.parse_statement()?;
+        _ = parser.consume_token(&Token::SemiColon);
+        parser.expect_token(&Token::EOF)?;
+
+        // Changes to the rows are only resolved when they return some.
+        let returns = match &statement {
+            Statement::Insert(insert) => insert.returning.is_some(),
+            Statement::Update { returning, .. } => returning.is_some(),
+            Statement::Delete(delete) => delete.returning.is_some(),
+            Statement::Truncate { .. } => false,
+            _ => true,
+        };
+
+        if !returns {
+            return Err(Error::MutationInReadOnly(statement.to_string()));
+        }
+
+        self.resolve_statement(statement)
+    }
+
+    /// Resolves the statements that don't change the Simulator.
+    fn resolve_statement(&self, statement: Statement) -> Result<ResolvedQuery, Error> {
+        let mutates = matches!(
+            statement,
+            Statement::Insert(_) | Statement::Update { .. } | Statement::Delete(_)
+        );
+
+        let mut resolved = match statement {
+            Statement::Query(query) => self.query(&query)?,
+            Statement::Update {
+                table,
+                assignments,
+                from,
+                selection,
+                returning,
+                or,
+            } => self.update(table, assignments, from, selection, returning, or)?,
+            Statement::Insert(insert) => self.insert(insert)?,
+            Statement::Delete(delete) => self.delete(delete)?,
+            Statement::Truncate {
+                table_names,
+                cascade,
+                ..
+            } => self.truncate(&table_names, cascade)?,
+            Statement::StartTransaction { .. }
+            | Statement::Commit { .. }
+            | Statement::Rollback { .. }
+            | Statement::Savepoint { .. }
+            | Statement::ReleaseSavepoint { .. }
+            | Statement::Set(_) => self.ignore_statement(&statement)?,
+            Statement::CreateTable(_)
+            | Statement::AlterTable { .. }
+            | Statement::Drop { .. }
+            | Statement::CreateSchema { .. }
+            | Statement::CreateExtension { .. }
+            | Statement::CreateType { .. }
+            | Statement::Comment { .. } => {
+                return Err(Error::MutationInReadOnly(statement.to_string()));
+            }
+            _ => return Err(Error::Unsupported(statement.to_string())),
+        };
+
+        check_inputs(&mut resolved)?;
+        resolved.mutates = mutates;
+
+        Ok(resolved)
+    }
+}
+
+/// Builds the inputs of the statement.
+///
+/// Errors if a placeholder was never given a type, eg. `$2` without a `$1`.
+fn check_inputs(resolved: &mut ResolvedQuery) -> Result<(), Error> {
+    resolved.build_inputs()?;
+
+    for (i, col) in resolved.inputs.iter().enumerate() {
+        if matches!(col.ty, SqlType::Unknown(_)) {
+            return Err(Error::MissingPlaceholder(i));
+        }
+    }
+
+    Ok(())
+}
+
+/// Errors if a statement uses both `?` and numbered (`$1` or `?1`) placeholders.
+fn check_placeholder_styles(tokens: &[TokenWithSpan]) -> Result<(), Error> {
+    let mut anonymous = false;
+    let mut numbered = false;
+
+    for token in tokens {
+        match &token.token {
+            Token::Placeholder(placeholder) => match Placeholder::parse(placeholder) {
+                Placeholder::Anonymous => anonymous = true,
+                Placeholder::Numbered(_) => numbered = true,
+                Placeholder::Named(_) => {}
+            },
+            Token::SemiColon => (anonymous, numbered) = (false, false),
+            _ => {}
+        }
+
+        if anonymous && numbered {
+            return Err(Error::MixedPlaceholderStyles);
+        }
+    }
+
+    Ok(())
 }
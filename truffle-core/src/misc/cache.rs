@@ -0,0 +1,134 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    fs,
+    path::Path,
+    sync::Arc,
+};
+
+use serde::{Deserialize, Serialize, Serializer};
+
+use crate::{
+    Error, Simulator,
+    dialect::DialectKind,
+    table::{Constraint, Table},
+    ty::SqlType,
+};
+
+/// A snapshot of the schema known to a Simulator.
+///
+/// Maps and sets are written in order, so the same schema always writes the same cache.
+#[derive(Serialize, Deserialize)]
+struct SimulatorCache {
+    dialect: DialectKind,
+    tables: BTreeMap<String, Table>,
+    schemas: BTreeSet<String>,
+    /// Types created by the migrations (eg. enums).
+    #[serde(default)]
+    types: BTreeMap<String, SqlType>,
+    /// Hash of the migrations that the schema was built from.
+    #[serde(default)]
+    hash: Option<String>,
+}
+
+impl Simulator {
+    /// Writes the schema of the Simulator to the given path.
+    pub fn to_cache(&self, path: impl AsRef<Path>) -> Result<(), Error> {
+        self.write_cache(path, None)
+    }
+
+    /// Like [`Simulator::to_cache`], but also records the hash of the migrations
+    /// so that [`Simulator::from_cache_with_hash`] can tell when the cache is stale.
+    pub fn to_cache_with_hash(&self, path: impl AsRef<Path>, hash: &str) -> Result<(), Error> {
+        self.write_cache(path, Some(hash.to_string()))
+    }
+
+    fn write_cache(&self, path: impl AsRef<Path>, hash: Option<String>) -> Result<(), Error> {
+        let cache = SimulatorCache {
+            dialect: self.dialect.kind(),
+            tables: self
+                .tables
+                .iter()
+                .map(|(name, table)| (name.clone(), table.clone()))
+                .collect(),
+            schemas: self.schemas.iter().cloned().collect(),
+            types: self
+                .types
+                .iter()
+                .map(|(name, ty)| (name.clone(), ty.clone()))
+                .collect(),
+            hash,
+        };
+
+        let data = serde_json::to_string(&cache).map_err(|e| Error::Cache(e.to_string()))?;
+        fs::write(path, data).map_err(|e| Error::Cache(e.to_string()))
+    }
+
+    /// Loads a Simulator from a cache written by [`Simulator::to_cache`].
+    ///
+    /// The cache is only valid if it was written with the same dialect.
+    pub fn from_cache(path: impl AsRef<Path>, dialect: DialectKind) -> Result<Simulator, Error> {
+        Self::read_cache(path, dialect).map(|(sim, _)| sim)
+    }
+
+    /// Loads a Simulator from a cache written by [`Simulator::to_cache_with_hash`].
+    ///
+    /// The cache is stale if it was built from migrations with a different hash.
+    pub fn from_cache_with_hash(
+        path: impl AsRef<Path>,
+        dialect: DialectKind,
+        hash: &str,
+    ) -> Result<Simulator, Error> {
+        let (sim, cache_hash) = Self::read_cache(path, dialect)?;
+
+        if cache_hash.as_deref() != Some(hash) {
+            return Err(Error::Cache(
+                "Cache doesn't match the migrations".to_string(),
+            ));
+        }
+
+        Ok(sim)
+    }
+
+    fn read_cache(
+        path: impl AsRef<Path>,
+        dialect: DialectKind,
+    ) -> Result<(Simulator, Option<String>), Error> {
+        let data = fs::read_to_string(path).map_err(|e| Error::Cache(e.to_string()))?;
+        let cache: SimulatorCache =
+            serde_json::from_str(&data).map_err(|e| Error::Cache(e.to_string()))?;
+
+        if cache.dialect != dialect {
+            return Err(Error::Cache(format!(
+                "Cache dialect {} doesn't match {dialect}",
+                cache.dialect
+            )));
+        }
+
+        let mut sim = Simulator::with_dialect(dialect);
+        sim.tables = Arc::new(cache.tables.into_iter().collect());
+        sim.schemas = cache.schemas.into_iter().collect();
+        sim.types = cache.types.into_iter().collect();
+
+        Ok((sim, cache.hash))
+    }
+}
+
+/// Serializes a map of a Table in the order of its keys.
+pub(crate) fn sorted_map<S: Serializer, V: Serialize>(
+    map: &HashMap<String, V>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    map.iter().collect::<BTreeMap<_, _>>().serialize(serializer)
+}
+
+/// Serializes the constraints of a Table in the order of their keys and the constraints.
+pub(crate) fn sorted_constraints<S: Serializer>(
+    constraints: &HashMap<String, HashSet<Constraint>>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    constraints
+        .iter()
+        .map(|(key, constraints)| (key, constraints.iter().collect::<BTreeSet<_>>()))
+        .collect::<BTreeMap<_, _>>()
+        .serialize(serializer)
+}
@@ -1,19 +1,128 @@
-use serde::Deserialize;
+use std::collections::HashMap;
 
-use crate::dialect::DialectKind;
+use serde::{Deserialize, Deserializer};
+
+use crate::{
+    Error,
+    dialect::DialectKind,
+    func::{Arity, FunctionSignature, Nullability},
+    ty::SqlType,
+};
 
 #[derive(Debug, Clone, Deserialize)]
 #[serde(default)]
 pub struct Config {
     pub dialect: DialectKind,
-    pub migrations: String,
+    /// Directories of the migrations (`*` matches any name, eg. `crates/*/migrations`),
+    /// otherwise `./migrations`.
+    ///
+    /// Either a single directory or a list of them.
+    #[serde(deserialize_with = "one_or_many")]
+    pub migrations: Vec<String>,
+    /// A single file with the whole schema (eg. `schema.sql`), used instead of migrations.
+    pub schema_path: Option<String>,
+    /// Path to a schema cache that is used instead of the migrations when it is valid,
+    /// otherwise `.truffle/schema.json`.
+    pub cache: Option<String>,
+    /// The schema of unqualified table names, otherwise the default of the dialect.
+    pub default_schema: Option<String>,
+    /// User-defined type names (eg. Postgres domains) mapped to the type they are based on.
+    pub types: HashMap<String, String>,
+    /// User-defined functions that exist in the database.
+    pub functions: HashMap<String, FunctionConfig>,
 }
 
 impl Default for Config {
     fn default() -> Self {
         Self {
             dialect: DialectKind::Generic,
-            migrations: "./migrations".into(),
+            migrations: vec![],
+            schema_path: None,
+            cache: None,
+            default_schema: None,
+            types: HashMap::new(),
+            functions: HashMap::new(),
+        }
+    }
+}
+
+impl Config {
+    /// The directories (or patterns) of the migrations.
+    pub fn migrations_dirs(&self) -> Vec<&str> {
+        if self.migrations.is_empty() {
+            vec!["./migrations"]
+        } else {
+            self.migrations.iter().map(|dir| dir.as_str()).collect()
         }
     }
 }
+
+fn one_or_many<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<String>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany {
+        One(String),
+        Many(Vec<String>),
+    }
+
+    Ok(match OneOrMany::deserialize(deserializer)? {
+        OneOrMany::One(one) => vec![one],
+        OneOrMany::Many(many) => many,
+    })
+}
+
+/// A user-defined function, eg.
+///
+/// ```toml
+/// [functions.slugify]
+/// args = ["text"]
+/// returns = "text"
+/// ```
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct FunctionConfig {
+    pub args: Vec<String>,
+    /// Arguments that can be left out, after the required ones.
+    pub optional: Vec<String>,
+    /// The type of every argument, for functions that take any number of them.
+    pub variadic: Option<String>,
+    pub returns: String,
+    /// Whether the result is always (or never) NULL, otherwise only with a NULL argument.
+    pub nullable: Option<bool>,
+}
+
+impl FunctionConfig {
+    pub fn signature(&self) -> Result<FunctionSignature, Error> {
+        let parse = |tys: &[String]| {
+            tys.iter()
+                .map(|ty| ty.parse())
+                .collect::<Result<Vec<SqlType>, Error>>()
+        };
+
+        let arity = if let Some(ty) = &self.variadic {
+            Arity::Variadic {
+                ty: ty.parse()?,
+                min: 1,
+            }
+        } else if !self.optional.is_empty() {
+            Arity::Optional {
+                required: parse(&self.args)?,
+                optional: parse(&self.optional)?,
+            }
+        } else {
+            Arity::Fixed(parse(&self.args)?)
+        };
+
+        let nullability = match self.nullable {
+            Some(true) => Nullability::Nullable,
+            Some(false) => Nullability::NotNull,
+            None => Nullability::Arguments,
+        };
+
+        Ok(FunctionSignature {
+            arity,
+            returns: self.returns.parse()?,
+            nullability,
+        })
+    }
+}
@@ -1,2 +1,5 @@
+#[cfg(feature = "serde")]
+pub mod cache;
 pub mod config;
 pub mod immutable;
+pub(crate) mod split;
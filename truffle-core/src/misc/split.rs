@@ -0,0 +1,159 @@
+use std::{iter::Peekable, str::CharIndices};
+
+use sqlparser::tokenizer::Location;
+
+use crate::DialectKind;
+
+/// Splits SQL into its statements, on the semicolons outside of strings, identifiers and comments.
+///
+/// Yields the text of each statement (without its semicolon) and the location it starts at,
+/// so that each statement can be tokenized on its own.
+pub(crate) fn split_statements(
+    sql: &str,
+    kind: DialectKind,
+) -> impl Iterator<Item = (&str, Location)> {
+    let mut splitter = Splitter {
+        sql,
+        kind,
+        chars: sql.char_indices().peekable(),
+        offset: 0,
+        location: Location { line: 1, column: 1 },
+        done: false,
+    };
+
+    std::iter::from_fn(move || splitter.next_statement())
+}
+
+struct Splitter<'a> {
+    sql: &'a str,
+    kind: DialectKind,
+    chars: Peekable<CharIndices<'a>>,
+    /// The offset of the next character.
+    offset: usize,
+    /// The location of the next character, counted the way the tokenizer does.
+    location: Location,
+    done: bool,
+}
+
+impl<'a> Splitter<'a> {
+    fn next_statement(&mut self) -> Option<(&'a str, Location)> {
+        if self.done {
+            return None;
+        }
+
+        let sql = self.sql;
+        let (start, location) = (self.offset, self.location);
+        let mut previous = None;
+
+        while let Some(c) = self.bump() {
+            match c {
+                ';' => return Some((&sql[start..self.offset - 1], location)),
+                '\'' => self.skip_quoted('\'', false),
+                // Escape strings, eg. `E'it\'s'`, unless the quote ends an identifier.
+                'e' | 'E'
+                    if self.kind != DialectKind::Sqlite
+                        && self.peek() == Some('\'')
+                        && !previous.is_some_and(|p: char| p.is_alphanumeric() || p == '_') =>
+                {
+                    self.bump();
+                    self.skip_quoted('\'', true);
+                }
+                '"' => self.skip_quoted('"', false),
+                '`' if self.kind != DialectKind::Postgres => self.skip_quoted('`', false),
+                '[' if self.kind == DialectKind::Sqlite => self.skip_quoted(']', false),
+                '-' if self.peek() == Some('-') => self.skip_line_comment(),
+                '/' if self.peek() == Some('*') => {
+                    self.bump();
+                    self.skip_block_comment();
+                }
+                '$' if self.kind != DialectKind::Sqlite => self.skip_dollar_quoted(),
+                _ => {}
+            }
+
+            previous = Some(c);
+        }
+
+        self.done = true;
+        Some((&sql[start..], location))
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.chars.peek().map(|(_, c)| *c)
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let (offset, c) = self.chars.next()?;
+        self.offset = offset + c.len_utf8();
+
+        if c == '\n' {
+            self.location.line += 1;
+            self.location.column = 1;
+        } else {
+            self.location.column += 1;
+        }
+
+        Some(c)
+    }
+
+    /// Skips to the closing quote, where a doubled quote is an escaped one.
+    fn skip_quoted(&mut self, quote: char, backslash: bool) {
+        while let Some(c) = self.bump() {
+            if backslash && c == '\\' {
+                self.bump();
+            } else if c == quote {
+                if self.peek() != Some(quote) {
+                    return;
+                }
+
+                self.bump();
+            }
+        }
+    }
+
+    fn skip_line_comment(&mut self) {
+        while self.bump().is_some_and(|c| c != '\n') {}
+    }
+
+    fn skip_block_comment(&mut self) {
+        // Postgres nests block comments, SQLite doesn't.
+        let nested = self.kind != DialectKind::Sqlite;
+        let mut depth = 1;
+
+        while let Some(c) = self.bump() {
+            if c == '*' && self.peek() == Some('/') {
+                self.bump();
+                depth -= 1;
+
+                if depth == 0 {
+                    return;
+                }
+            } else if nested && c == '/' && self.peek() == Some('*') {
+                self.bump();
+                depth += 1;
+            }
+        }
+    }
+
+    /// Skips a dollar-quoted string, eg. `$body$ ... $body$`, but not a placeholder like `$1`.
+    fn skip_dollar_quoted(&mut self) {
+        let sql = self.sql;
+        let start = self.offset;
+        while self.peek().is_some_and(|c| c.is_alphanumeric() || c == '_') {
+            self.bump();
+        }
+
+        let tag = &sql[start..self.offset];
+        if self.peek() != Some('$') || tag.starts_with(|c: char| c.is_ascii_digit()) {
+            return;
+        }
+        self.bump();
+
+        let delimiter = format!("${tag}$");
+        let end = match sql[self.offset..].find(&delimiter) {
+            Some(index) => self.offset + index + delimiter.len(),
+            None => sql.len(),
+        };
+
+        while self.offset < end && self.bump().is_some() {}
+    }
+}
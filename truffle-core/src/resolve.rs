@@ -1,8 +1,9 @@
-use std::fmt::Display;
+use std::{collections::HashMap, fmt::Display};
 
-use crate::{column::Column, ty::SqlType};
+use crate::{Error, column::Column, ty::SqlType};
 use indexmap::IndexMap;
 use itertools::Itertools;
+use sqlparser::tokenizer::Location;
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -32,12 +33,56 @@ impl Display for ColumnRef {
     }
 }
 
+/// How a placeholder in the SQL identifies its input.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum Placeholder {
+    /// `?`, every occurrence is a new input in positional order.
+    Anonymous,
+    /// `$1` or `?1`, every occurrence of the same number is the same input.
+    Numbered(usize),
+    /// `:name`, `@name` or `$name`, every occurrence of the same name is the same input.
+    Named(String),
+}
+
+impl Placeholder {
+    pub fn parse(placeholder: impl AsRef<str>) -> Placeholder {
+        let place = placeholder.as_ref();
+
+        if place == "?" {
+            return Placeholder::Anonymous;
+        }
+
+        match parse_placeholder(place) {
+            Some(index) => Placeholder::Numbered(index),
+            None => Placeholder::Named(place.to_string()),
+        }
+    }
+}
+
+impl Display for Placeholder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Placeholder::Anonymous => write!(f, "?"),
+            Placeholder::Numbered(index) => write!(f, "${index}"),
+            Placeholder::Named(name) => write!(f, "{name}"),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct ResolvedQuery {
     // TODO: Consider logging if the query will return One or Many result columns?
     pub inputs: Vec<Column>,
+    // The placeholder that each input came from.
+    pub placeholders: Vec<Placeholder>,
     pub outputs: IndexMap<ColumnRef, Column>,
+    // Whether the statement modifies rows (INSERT/UPDATE/DELETE).
+    pub mutates: bool,
+    // Every placeholder in the SQL ordered by position, which the inputs are built from.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    occurrences: Vec<(Location, Placeholder, Column)>,
 }
 
 impl Display for ResolvedQuery {
@@ -71,32 +116,106 @@ impl ResolvedQuery {
         self.inputs.get(index)
     }
 
-    pub fn insert_input(&mut self, placeholder: impl AsRef<str>, col: Column) {
-        if let Some(index) = parse_placeholder(placeholder) {
-            let idx = index - 1;
-
-            if idx < self.inputs.len() {
-                // Replace existing entry at index.
-                //
-                // TODO: Ensure that the sql types here are identical INSTEAD of replacing it.
-                // It should then error if they are different types as they can't share a placeholder.
-                _ = std::mem::replace(&mut self.inputs[idx], col);
-            } else {
-                // Extend the Vec then insert.
-                self.inputs.resize_with(index, || Column {
-                    ty: SqlType::Unknown("".to_string()),
-                    nullable: false,
-                    default: false,
-                });
-                self.inputs[idx] = col;
+    /// Registers the input for a placeholder at the given position in the SQL.
+    ///
+    /// Anonymous placeholders are numbered by their position, no matter the order
+    /// they are resolved in. Every occurrence of a numbered or named placeholder
+    /// is the same input, so their types must agree once the inputs are built.
+    pub fn insert_input(&mut self, placeholder: impl AsRef<str>, location: Location, col: Column) {
+        let placeholder = Placeholder::parse(placeholder);
+        self.occurrences.push((location, placeholder, col));
+    }
+
+    /// Registers all of the inputs of another query, keeping their placeholders.
+    pub fn merge_inputs(&mut self, other: ResolvedQuery) {
+        self.occurrences.extend(other.occurrences);
+    }
+
+    /// Builds the inputs from the placeholder occurrences, once the statement is resolved.
+    pub(crate) fn build_inputs(&mut self) -> Result<(), Error> {
+        // Occurrences are registered in the order they are resolved, not their position.
+        self.occurrences.sort_by_key(|(location, _, _)| *location);
+
+        // Numbered placeholders can skip a number, which leaves a gap.
+        let mut inputs: Vec<Option<(Placeholder, Column)>> = vec![];
+        let mut named = HashMap::new();
+
+        for (_, placeholder, col) in &self.occurrences {
+            let existing = match placeholder {
+                Placeholder::Anonymous => None,
+                Placeholder::Numbered(index) => {
+                    if inputs.len() < *index {
+                        inputs.resize(*index, None);
+                    }
+
+                    let idx = index - 1;
+                    if inputs[idx].is_none() {
+                        inputs[idx] = Some((placeholder.clone(), col.clone()));
+                        continue;
+                    }
+
+                    Some(idx)
+                }
+                Placeholder::Named(_) => {
+                    let existing = named.get(placeholder).copied();
+                    if existing.is_none() {
+                        named.insert(placeholder.clone(), inputs.len());
+                    }
+
+                    existing
+                }
+            };
+
+            match existing {
+                Some(idx) => {
+                    // A reused placeholder takes a type that fits every occurrence.
+                    let (_, input) = inputs[idx].as_mut().unwrap();
+                    input.ty = input
+                        .ty
+                        .promote(&col.ty)
+                        .ok_or_else(|| Error::TypeMismatch {
+                            expected: input.ty.clone(),
+                            got: col.ty.clone(),
+                        })?;
+                    input.nullable &= col.nullable;
+                    input.default &= col.default;
+                }
+                None => inputs.push(Some((placeholder.clone(), col.clone()))),
             }
-        } else {
-            self.inputs.push(col);
         }
+
+        (self.placeholders, self.inputs) = inputs
+            .into_iter()
+            .enumerate()
+            .map(|(i, input)| {
+                input.unwrap_or_else(|| {
+                    let unknown = Column::new(SqlType::Unknown("".to_string()), false, false);
+                    (Placeholder::Numbered(i + 1), unknown)
+                })
+            })
+            .unzip();
+
+        Ok(())
     }
 
-    pub fn insert_input_at(&mut self, index: usize, col: Column) {
-        self.inputs.insert(index.min(self.inputs.len()), col);
+    /// The position of every placeholder in the SQL, with the index of the input it binds.
+    pub fn placeholder_locations(&self) -> Vec<(Location, usize)> {
+        let mut anonymous = self
+            .placeholders
+            .iter()
+            .positions(|placeholder| *placeholder == Placeholder::Anonymous);
+
+        self.occurrences
+            .iter()
+            .filter_map(|(location, placeholder, _)| {
+                let index = match placeholder {
+                    Placeholder::Anonymous => anonymous.next()?,
+                    placeholder => self.placeholders.iter().position(|p| p == placeholder)?,
+                };
+
+                Some((*location, index))
+            })
+            .collect()
     }
 
     pub fn insert_output(&mut self, key: ColumnRef, col: Column) {
@@ -130,12 +249,18 @@ fn parse_placeholder(placeholder: impl AsRef<str>) -> Option<usize> {
     if place == "?" {
         return None;
     }
-    place.split_at(1).1.parse().ok()
+
+    let (prefix, index) = place.split_at(1);
+    if prefix != "$" && prefix != "?" {
+        return None;
+    }
+
+    index.parse().ok().filter(|index| *index > 0)
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::resolve::parse_placeholder;
+    use crate::resolve::{Placeholder, parse_placeholder};
 
     #[test]
     fn parse_unnumbered_placeholder() {
@@ -148,4 +273,13 @@ mod tests {
         let placeholder = "$5";
         assert_eq!(parse_placeholder(placeholder), Some(5))
     }
+
+    #[test]
+    fn parse_named_placeholder() {
+        assert_eq!(parse_placeholder(":5"), None);
+        assert_eq!(
+            Placeholder::parse(":name"),
+            Placeholder::Named(":name".to_string())
+        );
+    }
 }
@@ -47,7 +47,7 @@ impl Simulator {
                     }
                     _ => {
                         return Err(Error::Unsupported(format!(
-                            "Unsupported Select Expr: {expr:?}"
+                            "Unsupported Select Expr: {expr}"
                         )));
                     }
                 },
@@ -1,7 +1,7 @@
 use std::collections::{HashMap, HashSet, hash_map::Entry};
 
 use indexmap::{IndexMap, map::IndexedEntry};
-use sqlparser::ast::ReferentialAction;
+use sqlparser::ast::{Expr, ReferentialAction};
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -9,7 +9,7 @@ use serde::{Deserialize, Serialize};
 use crate::column::Column;
 
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
-#[derive(Debug, Clone, Hash, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord, Default)]
 pub enum OnAction {
     #[default]
     Nothing,
@@ -32,7 +32,7 @@ impl From<ReferentialAction> for OnAction {
 }
 
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
-#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Constraint {
     ForeignKey {
         foreign_table: String,
@@ -55,7 +55,26 @@ impl Constraint {
 #[derive(Clone, Debug, Default)]
 pub struct Table {
     pub columns: IndexMap<String, Column>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(serialize_with = "crate::misc::cache::sorted_constraints")
+    )]
     pub constraints: HashMap<String, HashSet<Constraint>>,
+    /// The text of `COMMENT ON TABLE`.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub comment: Option<String>,
+    /// The text of `COMMENT ON COLUMN`, by column name.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, serialize_with = "crate::misc::cache::sorted_map")
+    )]
+    pub column_comments: HashMap<String, String>,
+    /// CHECK expressions, validated again whenever the columns change.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub checks: Vec<Expr>,
+    /// Generation expressions, by column name.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub generated: IndexMap<String, Expr>,
 }
 
 impl Table {
@@ -63,6 +82,65 @@ impl Table {
         format!("({})", columns.join(", ").to_lowercase())
     }
 
+    /// The columns of a key made by [`Table::create_compound_key`].
+    pub fn compound_key_columns(key: &str) -> Vec<String> {
+        key.trim_start_matches('(')
+            .trim_end_matches(')')
+            .split(", ")
+            .map(|c| c.to_string())
+            .collect()
+    }
+
+    /// Renames a column, keeping its position and the constraints on it.
+    pub fn rename_column(&mut self, old: &str, new: &str) {
+        self.columns = std::mem::take(&mut self.columns)
+            .into_iter()
+            .map(|(name, column)| {
+                if name == old {
+                    (new.to_string(), column)
+                } else {
+                    (name, column)
+                }
+            })
+            .collect();
+
+        if let Some(comment) = self.column_comments.remove(old) {
+            self.column_comments.insert(new.to_string(), comment);
+        }
+
+        if let Some(index) = self.generated.get_index_of(old) {
+            let (_, expr) = self.generated.shift_remove_index(index).unwrap();
+            self.generated.shift_insert(index, new.to_string(), expr);
+        }
+
+        let old = old.to_lowercase();
+
+        self.constraints = std::mem::take(&mut self.constraints)
+            .into_iter()
+            .map(|(key, constraints)| {
+                let columns: Vec<String> = Table::compound_key_columns(&key)
+                    .into_iter()
+                    .map(|c| if c == old { new.to_string() } else { c })
+                    .collect();
+
+                (Table::create_compound_key(&columns), constraints)
+            })
+            .collect();
+    }
+
+    /// Removes a column and every constraint on it.
+    pub fn remove_column(&mut self, name: &str) -> Option<Column> {
+        let lowercase = name.to_lowercase();
+
+        self.constraints
+            .retain(|key, _| !Table::compound_key_columns(key).contains(&lowercase));
+
+        self.column_comments.remove(name);
+        self.generated.shift_remove(name);
+
+        self.columns.shift_remove(name)
+    }
+
     pub fn has_column(&self, name: &str) -> bool {
         self.columns.contains_key(name)
     }
@@ -1,12 +1,14 @@
-use std::{fmt::Display, hash::Hash};
+use std::{fmt::Display, hash::Hash, str::FromStr};
 
 use itertools::Itertools;
-use sqlparser::ast::DataType;
+#[cfg(feature = "decimal")]
+use sqlparser::ast::ExactNumberInfo;
+use sqlparser::ast::{ArrayElemTypeDef, CharacterLength, DataType, ObjectName};
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-use crate::column::Column;
+use crate::{Error, column::Column};
 
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 #[derive(Debug, Clone, Eq)]
@@ -27,9 +29,20 @@ pub enum SqlType {
 
     /// String
     Text,
+    /// Variable length string with a maximum length
+    Varchar {
+        len: u64,
+    },
+    /// Fixed length string
+    Char {
+        len: u64,
+    },
 
     Boolean,
 
+    /// Binary data
+    Bytes,
+
     #[cfg(feature = "time")]
     Date,
     #[cfg(feature = "time")]
@@ -38,12 +51,41 @@ pub enum SqlType {
     Timestamp,
     #[cfg(feature = "time")]
     TimestampTz,
+    /// Duration between two points in time
+    #[cfg(feature = "time")]
+    Interval,
 
     #[cfg(feature = "uuid")]
     Uuid,
 
     #[cfg(feature = "json")]
     Json,
+    /// Binary JSON (Postgres)
+    #[cfg(feature = "json")]
+    Jsonb,
+
+    /// Exact numeric with optional precision and scale.
+    #[cfg(feature = "decimal")]
+    Decimal {
+        precision: Option<u8>,
+        scale: Option<u8>,
+    },
+
+    /// IPv4 or IPv6 host address, with an optional netmask (Postgres)
+    #[cfg(feature = "ipnetwork")]
+    Inet,
+    /// IPv4 or IPv6 network (Postgres)
+    #[cfg(feature = "ipnetwork")]
+    Cidr,
+
+    /// Array of another type
+    Array(Box<SqlType>),
+
+    /// User-defined enum (eg. `CREATE TYPE status AS ENUM ('a', 'b')`)
+    Enum {
+        name: String,
+        variants: Vec<String>,
+    },
 
     Unknown(String),
 }
@@ -57,8 +99,144 @@ impl SqlType {
         matches!(self, Self::Float | Self::Double)
     }
 
+    pub fn is_decimal(&self) -> bool {
+        #[cfg(feature = "decimal")]
+        {
+            matches!(self, Self::Decimal { .. })
+        }
+        #[cfg(not(feature = "decimal"))]
+        {
+            false
+        }
+    }
+
     pub fn is_numeric(&self) -> bool {
-        self.is_integer() || self.is_floating()
+        self.is_integer() || self.is_floating() || self.is_decimal()
+    }
+
+    pub fn is_temporal(&self) -> bool {
+        #[cfg(feature = "time")]
+        {
+            matches!(
+                self,
+                Self::Date | Self::Time | Self::Timestamp | Self::TimestampTz
+            )
+        }
+        #[cfg(not(feature = "time"))]
+        {
+            false
+        }
+    }
+
+    pub fn is_json(&self) -> bool {
+        #[cfg(feature = "json")]
+        {
+            matches!(self, Self::Json | Self::Jsonb)
+        }
+        #[cfg(not(feature = "json"))]
+        {
+            false
+        }
+    }
+
+    pub fn is_network(&self) -> bool {
+        #[cfg(feature = "ipnetwork")]
+        {
+            matches!(self, Self::Inet | Self::Cidr)
+        }
+        #[cfg(not(feature = "ipnetwork"))]
+        {
+            false
+        }
+    }
+
+    pub fn is_text(&self) -> bool {
+        matches!(self, Self::Text | Self::Varchar { .. } | Self::Char { .. })
+    }
+
+    /// Whether a value of the other type can be used where this type is expected.
+    ///
+    /// Integers of different widths are implicitly converted between each other,
+    /// and so are strings of different lengths. A cidr can be used where an inet is expected.
+    pub fn is_compatible(&self, other: &SqlType) -> bool {
+        match (self, other) {
+            (SqlType::Tuple(first), SqlType::Tuple(second)) => {
+                first.len() == second.len()
+                    && first
+                        .iter()
+                        .zip(second.iter())
+                        .all(|(f, s)| f.ty.is_compatible(&s.ty))
+            }
+            (SqlType::Array(first), SqlType::Array(second)) => first.is_compatible(second),
+            // Only a cidr implicitly converts to an inet, not the other way around.
+            #[cfg(feature = "ipnetwork")]
+            (SqlType::Inet, SqlType::Cidr) => true,
+            _ => {
+                self == other
+                    || (self.is_integer() && other.is_integer())
+                    || (self.is_decimal() && other.is_decimal())
+                    || (self.is_text() && other.is_text())
+            }
+        }
+    }
+
+    /// The type that the result of arithmetic between the two types is promoted to.
+    ///
+    /// Numeric types widen (smallint < integer < bigint < decimal < float < double),
+    /// anything else has to be compatible.
+    pub fn promote(&self, other: &SqlType) -> Option<SqlType> {
+        fn rank(ty: &SqlType) -> u8 {
+            match ty {
+                SqlType::SmallInt => 0,
+                SqlType::Integer => 1,
+                SqlType::BigInt => 2,
+                SqlType::Float => 4,
+                SqlType::Double => 5,
+                // Decimal
+                _ => 3,
+            }
+        }
+
+        if self.is_numeric() && other.is_numeric() {
+            if rank(other) > rank(self) {
+                Some(other.clone())
+            } else {
+                Some(self.clone())
+            }
+        } else if self.is_compatible(other) {
+            Some(self.clone())
+        } else {
+            None
+        }
+    }
+
+    /// Whether a value of this type can be explicitly CAST to the given type.
+    pub fn is_castable_to(&self, to: &SqlType) -> bool {
+        if self.is_compatible(to) {
+            return true;
+        }
+
+        match (self, to) {
+            // We don't know enough about these types to reject anything.
+            (SqlType::Unknown(_), _) | (_, SqlType::Unknown(_)) => true,
+            (SqlType::Tuple(_), _) | (_, SqlType::Tuple(_)) => false,
+            (SqlType::Array(from), SqlType::Array(to)) => from.is_castable_to(to),
+            // Everything can be parsed from and formatted into text.
+            (from, _) if from.is_text() => true,
+            (_, to) if to.is_text() => true,
+            (from, to) if from.is_numeric() && to.is_numeric() => true,
+            (from, to) if from.is_json() && to.is_json() => true,
+            (SqlType::Boolean, to) if to.is_integer() => true,
+            (from, SqlType::Boolean) if from.is_integer() => true,
+            #[cfg(feature = "time")]
+            (
+                SqlType::Date | SqlType::Timestamp | SqlType::TimestampTz,
+                SqlType::Date | SqlType::Timestamp | SqlType::TimestampTz,
+            ) => true,
+            #[cfg(feature = "time")]
+            (SqlType::Timestamp | SqlType::TimestampTz, SqlType::Time) => true,
+            _ => false,
+        }
     }
 }
 
@@ -78,7 +256,10 @@ impl PartialEq for SqlType {
             (SqlType::Float, SqlType::Float) => true,
             (SqlType::Double, SqlType::Double) => true,
             (SqlType::Text, SqlType::Text) => true,
+            (SqlType::Varchar { len: l1 }, SqlType::Varchar { len: l2 }) => l1 == l2,
+            (SqlType::Char { len: l1 }, SqlType::Char { len: l2 }) => l1 == l2,
             (SqlType::Boolean, SqlType::Boolean) => true,
+            (SqlType::Bytes, SqlType::Bytes) => true,
             #[cfg(feature = "time")]
             (SqlType::Date, SqlType::Date) => true,
             #[cfg(feature = "time")]
@@ -87,10 +268,40 @@ impl PartialEq for SqlType {
             (SqlType::Timestamp, SqlType::Timestamp) => true,
             #[cfg(feature = "time")]
             (SqlType::TimestampTz, SqlType::TimestampTz) => true,
+            #[cfg(feature = "time")]
+            (SqlType::Interval, SqlType::Interval) => true,
             #[cfg(feature = "uuid")]
             (SqlType::Uuid, SqlType::Uuid) => true,
             #[cfg(feature = "json")]
             (SqlType::Json, SqlType::Json) => true,
+            #[cfg(feature = "json")]
+            (SqlType::Jsonb, SqlType::Jsonb) => true,
+            #[cfg(feature = "decimal")]
+            (
+                SqlType::Decimal {
+                    precision: p1,
+                    scale: s1,
+                },
+                SqlType::Decimal {
+                    precision: p2,
+                    scale: s2,
+                },
+            ) => p1 == p2 && s1 == s2,
+            #[cfg(feature = "ipnetwork")]
+            (SqlType::Inet, SqlType::Inet) => true,
+            #[cfg(feature = "ipnetwork")]
+            (SqlType::Cidr, SqlType::Cidr) => true,
+            (SqlType::Array(a), SqlType::Array(b)) => a == b,
+            (
+                SqlType::Enum {
+                    name: n1,
+                    variants: v1,
+                },
+                SqlType::Enum {
+                    name: n2,
+                    variants: v2,
+                },
+            ) => n1 == n2 && v1 == v2,
             (SqlType::Unknown(a), SqlType::Unknown(b)) => a == b,
             _ => false,
         }
@@ -119,6 +330,8 @@ impl Hash for SqlType {
             SqlType::Uuid => state.write_u8(12),
             #[cfg(feature = "json")]
             SqlType::Json => state.write_u8(13),
+            #[cfg(feature = "json")]
+            SqlType::Jsonb => state.write_u8(22),
             SqlType::Tuple(columns) => {
                 state.write_u8(14);
                 state.write_usize(columns.len());
@@ -128,21 +341,299 @@ impl Hash for SqlType {
                 state.write_u8(15);
                 text.hash(state)
             }
+            #[cfg(feature = "decimal")]
+            SqlType::Decimal { precision, scale } => {
+                state.write_u8(16);
+                precision.hash(state);
+                scale.hash(state)
+            }
+            SqlType::Bytes => state.write_u8(17),
+            #[cfg(feature = "time")]
+            SqlType::Interval => state.write_u8(18),
+            SqlType::Varchar { len } => {
+                state.write_u8(19);
+                len.hash(state)
+            }
+            SqlType::Char { len } => {
+                state.write_u8(20);
+                len.hash(state)
+            }
+            SqlType::Array(inner) => {
+                state.write_u8(21);
+                inner.hash(state)
+            }
+            SqlType::Enum { name, variants } => {
+                state.write_u8(23);
+                name.hash(state);
+                variants.hash(state)
+            }
+            #[cfg(feature = "ipnetwork")]
+            SqlType::Inet => state.write_u8(24),
+            #[cfg(feature = "ipnetwork")]
+            SqlType::Cidr => state.write_u8(25),
         }
     }
 }
 
+/// Formats the canonical name of the type, which can be parsed back with [`FromStr`].
 impl Display for SqlType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            SqlType::Tuple(sql_types) => write!(
+            SqlType::Tuple(columns) => write!(
                 f,
-                "Tuple({})",
-                sql_types.iter().map(|ty| ty.to_string()).join(", ")
+                "tuple({})",
+                columns.iter().map(|column| column.to_string()).join(", ")
             ),
-            _ => write!(f, "{self:#?}"),
+            SqlType::SmallInt => write!(f, "smallint"),
+            SqlType::Integer => write!(f, "integer"),
+            SqlType::BigInt => write!(f, "bigint"),
+            SqlType::Float => write!(f, "float"),
+            SqlType::Double => write!(f, "double"),
+            SqlType::Text => write!(f, "text"),
+            SqlType::Varchar { len } => write!(f, "varchar({len})"),
+            SqlType::Char { len } => write!(f, "char({len})"),
+            SqlType::Boolean => write!(f, "boolean"),
+            SqlType::Bytes => write!(f, "bytes"),
+            #[cfg(feature = "time")]
+            SqlType::Date => write!(f, "date"),
+            #[cfg(feature = "time")]
+            SqlType::Time => write!(f, "time"),
+            #[cfg(feature = "time")]
+            SqlType::Timestamp => write!(f, "timestamp"),
+            #[cfg(feature = "time")]
+            SqlType::TimestampTz => write!(f, "timestamptz"),
+            #[cfg(feature = "time")]
+            SqlType::Interval => write!(f, "interval"),
+            #[cfg(feature = "uuid")]
+            SqlType::Uuid => write!(f, "uuid"),
+            #[cfg(feature = "json")]
+            SqlType::Json => write!(f, "json"),
+            #[cfg(feature = "json")]
+            SqlType::Jsonb => write!(f, "jsonb"),
+            #[cfg(feature = "decimal")]
+            SqlType::Decimal { precision, scale } => match (precision, scale) {
+                (Some(precision), Some(scale)) => write!(f, "decimal({precision},{scale})"),
+                (Some(precision), None) => write!(f, "decimal({precision})"),
+                _ => write!(f, "decimal"),
+            },
+            #[cfg(feature = "ipnetwork")]
+            SqlType::Inet => write!(f, "inet"),
+            #[cfg(feature = "ipnetwork")]
+            SqlType::Cidr => write!(f, "cidr"),
+            SqlType::Array(inner) => write!(f, "{inner}[]"),
+            SqlType::Enum { name, variants } => write!(
+                f,
+                "enum {name}({})",
+                variants
+                    .iter()
+                    .map(|variant| format!("'{}'", variant.replace('\'', "''")))
+                    .join(", ")
+            ),
+            SqlType::Unknown(name) => write!(f, "{name}"),
+        }
+    }
+}
+
+impl FromStr for SqlType {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // Enum names and variants keep their case, so nested types are parsed from the input.
+        let trimmed = s.trim();
+        let name = trimmed.to_lowercase();
+
+        if let Some(inner) =
+            strip_prefix_ignore_case(trimmed, "tuple(").and_then(|rest| rest.strip_suffix(')'))
+        {
+            let columns = split_top_level(inner)
+                .into_iter()
+                .map(Column::from_str)
+                .collect::<Result<Vec<_>, _>>()?;
+
+            return Ok(SqlType::Tuple(columns));
+        }
+
+        if let Some(inner) = trimmed.strip_suffix("[]") {
+            return Ok(SqlType::Array(Box::new(inner.parse()?)));
+        }
+
+        if let Some(rest) = strip_prefix_ignore_case(trimmed, "enum ") {
+            let (enum_name, variants) = rest
+                .split_once('(')
+                .and_then(|(enum_name, rest)| Some((enum_name.trim(), rest.strip_suffix(')')?)))
+                .filter(|(enum_name, _)| !enum_name.is_empty())
+                .ok_or_else(|| Error::InvalidType(s.to_string()))?;
+
+            let variants = split_top_level(variants)
+                .into_iter()
+                .map(|variant| {
+                    variant
+                        .trim()
+                        .strip_prefix('\'')
+                        .and_then(|rest| rest.strip_suffix('\''))
+                        .map(|variant| variant.replace("''", "'"))
+                        .ok_or_else(|| Error::InvalidType(s.to_string()))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+
+            return Ok(SqlType::Enum {
+                name: enum_name.to_string(),
+                variants,
+            });
+        }
+
+        for (prefix, varying) in [("varchar(", true), ("char(", false)] {
+            if let Some(len) = name
+                .strip_prefix(prefix)
+                .and_then(|rest| rest.strip_suffix(')'))
+            {
+                let len = len
+                    .trim()
+                    .parse()
+                    .map_err(|_| Error::InvalidType(s.to_string()))?;
+
+                return Ok(if varying {
+                    SqlType::Varchar { len }
+                } else {
+                    SqlType::Char { len }
+                });
+            }
+        }
+
+        #[cfg(feature = "decimal")]
+        if let Some(params) = name.strip_prefix("decimal") {
+            let params = params.trim();
+
+            if params.is_empty() {
+                return Ok(SqlType::Decimal {
+                    precision: None,
+                    scale: None,
+                });
+            }
+
+            let params = params
+                .strip_prefix('(')
+                .and_then(|rest| rest.strip_suffix(')'))
+                .ok_or_else(|| Error::InvalidType(s.to_string()))?;
+
+            let mut numbers = params
+                .split(',')
+                .map(|n| n.trim().parse::<u8>())
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|_| Error::InvalidType(s.to_string()))?
+                .into_iter();
+
+            let (precision, scale) = (numbers.next(), numbers.next());
+
+            if precision.is_none() || numbers.next().is_some() {
+                return Err(Error::InvalidType(s.to_string()));
+            }
+
+            return Ok(SqlType::Decimal { precision, scale });
+        }
+
+        let ty = match name.as_str() {
+            "smallint" => SqlType::SmallInt,
+            "integer" => SqlType::Integer,
+            "bigint" => SqlType::BigInt,
+            "float" => SqlType::Float,
+            "double" => SqlType::Double,
+            "text" => SqlType::Text,
+            "boolean" => SqlType::Boolean,
+            "bytes" => SqlType::Bytes,
+            #[cfg(feature = "time")]
+            "date" => SqlType::Date,
+            #[cfg(feature = "time")]
+            "time" => SqlType::Time,
+            #[cfg(feature = "time")]
+            "timestamp" => SqlType::Timestamp,
+            #[cfg(feature = "time")]
+            "timestamptz" => SqlType::TimestampTz,
+            #[cfg(feature = "time")]
+            "interval" => SqlType::Interval,
+            #[cfg(feature = "uuid")]
+            "uuid" => SqlType::Uuid,
+            #[cfg(feature = "json")]
+            "json" => SqlType::Json,
+            #[cfg(feature = "json")]
+            "jsonb" => SqlType::Jsonb,
+            #[cfg(feature = "ipnetwork")]
+            "inet" => SqlType::Inet,
+            #[cfg(feature = "ipnetwork")]
+            "cidr" => SqlType::Cidr,
+            "" => return Err(Error::InvalidType(s.to_string())),
+            _ => SqlType::Unknown(s.trim().to_string()),
+        };
+
+        Ok(ty)
+    }
+}
+
+impl TryFrom<&str> for SqlType {
+    type Error = Error;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+/// Strips an ASCII prefix, ignoring its case.
+pub(crate) fn strip_prefix_ignore_case<'a>(s: &'a str, prefix: &str) -> Option<&'a str> {
+    s.get(..prefix.len())
+        .filter(|start| start.eq_ignore_ascii_case(prefix))
+        .map(|_| &s[prefix.len()..])
+}
+
+/// Strips an ASCII suffix, ignoring its case.
+pub(crate) fn strip_suffix_ignore_case<'a>(s: &'a str, suffix: &str) -> Option<&'a str> {
+    let start = s.len().checked_sub(suffix.len())?;
+
+    s.get(start..)
+        .filter(|end| end.eq_ignore_ascii_case(suffix))
+        .map(|_| &s[..start])
+}
+
+/// Splits on the commas that aren't nested within parentheses or quotes.
+fn split_top_level(s: &str) -> Vec<&str> {
+    let mut parts = vec![];
+    let mut depth = 0;
+    let mut quoted = false;
+    let mut start = 0;
+
+    for (i, c) in s.char_indices() {
+        match c {
+            // An escaped quote ('') toggles twice.
+            '\'' => quoted = !quoted,
+            _ if quoted => {}
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(&s[start..i]);
+                start = i + 1;
+            }
+            _ => {}
         }
     }
+
+    if !s[start..].trim().is_empty() || !parts.is_empty() {
+        parts.push(&s[start..]);
+    }
+
+    parts
+}
+
+/// Whether the type name is one of the Postgres auto-incrementing SERIAL types.
+pub(crate) fn is_serial(name: &ObjectName) -> bool {
+    matches!(
+        name.to_string().to_lowercase().as_str(),
+        "smallserial" | "serial2" | "serial" | "serial4" | "bigserial" | "serial8"
+    )
+}
+
+/// Whether the type name is one of the Postgres network address types.
+#[cfg(feature = "ipnetwork")]
+pub(crate) fn is_network(name: &ObjectName) -> bool {
+    matches!(name.to_string().to_lowercase().as_str(), "inet" | "cidr")
 }
 
 impl From<DataType> for SqlType {
@@ -156,13 +647,25 @@ impl From<DataType> for SqlType {
             DataType::Double(_) | DataType::Float8 => SqlType::Double,
             DataType::Float(Some(n)) if (4..=8).contains(&n) => SqlType::Double,
             DataType::Text | DataType::String(_) => SqlType::Text,
-            // TODO: Length validation.
+            DataType::CharacterVarying(Some(CharacterLength::IntegerLength { length, .. }))
+            | DataType::Varchar(Some(CharacterLength::IntegerLength { length, .. }))
+            | DataType::Nvarchar(Some(CharacterLength::IntegerLength { length, .. })) => {
+                SqlType::Varchar { len: length }
+            }
+            DataType::Character(Some(CharacterLength::IntegerLength { length, .. }))
+            | DataType::Char(Some(CharacterLength::IntegerLength { length, .. })) => {
+                SqlType::Char { len: length }
+            }
+            // Without a length (or with MAX) these are unbounded.
             DataType::Character(_)
             | DataType::CharacterVarying(_)
             | DataType::Char(_)
             | DataType::Varchar(_)
             | DataType::Nvarchar(_) => SqlType::Text,
             DataType::Bool | DataType::Boolean => SqlType::Boolean,
+            DataType::Blob(_) | DataType::Bytea | DataType::Binary(_) | DataType::Varbinary(_) => {
+                SqlType::Bytes
+            }
             #[cfg(feature = "time")]
             DataType::Date => SqlType::Date,
             #[cfg(feature = "time")]
@@ -171,10 +674,45 @@ impl From<DataType> for SqlType {
             DataType::TimestampNtz => SqlType::Timestamp,
             #[cfg(feature = "time")]
             DataType::Time(_, _) => SqlType::Time,
+            #[cfg(feature = "time")]
+            DataType::Interval => SqlType::Interval,
             #[cfg(feature = "uuid")]
             DataType::Uuid => SqlType::Uuid,
             #[cfg(feature = "json")]
             DataType::JSON => SqlType::Json,
+            #[cfg(feature = "json")]
+            DataType::JSONB => SqlType::Jsonb,
+            #[cfg(feature = "decimal")]
+            DataType::Decimal(info) | DataType::Numeric(info) | DataType::Dec(info) => {
+                let (precision, scale) = match info {
+                    ExactNumberInfo::None => (None, None),
+                    ExactNumberInfo::Precision(precision) => (u8::try_from(precision).ok(), None),
+                    ExactNumberInfo::PrecisionAndScale(precision, scale) => {
+                        (u8::try_from(precision).ok(), u8::try_from(scale).ok())
+                    }
+                };
+
+                SqlType::Decimal { precision, scale }
+            }
+            DataType::Custom(ref name, _) if is_serial(name) => {
+                match name.to_string().to_lowercase().as_str() {
+                    "smallserial" | "serial2" => SqlType::SmallInt,
+                    "bigserial" | "serial8" => SqlType::BigInt,
+                    _ => SqlType::Integer,
+                }
+            }
+            #[cfg(feature = "ipnetwork")]
+            DataType::Custom(ref name, _) if is_network(name) => {
+                match name.to_string().to_lowercase().as_str() {
+                    "cidr" => SqlType::Cidr,
+                    _ => SqlType::Inet,
+                }
+            }
+            DataType::Array(
+                ArrayElemTypeDef::AngleBracket(inner)
+                | ArrayElemTypeDef::SquareBracket(inner, _)
+                | ArrayElemTypeDef::Parenthesis(inner),
+            ) => SqlType::Array(Box::new((*inner).into())),
             _ => SqlType::Unknown(value.to_string()),
         }
     }
@@ -0,0 +1,261 @@
+use truffle::{DialectKind, Error, Simulator, ty::SqlType};
+
+#[test]
+fn alter_table_add_column() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table users (id int primary key, name text not null)")
+        .unwrap();
+    sim.execute("alter table users add column age int not null default 0")
+        .unwrap();
+
+    let age = sim.get_table("users").unwrap().get_column("age").unwrap();
+    assert_eq!(age.ty, SqlType::Integer);
+    assert!(!age.nullable);
+    assert!(age.default);
+
+    let resolve = sim.execute("select age from users where id = $1").unwrap();
+    assert_eq!(resolve.outputs.len(), 1);
+}
+
+#[test]
+fn alter_table_add_column_already_exists() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table users (id int primary key, name text not null)")
+        .unwrap();
+
+    assert_eq!(
+        sim.execute("alter table users add column name text"),
+        Err(Error::ColumnAlreadyExists("name".to_string()))
+    );
+
+    sim.execute("alter table users add column if not exists name text")
+        .unwrap();
+}
+
+#[test]
+fn alter_table_add_column_check() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table users (id int primary key, name text not null)")
+        .unwrap();
+
+    assert_eq!(
+        sim.execute("alter table users add column age int check (age > name)"),
+        Err(Error::TypeMismatch {
+            expected: SqlType::Integer,
+            got: SqlType::Text
+        })
+    );
+
+    // The failed ALTER leaves the table untouched.
+    assert!(!sim.get_table("users").unwrap().has_column("age"));
+}
+
+#[test]
+fn alter_table_drop_column() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table users (id int primary key, name text not null)")
+        .unwrap();
+    sim.execute("alter table users drop column name").unwrap();
+
+    assert!(!sim.get_table("users").unwrap().has_column("name"));
+    assert_eq!(
+        sim.execute("select name from users"),
+        Err(Error::ColumnDoesntExist("name".to_string()))
+    );
+}
+
+#[test]
+fn alter_table_drop_column_doesnt_exist() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table users (id int primary key, name text not null)")
+        .unwrap();
+
+    assert_eq!(
+        sim.execute("alter table users drop column age"),
+        Err(Error::ColumnDoesntExist("age".to_string()))
+    );
+
+    sim.execute("alter table users drop column if exists age")
+        .unwrap();
+}
+
+#[test]
+fn alter_table_drop_referenced_column() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table users (id int primary key, name text not null)")
+        .unwrap();
+    sim.execute(
+        "create table post (id int primary key, user_id int not null references users(id))",
+    )
+    .unwrap();
+
+    assert_eq!(
+        sim.execute("alter table users drop column id"),
+        Err(Error::ForeignKeyConstraint("id".to_string()))
+    );
+
+    // Dropping the referencing column drops its foreign key with it.
+    sim.execute("alter table post drop column user_id").unwrap();
+    sim.execute("alter table users drop column id").unwrap();
+}
+
+#[test]
+fn alter_table_rename_column() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table users (id int primary key, name text not null)")
+        .unwrap();
+    sim.execute(
+        "create table post (id int primary key, user_id int not null references users(id))",
+    )
+    .unwrap();
+
+    sim.execute("alter table users rename column id to user_id")
+        .unwrap();
+
+    let table = sim.get_table("users").unwrap();
+    assert!(table.has_column("user_id"));
+    assert!(!table.has_column("id"));
+    assert!(table.is_primary_key(&["user_id"]));
+
+    // The foreign key follows the renamed column.
+    assert_eq!(
+        sim.execute("alter table users drop column user_id"),
+        Err(Error::ForeignKeyConstraint("user_id".to_string()))
+    );
+}
+
+#[test]
+fn alter_table_rename_column_already_exists() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table users (id int primary key, name text not null)")
+        .unwrap();
+
+    assert_eq!(
+        sim.execute("alter table users rename column id to name"),
+        Err(Error::ColumnAlreadyExists("name".to_string()))
+    );
+}
+
+#[test]
+fn alter_table_rename_table() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table users (id int primary key, name text not null)")
+        .unwrap();
+    sim.execute(
+        "create table post (id int primary key, user_id int not null references users(id))",
+    )
+    .unwrap();
+
+    sim.execute("alter table users rename to account").unwrap();
+
+    assert!(!sim.has_table("users"));
+    assert!(sim.has_table("account"));
+
+    // The foreign key follows the renamed table.
+    assert_eq!(
+        sim.execute("drop table account"),
+        Err(Error::ForeignKeyConstraint("account".to_string()))
+    );
+}
+
+#[test]
+fn alter_table_doesnt_exist() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+
+    assert_eq!(
+        sim.execute("alter table users add column age int"),
+        Err(Error::TableDoesntExist("users".to_string()))
+    );
+
+    sim.execute("alter table if exists users add column age int")
+        .unwrap();
+}
+
+#[test]
+fn alter_table_drop_column_referenced_by_check() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute(
+        "create table users (id int primary key, age int not null check (age > 0), \
+         name text not null check (name <> ''))",
+    )
+    .unwrap();
+
+    // Postgres drops the CHECK constraints along with the column.
+    sim.execute("alter table users drop column age").unwrap();
+
+    let users = sim.get_table("users").unwrap();
+    assert!(!users.has_column("age"));
+    assert_eq!(users.checks.len(), 1);
+}
+
+#[test]
+fn alter_table_drop_column_referenced_by_check_sqlite() {
+    let mut sim = Simulator::with_dialect(DialectKind::Sqlite);
+    sim.execute("create table users (id int primary key, age int not null, check (age > 0))")
+        .unwrap();
+
+    assert_eq!(
+        sim.execute("alter table users drop column age"),
+        Err(Error::ColumnInUse("age".to_string()))
+    );
+
+    // A failing operation leaves the table untouched.
+    assert!(sim.get_table("users").unwrap().has_column("age"));
+}
+
+#[test]
+fn alter_table_drop_column_referenced_by_generated() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute(
+        "create table users (id int primary key, age int not null, \
+         next_age int generated always as (age + 1) stored)",
+    )
+    .unwrap();
+
+    assert_eq!(
+        sim.execute("alter table users drop column age"),
+        Err(Error::ColumnInUse("age".to_string()))
+    );
+
+    // Dropping the generated column itself is fine.
+    sim.execute("alter table users drop column next_age")
+        .unwrap();
+    sim.execute("alter table users drop column age").unwrap();
+}
+
+#[test]
+fn alter_table_rename_column_referenced_by_check() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table users (id int primary key, age int not null check (age > 0))")
+        .unwrap();
+
+    sim.execute("alter table users rename column age to years")
+        .unwrap();
+
+    // The CHECK follows the renamed column.
+    assert_eq!(
+        sim.execute("alter table users drop column years"),
+        Err(Error::ColumnDoesntExist("years".to_string()))
+    );
+    sim.execute("alter table users add column nickname text")
+        .unwrap();
+}
+
+#[test]
+fn alter_table_rename_column_named_like_function() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute(
+        "create table users (id int primary key, lower text not null check (lower(lower) <> ''))",
+    )
+    .unwrap();
+
+    sim.execute("alter table users rename column lower to name")
+        .unwrap();
+
+    // Only the column reference is renamed, not the function.
+    let users = sim.get_table("users").unwrap();
+    assert_eq!(users.checks[0].to_string(), "lower(name) <> ''");
+
+    sim.execute("alter table users add column nickname text")
+        .unwrap();
+}
@@ -0,0 +1,98 @@
+use truffle::{DialectKind, Error, Simulator, ty::SqlType};
+
+#[test]
+fn any_array_placeholder() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table person (id int primary key, name text not null)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select name from person where id = any($1)")
+        .unwrap();
+
+    assert_eq!(resolve.inputs.len(), 1);
+    assert_eq!(
+        resolve.get_input(0).unwrap().ty,
+        SqlType::Array(Box::new(SqlType::Integer))
+    );
+}
+
+#[test]
+fn all_array_column() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute(
+        "create table post (id int primary key, score int not null, thresholds int[] not null)",
+    )
+    .unwrap();
+
+    let resolve = sim
+        .execute("select id from post where score >= all(thresholds)")
+        .unwrap();
+
+    assert_eq!(resolve.outputs.len(), 1);
+}
+
+#[test]
+fn any_subquery() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table person (id int primary key, name text not null)")
+        .unwrap();
+    sim.execute("create table banned (person_id int not null, reason text)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select name from person where id = any(select person_id from banned where reason = $1)")
+        .unwrap();
+
+    assert_eq!(resolve.inputs.len(), 1);
+    assert_eq!(resolve.get_input(0).unwrap().ty, SqlType::Text);
+}
+
+#[test]
+fn any_subquery_type_mismatch() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table person (id int primary key, name text not null)")
+        .unwrap();
+    sim.execute("create table banned (person_id int not null, reason text)")
+        .unwrap();
+
+    assert_eq!(
+        sim.execute("select name from person where name = any(select person_id from banned)"),
+        Err(Error::TypeMismatch {
+            expected: SqlType::Integer,
+            got: SqlType::Text
+        })
+    );
+}
+
+#[test]
+fn any_subquery_multiple_columns() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table person (id int primary key, name text not null)")
+        .unwrap();
+    sim.execute("create table banned (person_id int not null, reason text)")
+        .unwrap();
+
+    assert_eq!(
+        sim.execute("select name from person where id = any(select person_id, reason from banned)"),
+        Err(Error::ColumnCountMismatch {
+            expected: 1,
+            got: 2
+        })
+    );
+}
+
+#[test]
+fn any_array_type_mismatch() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table post (id int primary key, tags text[] not null)")
+        .unwrap();
+
+    assert_eq!(
+        sim.execute("select id from post where id = any(tags)"),
+        Err(Error::TypeMismatch {
+            expected: SqlType::Array(Box::new(SqlType::Integer)),
+            got: SqlType::Array(Box::new(SqlType::Text))
+        })
+    );
+}
@@ -0,0 +1,160 @@
+use truffle::{DialectKind, Error, Simulator, ty::SqlType};
+
+#[test]
+fn select_float_times_integer_literal() {
+    let mut sim = Simulator::default();
+    sim.execute("create table product (id int primary key, price real not null, stock int not null, sold bigint)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select price * 2 as doubled, 2 * price as doubled2 from product")
+        .unwrap();
+
+    assert_eq!(
+        resolve.get_output_with_name("doubled").unwrap().ty,
+        SqlType::Float
+    );
+    assert_eq!(
+        resolve.get_output_with_name("doubled2").unwrap().ty,
+        SqlType::Float
+    );
+}
+
+#[test]
+fn select_integer_promotion() {
+    let mut sim = Simulator::default();
+    sim.execute("create table product (id int primary key, price real not null, stock int not null, sold bigint)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select stock + sold as total, stock * price as value from product")
+        .unwrap();
+
+    let total = resolve.get_output_with_name("total").unwrap();
+    assert_eq!(total.ty, SqlType::BigInt);
+    assert!(total.nullable);
+
+    assert_eq!(
+        resolve.get_output_with_name("value").unwrap().ty,
+        SqlType::Float
+    );
+}
+
+#[test]
+fn select_arithmetic_placeholder_takes_other_side() {
+    let mut sim = Simulator::default();
+    sim.execute("create table product (id int primary key, price real not null, stock int not null, sold bigint)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select $1 * price as value, stock - $2 as remaining from product")
+        .unwrap();
+
+    assert_eq!(resolve.get_input(0).unwrap().ty, SqlType::Float);
+    assert_eq!(resolve.get_input(1).unwrap().ty, SqlType::Integer);
+}
+
+#[test]
+fn select_arithmetic_placeholder_takes_expected_type() {
+    let mut sim = Simulator::default();
+    sim.execute("create table product (id int primary key, price real not null, stock int not null, sold bigint)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select id from product where sold = $1 + 1")
+        .unwrap();
+
+    assert_eq!(resolve.get_input(0).unwrap().ty, SqlType::BigInt);
+}
+
+#[test]
+fn select_arithmetic_non_numeric() {
+    let mut sim = Simulator::default();
+    sim.execute(
+        "create table product (id int primary key, name text not null, stock int not null)",
+    )
+    .unwrap();
+
+    assert_eq!(
+        sim.execute("select stock + name from product"),
+        Err(Error::TypeMismatch {
+            expected: SqlType::Integer,
+            got: SqlType::Text
+        })
+    );
+}
+
+#[test]
+fn select_arithmetic_text_operands() {
+    let mut sim = Simulator::default();
+    sim.execute(
+        "create table product (id int primary key, name text not null, stock int not null)",
+    )
+    .unwrap();
+
+    assert_eq!(
+        sim.execute("select name + name from product"),
+        Err(Error::TypeNotNumeric(SqlType::Text))
+    );
+}
+
+#[test]
+fn select_arithmetic_boolean_operands() {
+    let mut sim = Simulator::default();
+    sim.execute("create table product (id int primary key, active bool not null)")
+        .unwrap();
+
+    assert_eq!(
+        sim.execute("select active * active from product"),
+        Err(Error::TypeNotNumeric(SqlType::Boolean))
+    );
+}
+
+#[test]
+fn select_bit_shift() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table product (id int primary key, flags bigint)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select id << 2 as shifted, flags >> $1 as high from product")
+        .unwrap();
+
+    let shifted = resolve.get_output_with_name("shifted").unwrap();
+    assert_eq!(shifted.ty, SqlType::Integer);
+    assert!(!shifted.nullable);
+
+    let high = resolve.get_output_with_name("high").unwrap();
+    assert_eq!(high.ty, SqlType::BigInt);
+    assert!(high.nullable);
+
+    assert_eq!(resolve.get_input(0).unwrap().ty, SqlType::Integer);
+}
+
+#[test]
+fn select_bit_shift_sqlite() {
+    let mut sim = Simulator::with_dialect(DialectKind::Sqlite);
+    sim.execute("create table product (id int primary key, flags bigint)")
+        .unwrap();
+
+    // The SQLite parser doesn't know the shift operators.
+    assert!(matches!(
+        sim.execute("select id << 2 from product"),
+        Err(Error::Parsing(_))
+    ));
+}
+
+#[test]
+fn select_bit_shift_non_integer() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table product (id int primary key, name text not null)")
+        .unwrap();
+
+    assert_eq!(
+        sim.execute("select name << 2 from product"),
+        Err(Error::TypeMismatch {
+            expected: SqlType::Integer,
+            got: SqlType::Text
+        })
+    );
+}
@@ -0,0 +1,104 @@
+use truffle::{DialectKind, Error, Simulator, ty::SqlType};
+
+#[test]
+fn array_column() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table post (id int primary key, tags text[] not null, scores int[])")
+        .unwrap();
+
+    let table = sim.get_table("post").unwrap();
+    assert_eq!(
+        table.get_column("tags").unwrap().ty,
+        SqlType::Array(Box::new(SqlType::Text))
+    );
+    assert_eq!(
+        table.get_column("scores").unwrap().ty,
+        SqlType::Array(Box::new(SqlType::Integer))
+    );
+
+    let resolve = sim.execute("select tags from post where id = $1").unwrap();
+    assert_eq!(
+        resolve.get_output_with_name("tags").unwrap().ty,
+        SqlType::Array(Box::new(SqlType::Text))
+    );
+}
+
+#[test]
+fn array_insert_placeholder() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table post (id int primary key, tags text[] not null)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("insert into post (id, tags) values ($1, $2)")
+        .unwrap();
+
+    assert_eq!(
+        resolve.get_input(1).unwrap().ty,
+        SqlType::Array(Box::new(SqlType::Text))
+    );
+}
+
+#[test]
+fn array_literal() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table post (id int primary key, tags text[] not null)")
+        .unwrap();
+
+    sim.execute("insert into post (id, tags) values (1, array['rust', $1])")
+        .unwrap();
+
+    assert_eq!(
+        sim.execute("insert into post (id, tags) values (1, array[1, 2])"),
+        Err(Error::TypeMismatch {
+            expected: SqlType::Text,
+            got: SqlType::SmallInt
+        })
+    );
+}
+
+#[test]
+fn array_contains() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table post (id int primary key, tags text[] not null)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select id from post where tags @> $1 or tags <@ array['rust']")
+        .unwrap();
+
+    assert_eq!(
+        resolve.get_input(0).unwrap().ty,
+        SqlType::Array(Box::new(SqlType::Text))
+    );
+}
+
+#[test]
+fn array_contains_requires_array() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table post (id int primary key, title text not null)")
+        .unwrap();
+
+    assert_eq!(
+        sim.execute("select id from post where title @> $1"),
+        Err(Error::TypeMismatch {
+            expected: SqlType::Array(Box::new(SqlType::Text)),
+            got: SqlType::Text
+        })
+    );
+}
+
+#[test]
+fn array_compared_to_scalar() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table post (id int primary key, tags text[] not null)")
+        .unwrap();
+
+    assert_eq!(
+        sim.execute("select id from post where tags = 'rust'"),
+        Err(Error::TypeMismatch {
+            expected: SqlType::Array(Box::new(SqlType::Text)),
+            got: SqlType::Text
+        })
+    );
+}
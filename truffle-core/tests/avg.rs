@@ -40,3 +40,46 @@ fn select_with_avg_wildcard_function() {
         Err(Error::FunctionCall(_))
     ))
 }
+
+#[test]
+fn select_with_avg_nullable_over_not_null_column() {
+    let mut sim = Simulator::default();
+    sim.execute("create table item (id int primary key, name text not null default 'abc', age int not null)").unwrap();
+
+    let resolve = sim.execute("select avg(age) from item").unwrap();
+
+    let output = resolve.outputs.get_index(0).unwrap().1;
+    assert_eq!(output.ty, SqlType::Integer);
+    assert!(output.nullable);
+}
+
+#[test]
+fn select_with_sum_function() {
+    let mut sim = Simulator::default();
+    sim.execute(
+        "create table person (id int primary key, name text not null, salary float not null)",
+    )
+    .unwrap();
+
+    let resolve = sim.execute("select sum(salary) from person").unwrap();
+
+    assert_eq!(resolve.outputs.len(), 1);
+
+    let output = resolve.outputs.get_index(0).unwrap().1;
+    assert_eq!(output.ty, SqlType::Float);
+    assert!(output.nullable);
+}
+
+#[test]
+fn select_with_sum_not_numeric() {
+    let mut sim = Simulator::default();
+    sim.execute(
+        "create table person (id int primary key, name text not null, salary float not null)",
+    )
+    .unwrap();
+
+    assert_eq!(
+        sim.execute("select sum(name) from person"),
+        Err(Error::TypeNotNumeric(SqlType::Text))
+    );
+}
@@ -0,0 +1,49 @@
+use truffle::{DialectKind, Error, Simulator, ty::SqlType};
+
+#[test]
+fn create_table_with_blob() {
+    let mut sim = Simulator::with_dialect(DialectKind::Sqlite);
+    sim.execute("create table file (id int primary key, data blob not null)")
+        .unwrap();
+
+    let column = sim.get_table("file").unwrap().get_column("data").unwrap();
+    assert_eq!(column.ty, SqlType::Bytes);
+}
+
+#[test]
+fn create_table_with_bytea() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table file (id int primary key, data bytea not null)")
+        .unwrap();
+
+    let column = sim.get_table("file").unwrap().get_column("data").unwrap();
+    assert_eq!(column.ty, SqlType::Bytes);
+}
+
+#[test]
+fn select_bytes_equality_placeholder() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table file (id int primary key, data bytea not null, backup bytea)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select id from file where data = $1 or data = backup")
+        .unwrap();
+
+    assert_eq!(resolve.get_input(0).unwrap().ty, SqlType::Bytes);
+}
+
+#[test]
+fn select_bytes_compare_integer() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table file (id int primary key, data bytea not null)")
+        .unwrap();
+
+    assert_eq!(
+        sim.execute("select id from file where data = id"),
+        Err(Error::TypeMismatch {
+            expected: SqlType::Bytes,
+            got: SqlType::Integer
+        })
+    );
+}
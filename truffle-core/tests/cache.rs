@@ -0,0 +1,105 @@
+#![cfg(feature = "serde")]
+
+use truffle::{DialectKind, Error, Simulator, ty::SqlType};
+
+#[test]
+fn cache_round_trip() {
+    let path = std::env::temp_dir().join("truffle_cache_round_trip.json");
+
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create schema app; create table person (id int primary key, name text)")
+        .unwrap();
+    sim.to_cache(&path).unwrap();
+
+    let mut cached = Simulator::from_cache(&path, DialectKind::Postgres).unwrap();
+    assert!(cached.has_schema("app"));
+
+    let resolve = cached
+        .execute("select name from person where id = $1")
+        .unwrap();
+    assert_eq!(resolve.get_input(0).unwrap().ty, SqlType::Integer);
+}
+
+#[test]
+fn cache_dialect_mismatch() {
+    let path = std::env::temp_dir().join("truffle_cache_dialect_mismatch.json");
+
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table person (id int primary key, name text)")
+        .unwrap();
+    sim.to_cache(&path).unwrap();
+
+    assert!(matches!(
+        Simulator::from_cache(&path, DialectKind::Sqlite),
+        Err(Error::Cache(_))
+    ));
+}
+
+#[test]
+fn cache_keeps_types() {
+    let path = std::env::temp_dir().join("truffle_cache_keeps_types.json");
+
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create type mood as enum ('happy', 'sad')")
+        .unwrap();
+    sim.to_cache(&path).unwrap();
+
+    let mut cached = Simulator::from_cache(&path, DialectKind::Postgres).unwrap();
+    cached
+        .execute("create table person (id int primary key, m mood not null)")
+        .unwrap();
+
+    assert_eq!(
+        cached.execute("create type mood as enum ('meh')"),
+        Err(Error::TypeAlreadyExists("mood".to_string()))
+    );
+}
+
+#[test]
+fn cache_hash_mismatch() {
+    let path = std::env::temp_dir().join("truffle_cache_hash_mismatch.json");
+
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table person (id int primary key, name text)")
+        .unwrap();
+    sim.to_cache_with_hash(&path, "abc").unwrap();
+
+    let cached = Simulator::from_cache_with_hash(&path, DialectKind::Postgres, "abc").unwrap();
+    assert!(cached.has_table("person"));
+
+    assert!(matches!(
+        Simulator::from_cache_with_hash(&path, DialectKind::Postgres, "def"),
+        Err(Error::Cache(_))
+    ));
+
+    // A cache without a hash can't be matched to the migrations.
+    sim.to_cache(&path).unwrap();
+    assert!(matches!(
+        Simulator::from_cache_with_hash(&path, DialectKind::Postgres, "abc"),
+        Err(Error::Cache(_))
+    ));
+}
+
+#[test]
+fn cache_is_stable() {
+    let schema = "create schema app; create schema audit;
+        create type mood as enum ('happy', 'sad'); create type size as enum ('s', 'l');
+        create table person (id int primary key, email text unique, m mood, s size);
+        create table pet (id int primary key, owner int references person(id), name text);
+        comment on column pet.name is 'The name'; comment on column pet.owner is 'The owner';";
+
+    let write = |name: &str| {
+        let path = std::env::temp_dir().join(name);
+
+        let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+        sim.execute(schema).unwrap();
+        sim.to_cache(&path).unwrap();
+
+        std::fs::read_to_string(path).unwrap()
+    };
+
+    assert_eq!(
+        write("truffle_cache_is_stable_1.json"),
+        write("truffle_cache_is_stable_2.json")
+    );
+}
@@ -0,0 +1,86 @@
+use truffle::{DialectKind, Error, Simulator, ty::SqlType};
+
+#[test]
+fn select_cast_text_to_integer() {
+    let mut sim = Simulator::default();
+    sim.execute("create table item (id int primary key, name text not null, active boolean)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select cast(name as integer) as value from item")
+        .unwrap();
+
+    let output = resolve.get_output_with_name("value").unwrap();
+    assert_eq!(output.ty, SqlType::Integer);
+    assert!(!output.nullable);
+}
+
+#[test]
+fn select_cast_numeric_to_numeric() {
+    let mut sim = Simulator::default();
+    sim.execute("create table item (id int primary key, name text not null, active boolean)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select cast(id as real) as value from item")
+        .unwrap();
+
+    assert_eq!(
+        resolve.get_output_with_name("value").unwrap().ty,
+        SqlType::Float
+    );
+}
+
+#[test]
+fn select_cast_boolean_to_float_invalid() {
+    let mut sim = Simulator::default();
+    sim.execute("create table item (id int primary key, name text not null, active boolean)")
+        .unwrap();
+
+    assert_eq!(
+        sim.execute("select cast(active as real) from item"),
+        Err(Error::InvalidCast {
+            from: SqlType::Boolean,
+            to: SqlType::Float
+        })
+    );
+}
+
+#[test]
+fn select_double_colon_matches_cast() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table item (id int primary key, name text not null, active boolean)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select active::int as value from item")
+        .unwrap();
+    assert_eq!(
+        resolve.get_output_with_name("value").unwrap().ty,
+        SqlType::Integer
+    );
+
+    assert_eq!(
+        sim.execute("select active::real from item"),
+        Err(Error::InvalidCast {
+            from: SqlType::Boolean,
+            to: SqlType::Float
+        })
+    );
+}
+
+#[cfg(feature = "time")]
+#[test]
+fn select_cast_boolean_to_date_invalid() {
+    let mut sim = Simulator::default();
+    sim.execute("create table item (id int primary key, name text not null, active boolean)")
+        .unwrap();
+
+    assert_eq!(
+        sim.execute("select cast(active as date) from item"),
+        Err(Error::InvalidCast {
+            from: SqlType::Boolean,
+            to: SqlType::Date
+        })
+    );
+}
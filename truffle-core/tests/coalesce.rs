@@ -127,3 +127,63 @@ fn select_with_coalesce_placeholder_first() {
     assert_eq!(output.ty, SqlType::Integer);
     assert!(!output.nullable);
 }
+
+#[test]
+fn select_with_coalesce_text_fallback() {
+    let mut sim = Simulator::default();
+    sim.execute("create table person (id int primary key, nickname text, name text)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select coalesce(nickname, name, 'anon') from person")
+        .unwrap();
+
+    let output = resolve.outputs.get_index(0).unwrap().1;
+    assert_eq!(output.ty, SqlType::Text);
+    assert!(!output.nullable);
+}
+
+#[test]
+fn select_with_coalesce_all_nullable() {
+    let mut sim = Simulator::default();
+    sim.execute("create table person (id int primary key, nickname text, name text)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select coalesce(nickname, name) from person")
+        .unwrap();
+
+    let output = resolve.outputs.get_index(0).unwrap().1;
+    assert_eq!(output.ty, SqlType::Text);
+    assert!(output.nullable);
+}
+
+#[test]
+fn select_with_coalesce_type_mismatch() {
+    let mut sim = Simulator::default();
+    sim.execute("create table person (id int primary key, nickname text, name text)")
+        .unwrap();
+
+    assert_eq!(
+        sim.execute("select coalesce(nickname, id) from person"),
+        Err(Error::TypeMismatch {
+            expected: SqlType::Text,
+            got: SqlType::Integer
+        })
+    );
+}
+
+#[test]
+fn select_with_coalesce_anonymous_placeholder_registered_once() {
+    let mut sim = Simulator::default();
+    sim.execute("create table person (id int primary key, nickname text, name text)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select coalesce(nickname, ?) from person where id = ?")
+        .unwrap();
+
+    assert_eq!(resolve.inputs.len(), 2);
+    assert_eq!(resolve.get_input(0).unwrap().ty, SqlType::Text);
+    assert_eq!(resolve.get_input(1).unwrap().ty, SqlType::Integer);
+}
@@ -0,0 +1,97 @@
+use truffle::{DialectKind, Error, Simulator};
+
+#[test]
+fn comment_on_table() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table account (id int primary key, email text not null)")
+        .unwrap();
+
+    sim.execute("comment on table account is 'user accounts'")
+        .unwrap();
+
+    assert_eq!(
+        sim.get_table("account").unwrap().comment.as_deref(),
+        Some("user accounts")
+    );
+
+    sim.execute("comment on table account is null").unwrap();
+    assert_eq!(sim.get_table("account").unwrap().comment, None);
+}
+
+#[test]
+fn comment_on_column() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table account (id int primary key, email text not null)")
+        .unwrap();
+
+    sim.execute("comment on column account.email is 'user email'")
+        .unwrap();
+
+    let table = sim.get_table("account").unwrap();
+    assert_eq!(
+        table.column_comments.get("email").map(|c| c.as_str()),
+        Some("user email")
+    );
+
+    sim.execute("alter table account rename column email to address")
+        .unwrap();
+
+    let table = sim.get_table("account").unwrap();
+    assert!(!table.column_comments.contains_key("email"));
+    assert!(table.column_comments.contains_key("address"));
+}
+
+#[test]
+fn comment_on_table_doesnt_exist() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+
+    assert_eq!(
+        sim.execute("comment on table account is 'user accounts'"),
+        Err(Error::TableDoesntExist("account".to_string()))
+    );
+}
+
+#[test]
+fn comment_on_column_doesnt_exist() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table account (id int primary key, email text not null)")
+        .unwrap();
+
+    assert_eq!(
+        sim.execute("comment on column account.name is 'user name'"),
+        Err(Error::ColumnDoesntExist("name".to_string()))
+    );
+}
+
+#[test]
+fn comment_in_migration() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+
+    sim.execute(
+        "create table account (id int primary key, email text not null);
+        comment on column account.email is 'user email';
+        create table session (id int primary key, account_id int references account(id));",
+    )
+    .unwrap();
+
+    assert!(sim.has_table("session"));
+}
+
+#[test]
+fn comment_if_exists() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+
+    sim.execute("comment if exists on table account is 'user accounts'")
+        .unwrap();
+    sim.execute("comment if exists on column account.email is 'user email'")
+        .unwrap();
+
+    // IF EXISTS only skips a missing table, not a missing column of an existing one.
+    sim.execute("create table account (id int primary key, email text not null)")
+        .unwrap();
+
+    assert_eq!(
+        sim.execute("comment if exists on column account.name is 'user name'"),
+        Err(Error::ColumnDoesntExist("name".to_string()))
+    );
+}
@@ -0,0 +1,121 @@
+use truffle::{DialectKind, Error, Simulator, ty::SqlType};
+
+#[test]
+fn select_concat_text() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table item (id int primary key, name text not null, note text)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select name || note as label from item")
+        .unwrap();
+
+    let output = resolve.get_output_with_name("label").unwrap();
+    assert_eq!(output.ty, SqlType::Text);
+    assert!(output.nullable);
+}
+
+#[test]
+fn select_concat_integer() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table item (id int primary key, name text not null, note text)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select name || '#' || id as label, 'a' || 5 as other from item")
+        .unwrap();
+
+    let output = resolve.get_output_with_name("label").unwrap();
+    assert_eq!(output.ty, SqlType::Text);
+    assert!(!output.nullable);
+
+    assert_eq!(
+        resolve.get_output_with_name("other").unwrap().ty,
+        SqlType::Text
+    );
+}
+
+#[test]
+fn select_concat_placeholder() {
+    let mut sim = Simulator::with_dialect(DialectKind::Sqlite);
+    sim.execute("create table item (id int primary key, name text not null, note text)")
+        .unwrap();
+
+    let resolve = sim.execute("select id || $1 as label from item").unwrap();
+
+    assert_eq!(resolve.get_input(0).unwrap().ty, SqlType::Text);
+}
+
+#[test]
+fn select_concat_tuple() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table item (id int primary key, name text not null, note text)")
+        .unwrap();
+
+    assert!(matches!(
+        sim.execute("select name || (id, note) from item"),
+        Err(Error::TypeMismatch { .. })
+    ));
+}
+
+#[test]
+fn select_concat_integers() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table item (id int primary key, name text not null, note text)")
+        .unwrap();
+
+    assert_eq!(
+        sim.execute("select 5 || 6 from item"),
+        Err(Error::TypeMismatch {
+            expected: SqlType::Text,
+            got: SqlType::SmallInt
+        })
+    );
+    assert!(matches!(
+        sim.execute("select id || id from item"),
+        Err(Error::TypeMismatch { .. })
+    ));
+}
+
+#[test]
+fn select_concat_integers_sqlite() {
+    let mut sim = Simulator::with_dialect(DialectKind::Sqlite);
+    sim.execute("create table item (id int primary key, name text not null, note text)")
+        .unwrap();
+
+    let resolve = sim.execute("select id || id as label from item").unwrap();
+
+    assert_eq!(
+        resolve.get_output_with_name("label").unwrap().ty,
+        SqlType::Text
+    );
+}
+
+#[test]
+fn select_concat_arrays() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table item (id int primary key, tags text[] not null)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select tags || tags as merged, tags || 'new' as appended, tags || $1 as param from item")
+        .unwrap();
+
+    let array = SqlType::Array(Box::new(SqlType::Text));
+    assert_eq!(resolve.get_output_with_name("merged").unwrap().ty, array);
+    assert_eq!(resolve.get_output_with_name("appended").unwrap().ty, array);
+    assert_eq!(resolve.get_output_with_name("param").unwrap().ty, array);
+    assert_eq!(resolve.get_input(0).unwrap().ty, array);
+}
+
+#[test]
+fn select_concat_array_element_mismatch() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table item (id int primary key, ids int[] not null)")
+        .unwrap();
+
+    assert!(matches!(
+        sim.execute("select ids || true from item"),
+        Err(Error::TypeMismatch { .. })
+    ));
+}
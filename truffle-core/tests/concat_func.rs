@@ -0,0 +1,56 @@
+use truffle::{Error, Simulator, ty::SqlType};
+
+#[test]
+fn select_concat_variadic() {
+    let mut sim = Simulator::default();
+    sim.execute("create table users (id int primary key, first text not null, last text, age int)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select concat(first, ' ', last, ' (', age, ')') as full from users")
+        .unwrap();
+
+    let full = resolve.get_output_with_name("full").unwrap();
+    assert_eq!(full.ty, SqlType::Text);
+    // NULL arguments are skipped, so concat is never NULL.
+    assert!(!full.nullable);
+}
+
+#[test]
+fn select_concat_placeholder() {
+    let mut sim = Simulator::default();
+    sim.execute("create table users (id int primary key, first text not null, last text, age int)")
+        .unwrap();
+
+    let resolve = sim.execute("select concat(first, ?) from users").unwrap();
+
+    assert_eq!(resolve.inputs.len(), 1);
+    assert_eq!(resolve.get_input(0).unwrap().ty, SqlType::Text);
+}
+
+#[test]
+fn select_concat_no_arguments() {
+    let mut sim = Simulator::default();
+    sim.execute("create table users (id int primary key, first text not null, last text, age int)")
+        .unwrap();
+
+    assert_eq!(
+        sim.execute("select concat() from users"),
+        Err(Error::FunctionArgumentCount {
+            expected: 1,
+            got: 0
+        })
+    );
+}
+
+#[test]
+fn select_concat_column_doesnt_exist() {
+    let mut sim = Simulator::default();
+    sim.execute("create table users (id int primary key, first text not null, last text, age int)")
+        .unwrap();
+
+    assert_eq!(
+        sim.execute("select concat(first, middle) from users"),
+        Err(Error::ColumnDoesntExist("middle".to_string()))
+    );
+}
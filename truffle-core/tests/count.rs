@@ -1,4 +1,4 @@
-use truffle::{Error, Simulator, ty::SqlType};
+use truffle::{DialectKind, Error, Simulator, ty::SqlType};
 
 #[test]
 fn select_with_count_function() {
@@ -13,10 +13,7 @@ fn select_with_count_function() {
     assert_eq!(resolve.get_input(0).unwrap().ty, SqlType::Integer);
 
     assert_eq!(resolve.outputs.len(), 1);
-    assert_eq!(
-        resolve.outputs.iter().next().unwrap().1.ty,
-        SqlType::Integer
-    );
+    assert_eq!(resolve.outputs.iter().next().unwrap().1.ty, SqlType::BigInt);
 }
 
 #[test]
@@ -43,10 +40,7 @@ fn select_with_count_wildcard_function() {
     assert_eq!(resolve.get_input(0).unwrap().ty, SqlType::Integer);
 
     assert_eq!(resolve.outputs.len(), 1);
-    assert_eq!(
-        resolve.outputs.iter().next().unwrap().1.ty,
-        SqlType::Integer
-    );
+    assert_eq!(resolve.outputs.iter().next().unwrap().1.ty, SqlType::BigInt);
 }
 
 #[test]
@@ -64,6 +58,171 @@ fn select_with_count_function_aliased() {
     assert_eq!(resolve.outputs.len(), 1);
     assert_eq!(
         resolve.get_output_with_name("item_count").unwrap().ty,
-        SqlType::Integer
+        SqlType::BigInt
+    );
+}
+
+#[test]
+fn select_with_count_distinct_and_filter() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute(
+        "create table events (id int primary key, user_id int not null, active boolean not null)",
+    )
+    .unwrap();
+
+    let resolve = sim
+        .execute("select count(distinct user_id) filter (where active and id > $1) from events")
+        .unwrap();
+
+    assert_eq!(resolve.inputs.len(), 1);
+    assert_eq!(resolve.get_input(0).unwrap().ty, SqlType::Integer);
+
+    assert_eq!(resolve.outputs.len(), 1);
+    assert!(!resolve.outputs.get_index(0).unwrap().1.nullable);
+}
+
+#[test]
+fn select_with_count_filter_not_boolean() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute(
+        "create table events (id int primary key, user_id int not null, active boolean not null)",
+    )
+    .unwrap();
+
+    assert_eq!(
+        sim.execute("select count(distinct user_id) filter (where user_id) from events"),
+        Err(Error::TypeMismatch {
+            expected: SqlType::Boolean,
+            got: SqlType::Integer
+        })
+    );
+}
+
+#[test]
+fn select_with_count_filter_column_doesnt_exist() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute(
+        "create table events (id int primary key, user_id int not null, active boolean not null)",
+    )
+    .unwrap();
+
+    assert_eq!(
+        sim.execute("select count(distinct user_id) filter (where deleted) from events"),
+        Err(Error::ColumnDoesntExist("deleted".to_string()))
+    );
+}
+
+#[test]
+fn select_with_count_wildcard_without_group_by() {
+    let mut sim = Simulator::default();
+    sim.execute("create table person (id int primary key, name text not null)")
+        .unwrap();
+
+    let resolve = sim.execute("select count(*) from person").unwrap();
+
+    assert_eq!(resolve.inputs.len(), 0);
+    assert_eq!(resolve.outputs.len(), 1);
+
+    let output = resolve.outputs.get_index(0).unwrap().1;
+    assert_eq!(output.ty, SqlType::BigInt);
+    assert!(!output.nullable);
+}
+
+#[test]
+fn select_with_count_expression() {
+    let mut sim = Simulator::default();
+    sim.execute("create table person (id int primary key, name text not null)")
+        .unwrap();
+
+    let resolve = sim.execute("select count(name || $1) from person").unwrap();
+
+    assert_eq!(resolve.inputs.len(), 1);
+    assert_eq!(resolve.get_input(0).unwrap().ty, SqlType::Text);
+    assert_eq!(resolve.outputs.get_index(0).unwrap().1.ty, SqlType::BigInt);
+}
+
+#[test]
+fn select_with_count_wildcard_mixed_with_row_column() {
+    let mut sim = Simulator::default();
+    sim.execute("create table person (id int primary key, name text not null)")
+        .unwrap();
+
+    assert_eq!(
+        sim.execute("select id, count(*) from person"),
+        Err(Error::IncompatibleScope)
+    );
+}
+
+#[test]
+fn select_with_count_distinct_group_by() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute(
+        "create table events (id int primary key, user_id int not null, kind text not null)",
+    )
+    .unwrap();
+
+    let resolve = sim
+        .execute("select kind, count(distinct user_id) as users from events group by kind")
+        .unwrap();
+
+    let users = resolve.get_output_with_name("users").unwrap();
+    assert_eq!(users.ty, SqlType::BigInt);
+    assert!(!users.nullable);
+}
+
+#[test]
+fn select_with_count_distinct_column_doesnt_exist() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table events (id int primary key, user_id int not null)")
+        .unwrap();
+
+    assert_eq!(
+        sim.execute("select count(distinct account_id) from events"),
+        Err(Error::ColumnDoesntExist("account_id".to_string()))
+    );
+}
+
+#[test]
+fn select_with_count_distinct_wildcard() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table events (id int primary key, user_id int not null)")
+        .unwrap();
+
+    assert!(matches!(
+        sim.execute("select count(distinct *) from events"),
+        Err(Error::FunctionCall(_))
+    ));
+}
+
+#[test]
+fn select_with_sum_avg_distinct() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table orders (id int primary key, amount int not null)")
+        .unwrap();
+
+    let resolve = sim
+        .execute(
+            "select sum(distinct amount) as total, avg(distinct amount) as average from orders",
+        )
+        .unwrap();
+
+    let total = resolve.get_output_with_name("total").unwrap();
+    assert_eq!(total.ty, SqlType::Integer);
+    assert!(total.nullable);
+
+    let average = resolve.get_output_with_name("average").unwrap();
+    assert_eq!(average.ty, SqlType::Integer);
+    assert!(average.nullable);
+}
+
+#[test]
+fn select_with_sum_distinct_mixed_with_row_column() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table orders (id int primary key, amount int not null)")
+        .unwrap();
+
+    assert_eq!(
+        sim.execute("select id, sum(distinct amount) from orders"),
+        Err(Error::IncompatibleScope)
     );
 }
@@ -404,3 +404,62 @@ fn create_table_with_default_value_column_name() {
             Err(Error::InvalidDefault("name".to_string()))
         )
 }
+
+#[test]
+fn create_table_column_check() {
+    let mut sim = Simulator::default();
+    sim.execute("create table person (id int primary key, age int check (age >= 0))")
+        .unwrap();
+
+    assert!(sim.get_table("person").is_some());
+}
+
+#[test]
+fn create_table_column_check_references_later_column() {
+    let mut sim = Simulator::default();
+    sim.execute("create table range (low int check (low <= high), high int not null)")
+        .unwrap();
+}
+
+#[test]
+fn create_table_table_check() {
+    let mut sim = Simulator::default();
+    sim.execute("create table range (low int not null, high int not null, constraint ordered check (range.low <= high))")
+        .unwrap();
+}
+
+#[test]
+fn create_table_check_column_doesnt_exist() {
+    let mut sim = Simulator::default();
+
+    assert_eq!(
+        sim.execute("create table person (id int primary key, age int check (height >= 0))"),
+        Err(Error::ColumnDoesntExist("height".to_string()))
+    );
+}
+
+#[test]
+fn create_table_check_wrong_qualifier() {
+    let mut sim = Simulator::default();
+
+    assert_eq!(
+        sim.execute("create table person (id int primary key, age int, check (other.age >= 0))"),
+        Err(Error::QualifiedColumnDoesntExist {
+            qualifier: "other".to_string(),
+            column: "age".to_string()
+        })
+    );
+}
+
+#[test]
+fn create_table_check_not_boolean() {
+    let mut sim = Simulator::default();
+
+    assert_eq!(
+        sim.execute("create table person (id int primary key, age int check (age + 1))"),
+        Err(Error::TypeMismatch {
+            expected: SqlType::Boolean,
+            got: SqlType::Integer
+        })
+    );
+}
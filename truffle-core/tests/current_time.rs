@@ -0,0 +1,71 @@
+#![cfg(feature = "time")]
+
+use truffle::{DialectKind, Error, Simulator, ty::SqlType};
+
+#[test]
+fn create_table_default_now() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table t (id int primary key, created timestamptz not null default now())")
+        .unwrap();
+
+    sim.execute("insert into t (id) values ($1)").unwrap();
+}
+
+#[test]
+fn create_table_default_current_timestamp() {
+    let mut sim = Simulator::with_dialect(DialectKind::Sqlite);
+    sim.execute("create table t (id int primary key, created timestamp not null default current_timestamp, day date default current_date, at time default current_time)")
+        .unwrap();
+}
+
+#[test]
+fn create_table_default_current_date_type_mismatch() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+
+    assert_eq!(
+        sim.execute("create table t (id int primary key, name text default current_date)"),
+        Err(Error::TypeMismatch {
+            expected: SqlType::Text,
+            got: SqlType::Date
+        })
+    );
+}
+
+#[test]
+fn select_current_time_functions() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table t (id int primary key)").unwrap();
+
+    let resolve = sim
+        .execute("select now() as n, current_date as d, current_time as t from t")
+        .unwrap();
+
+    let n = resolve.get_output_with_name("n").unwrap();
+    assert_eq!(n.ty, SqlType::TimestampTz);
+    assert!(!n.nullable);
+    assert_eq!(resolve.get_output_with_name("d").unwrap().ty, SqlType::Date);
+    assert_eq!(resolve.get_output_with_name("t").unwrap().ty, SqlType::Time);
+}
+
+#[test]
+fn select_where_before_now() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table t (id int primary key, created timestamptz not null)")
+        .unwrap();
+
+    sim.execute("select * from t where created < now()")
+        .unwrap();
+}
+
+#[test]
+fn select_now_with_arguments() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+
+    assert_eq!(
+        sim.execute("select now(1)"),
+        Err(Error::FunctionArgumentCount {
+            expected: 0,
+            got: 1
+        })
+    );
+}
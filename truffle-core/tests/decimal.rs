@@ -0,0 +1,63 @@
+#![cfg(feature = "decimal")]
+
+use truffle::{DialectKind, Simulator, ty::SqlType};
+
+#[test]
+fn create_table_with_numeric() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table payment (id int primary key, amount numeric(12, 2) not null)")
+        .unwrap();
+
+    let column = sim
+        .get_table("payment")
+        .unwrap()
+        .get_column("amount")
+        .unwrap();
+
+    assert_eq!(
+        column.ty,
+        SqlType::Decimal {
+            precision: Some(12),
+            scale: Some(2)
+        }
+    );
+}
+
+#[test]
+fn select_decimal_compare_integer_literal() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table payment (id int primary key, amount numeric(12, 2) not null)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select id from payment where amount > 100 and amount < 250.50")
+        .unwrap();
+
+    assert_eq!(resolve.outputs.len(), 1);
+}
+
+#[test]
+fn select_decimal_arithmetic() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table payment (id int primary key, amount decimal not null)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select amount * 2 as doubled from payment where amount = $1")
+        .unwrap();
+
+    assert_eq!(
+        resolve.get_input(0).unwrap().ty,
+        SqlType::Decimal {
+            precision: None,
+            scale: None
+        }
+    );
+    assert_eq!(
+        resolve.get_output_with_name("doubled").unwrap().ty,
+        SqlType::Decimal {
+            precision: None,
+            scale: None
+        }
+    );
+}
@@ -49,3 +49,64 @@ fn delete_row_join() {
     sim.execute("delete from person natural join order where address = ?")
         .unwrap();
 }
+
+#[test]
+fn delete_using() {
+    let mut sim = Simulator::default();
+    sim.execute("create table person (id int primary key, name text not null, department_id int)")
+        .unwrap();
+    sim.execute("create table department (id int primary key, name text not null)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("delete from person using department where person.department_id = department.id and department.name = $1")
+        .unwrap();
+
+    assert_eq!(resolve.inputs.len(), 1);
+    assert_eq!(resolve.get_input(0).unwrap().ty, SqlType::Text);
+}
+
+#[test]
+fn delete_using_ambiguous_column() {
+    let mut sim = Simulator::default();
+    sim.execute("create table person (id int primary key, name text not null, department_id int)")
+        .unwrap();
+    sim.execute("create table department (id int primary key, name text not null)")
+        .unwrap();
+
+    assert_eq!(
+        sim.execute("delete from person using department where name = $1"),
+        Err(Error::AmbiguousColumn("name".to_string()))
+    );
+}
+
+#[test]
+fn delete_using_table_doesnt_exist() {
+    let mut sim = Simulator::default();
+    sim.execute("create table person (id int primary key, name text not null)")
+        .unwrap();
+
+    assert_eq!(
+        sim.execute("delete from person using department where person.id = $1"),
+        Err(Error::TableDoesntExist("department".to_string()))
+    );
+}
+
+#[test]
+fn delete_with_returning() {
+    let mut sim = Simulator::default();
+    sim.execute("create table person (id int primary key, name text not null, department_id int)")
+        .unwrap();
+    sim.execute("create table department (id int primary key, name text not null)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("delete from person p using department d where p.department_id = d.id returning p.*, d.name as department")
+        .unwrap();
+
+    assert_eq!(resolve.outputs.len(), 4);
+    assert_eq!(
+        resolve.get_output_with_name("department").unwrap().ty,
+        SqlType::Text
+    );
+}
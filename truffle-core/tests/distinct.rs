@@ -0,0 +1,125 @@
+use truffle::{DialectKind, Error, Simulator};
+
+#[test]
+fn select_distinct_order_by_selected() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table person (id int primary key, name text not null, age int)")
+        .unwrap();
+
+    sim.execute("select distinct name from person order by name")
+        .unwrap();
+}
+
+#[test]
+fn select_distinct_order_by_alias() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table person (id int primary key, name text not null, age int)")
+        .unwrap();
+
+    sim.execute("select distinct name as n from person order by name")
+        .unwrap();
+}
+
+#[test]
+fn select_distinct_order_by_not_selected() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table person (id int primary key, name text not null, age int)")
+        .unwrap();
+
+    assert_eq!(
+        sim.execute("select distinct name from person order by age"),
+        Err(Error::DistinctOrderBy("age".to_string()))
+    );
+}
+
+#[test]
+fn select_distinct_order_by_not_selected_sqlite() {
+    let mut sim = Simulator::with_dialect(DialectKind::Sqlite);
+    sim.execute("create table person (id int primary key, name text not null, age int)")
+        .unwrap();
+
+    sim.execute("select distinct name from person order by age")
+        .unwrap();
+}
+
+#[test]
+fn select_order_by_not_selected_without_distinct() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table person (id int primary key, name text not null, age int)")
+        .unwrap();
+
+    sim.execute("select name from person order by age").unwrap();
+}
+
+#[test]
+fn select_distinct_on_postgres() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute(
+        "create table events (id int primary key, user_id int not null, created_at int not null)",
+    )
+    .unwrap();
+
+    let resolve = sim
+        .execute("select distinct on (user_id) * from events order by user_id, created_at desc")
+        .unwrap();
+
+    assert_eq!(resolve.outputs.len(), 3);
+}
+
+#[test]
+fn select_distinct_on_column_doesnt_exist() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute(
+        "create table events (id int primary key, user_id int not null, created_at int not null)",
+    )
+    .unwrap();
+
+    assert_eq!(
+        sim.execute("select distinct on (account_id) * from events"),
+        Err(Error::ColumnDoesntExist("account_id".to_string()))
+    );
+}
+
+#[test]
+fn select_distinct_on_sqlite_unsupported() {
+    let mut sim = Simulator::with_dialect(DialectKind::Sqlite);
+    sim.execute(
+        "create table events (id int primary key, user_id int not null, created_at int not null)",
+    )
+    .unwrap();
+
+    assert!(matches!(
+        sim.execute("select distinct on (user_id) * from events"),
+        Err(Error::Unsupported(_))
+    ));
+}
+
+#[test]
+fn select_distinct_order_by_qualified() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table person (id int primary key, name text not null, age int)")
+        .unwrap();
+
+    sim.execute("select distinct name from person p order by p.name")
+        .unwrap();
+    sim.execute("select distinct p.name from person p order by name")
+        .unwrap();
+    sim.execute("select distinct p.* from person p order by age")
+        .unwrap();
+}
+
+#[test]
+fn select_distinct_order_by_other_table_column() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table person (id int primary key, name text not null, age int)")
+        .unwrap();
+    sim.execute("create table pet (id int primary key, owner int not null, name text not null)")
+        .unwrap();
+
+    assert_eq!(
+        sim.execute(
+            "select distinct p.name from person p join pet on pet.owner = p.id order by pet.name"
+        ),
+        Err(Error::DistinctOrderBy("pet.name".to_string()))
+    );
+}
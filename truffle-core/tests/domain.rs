@@ -0,0 +1,77 @@
+use std::collections::HashMap;
+
+use truffle::{Config, DialectKind, Error, Simulator, ty::SqlType};
+
+#[test]
+fn create_table_with_type_alias() {
+    let config = Config {
+        dialect: DialectKind::Postgres,
+        types: HashMap::from([("email_address".to_string(), "text".to_string())]),
+        ..Default::default()
+    };
+    let mut sim = Simulator::with_config(&config).unwrap();
+    sim.execute("create table account (id int primary key, email email_address not null)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select email from account where email = $1")
+        .unwrap();
+
+    assert_eq!(resolve.get_input(0).unwrap().ty, SqlType::Text);
+    assert_eq!(
+        resolve.get_output_with_name("email").unwrap().ty,
+        SqlType::Text
+    );
+}
+
+#[test]
+fn cast_to_type_alias() {
+    let config = Config {
+        dialect: DialectKind::Postgres,
+        types: HashMap::from([("email_address".to_string(), "text".to_string())]),
+        ..Default::default()
+    };
+    let mut sim = Simulator::with_config(&config).unwrap();
+    sim.execute("create table account (id int primary key, email text not null)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select cast(email as email_address) as e from account")
+        .unwrap();
+
+    assert_eq!(resolve.get_output_with_name("e").unwrap().ty, SqlType::Text);
+}
+
+#[test]
+fn type_alias_is_case_insensitive() {
+    let config = Config {
+        dialect: DialectKind::Postgres,
+        types: HashMap::from([("email_address".to_string(), "text".to_string())]),
+        ..Default::default()
+    };
+    let mut sim = Simulator::with_config(&config).unwrap();
+    sim.execute("create table account (id int primary key, email EMAIL_ADDRESS not null)")
+        .unwrap();
+
+    assert_eq!(
+        sim.get_table("account")
+            .unwrap()
+            .get_column("email")
+            .unwrap()
+            .ty,
+        SqlType::Text
+    );
+}
+
+#[test]
+fn type_alias_invalid_base() {
+    let config = Config {
+        types: HashMap::from([("email_address".to_string(), "".to_string())]),
+        ..Default::default()
+    };
+
+    assert!(matches!(
+        Simulator::with_config(&config),
+        Err(Error::InvalidType(_))
+    ));
+}
@@ -1,4 +1,4 @@
-use truffle::{Error, Simulator};
+use truffle::{DialectKind, Error, Simulator};
 
 #[test]
 fn drop_table_success() {
@@ -32,3 +32,45 @@ fn drop_table_foreign_key_constaint() {
         Err(Error::ForeignKeyConstraint("person".to_string()))
     )
 }
+
+#[test]
+fn drop_table_if_exists() {
+    let mut sim = Simulator::default();
+    sim.execute("drop table if exists person").unwrap();
+
+    sim.execute("create table person (id int primary key)")
+        .unwrap();
+    sim.execute("drop table if exists person, pet").unwrap();
+    assert!(!sim.has_table("person"));
+}
+
+#[test]
+fn drop_table_with_referencing_table() {
+    let mut sim = Simulator::default();
+    sim.execute("create table person (id int primary key, name text)")
+        .unwrap();
+    sim.execute("create table pet (id int primary key, owner_id int references person(id))")
+        .unwrap();
+
+    // Both tables are dropped together, so nothing is left referencing person.
+    sim.execute("drop table person, pet").unwrap();
+    assert_eq!(sim.tables.len(), 0);
+}
+
+#[test]
+fn drop_table_cascade() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table person (id int primary key, name text)")
+        .unwrap();
+    sim.execute("create table pet (id int primary key, owner_id int references person(id))")
+        .unwrap();
+
+    sim.execute("drop table person cascade").unwrap();
+    assert!(!sim.has_table("person"));
+    assert!(sim.has_table("pet"));
+
+    // The foreign key was dropped with the table, so the name can be reused.
+    sim.execute("create table person (id int primary key)")
+        .unwrap();
+    sim.execute("drop table person").unwrap();
+}
@@ -0,0 +1,223 @@
+use truffle::{DialectKind, Error, Simulator, ty::SqlType};
+
+#[test]
+fn create_table_with_enum() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create type status as enum ('active', 'banned')")
+        .unwrap();
+    sim.execute("create table account (id int primary key, s status not null)")
+        .unwrap();
+
+    let resolve = sim.execute("select s from account").unwrap();
+
+    let s = resolve.get_output_with_name("s").unwrap();
+    assert_eq!(
+        s.ty,
+        SqlType::Enum {
+            name: "status".to_string(),
+            variants: vec!["active".to_string(), "banned".to_string()],
+        }
+    );
+    assert!(!s.nullable);
+}
+
+#[test]
+fn create_enum_twice() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create type status as enum ('active', 'banned')")
+        .unwrap();
+    sim.execute("create table account (id int primary key, s status not null)")
+        .unwrap();
+
+    assert_eq!(
+        sim.execute("create type status as enum ('a')"),
+        Err(Error::TypeAlreadyExists("status".to_string()))
+    );
+}
+
+#[test]
+fn compare_enum_with_variant() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create type status as enum ('active', 'banned')")
+        .unwrap();
+    sim.execute("create table account (id int primary key, s status not null)")
+        .unwrap();
+
+    sim.execute("select id from account where s = 'banned'")
+        .unwrap();
+}
+
+#[test]
+fn compare_enum_with_invalid_variant() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create type status as enum ('active', 'banned')")
+        .unwrap();
+    sim.execute("create table account (id int primary key, s status not null)")
+        .unwrap();
+
+    assert_eq!(
+        sim.execute("select id from account where s = 'deleted'"),
+        Err(Error::InvalidEnumVariant {
+            name: "status".to_string(),
+            variant: "deleted".to_string()
+        })
+    );
+}
+
+#[test]
+fn insert_enum_variant() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create type status as enum ('active', 'banned')")
+        .unwrap();
+    sim.execute("create table account (id int primary key, s status not null)")
+        .unwrap();
+
+    sim.execute("insert into account (id, s) values (1, 'active')")
+        .unwrap();
+
+    assert_eq!(
+        sim.execute("insert into account (id, s) values (2, 'Active')"),
+        Err(Error::InvalidEnumVariant {
+            name: "status".to_string(),
+            variant: "Active".to_string()
+        })
+    );
+}
+
+#[test]
+fn enum_placeholder() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create type status as enum ('active', 'banned')")
+        .unwrap();
+    sim.execute("create table account (id int primary key, s status not null)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("update account set s = $1 where id = $2")
+        .unwrap();
+
+    assert_eq!(
+        resolve.get_input(0).unwrap().ty,
+        SqlType::Enum {
+            name: "status".to_string(),
+            variants: vec!["active".to_string(), "banned".to_string()],
+        }
+    );
+}
+
+#[test]
+fn compare_enum_with_text_column() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create type status as enum ('active', 'banned')")
+        .unwrap();
+    sim.execute("create table account (id int primary key, s status not null)")
+        .unwrap();
+    sim.execute("create table note (id int primary key, body text not null)")
+        .unwrap();
+
+    assert!(
+        sim.execute("select a.id from account a join note n on a.s = n.body")
+            .is_err()
+    );
+}
+
+#[test]
+fn create_enum_quoted_name() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute(r#"create type "Mood" as enum ('happy', 'sad')"#)
+        .unwrap();
+    sim.execute(r#"create table person (id int primary key, m "Mood" not null)"#)
+        .unwrap();
+
+    let resolve = sim.execute("select m from person").unwrap();
+    assert_eq!(
+        resolve.get_output_with_name("m").unwrap().ty,
+        SqlType::Enum {
+            name: "Mood".to_string(),
+            variants: vec!["happy".to_string(), "sad".to_string()],
+        }
+    );
+
+    // Unquoted names are folded, so they don't match the quoted name.
+    sim.execute("create table pet (id int primary key, m Mood not null)")
+        .unwrap();
+
+    let resolve = sim.execute("select m from pet").unwrap();
+    assert!(matches!(
+        resolve.get_output_with_name("m").unwrap().ty,
+        SqlType::Unknown(_)
+    ));
+}
+
+#[test]
+fn create_enum_schema_qualified() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create type public.status as enum ('active', 'banned')")
+        .unwrap();
+    sim.execute("create table account (id int primary key, s status not null)")
+        .unwrap();
+
+    let resolve = sim.execute("select s from account").unwrap();
+    assert_eq!(
+        resolve.get_output_with_name("s").unwrap().ty,
+        SqlType::Enum {
+            name: "status".to_string(),
+            variants: vec!["active".to_string(), "banned".to_string()],
+        }
+    );
+
+    assert_eq!(
+        sim.execute("create type status as enum ('a')"),
+        Err(Error::TypeAlreadyExists("status".to_string()))
+    );
+    assert_eq!(
+        sim.execute("create type app.status as enum ('a')"),
+        Err(Error::SchemaDoesntExist("app".to_string()))
+    );
+}
+
+#[test]
+fn drop_enum_and_recreate() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create type status as enum ('active', 'banned')")
+        .unwrap();
+    sim.execute("drop type status").unwrap();
+    sim.execute("create type status as enum ('active', 'banned', 'deleted')")
+        .unwrap();
+    sim.execute("create table account (id int primary key, s status not null)")
+        .unwrap();
+
+    sim.execute("select id from account where s = 'deleted'")
+        .unwrap();
+}
+
+#[test]
+fn drop_enum_if_exists() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    assert_eq!(
+        sim.execute("drop type status"),
+        Err(Error::TypeDoesntExist("status".to_string()))
+    );
+
+    sim.execute("drop type if exists status").unwrap();
+}
+
+#[test]
+fn drop_enum_used_by_column() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create type status as enum ('active', 'banned')")
+        .unwrap();
+    sim.execute("create table account (id int primary key, s status[] not null)")
+        .unwrap();
+
+    assert_eq!(
+        sim.execute("drop type status"),
+        Err(Error::TypeInUse {
+            name: "status".to_string(),
+            column: "s".to_string()
+        })
+    );
+
+    sim.execute("drop table account").unwrap();
+    sim.execute("drop type status").unwrap();
+}
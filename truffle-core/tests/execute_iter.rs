@@ -0,0 +1,126 @@
+use truffle::{DialectKind, Error, Simulator, ty::SqlType};
+
+#[test]
+fn execute_iter_resolves_in_order() {
+    let mut sim = Simulator::default();
+
+    let resolved: Vec<_> = sim
+        .execute_iter(
+            "create table person (id int primary key, name text not null);
+            insert into person (id, name) values (1, 'a');
+            select name from person where id = $1;",
+        )
+        .collect();
+
+    assert_eq!(resolved.len(), 3);
+    assert!(resolved.iter().all(|r| r.is_ok()));
+
+    let select = resolved[2].as_ref().unwrap();
+    assert_eq!(select.get_input(0).unwrap().ty, SqlType::Integer);
+    assert!(sim.has_table("person"));
+}
+
+#[test]
+fn execute_iter_applies_side_effects_lazily() {
+    let mut sim = Simulator::default();
+
+    let mut iter = sim.execute_iter(
+        "create table person (id int primary key);
+        create table person (id int primary key);",
+    );
+
+    assert!(iter.next().unwrap().is_ok());
+    assert_eq!(
+        iter.next().unwrap(),
+        Err(Error::TableAlreadyExists("person".to_string()))
+    );
+    assert!(iter.next().is_none());
+}
+
+#[test]
+fn execute_iter_skips_empty_statements() {
+    let mut sim = Simulator::default();
+
+    let count = sim
+        .execute_iter(";; create table person (id int primary key);;")
+        .count();
+
+    assert_eq!(count, 1);
+}
+
+#[test]
+fn execute_iter_reports_parse_errors_per_statement() {
+    let mut sim = Simulator::default();
+
+    let resolved: Vec<_> = sim
+        .execute_iter("create table person (id int primary key); selec 1; select id from person;")
+        .collect();
+
+    assert_eq!(resolved.len(), 3);
+    assert!(resolved[0].is_ok());
+    assert!(matches!(resolved[1], Err(Error::Parsing(_))));
+    assert!(resolved[2].is_ok());
+}
+
+#[test]
+fn execute_iter_continues_after_error() {
+    let mut sim = Simulator::default();
+
+    let results: Vec<_> = sim
+        .execute_iter_with_sql(
+            "create table person (id int primary key);
+            create table pet (id int primary key, owner int references missing(id));
+            select id from pet;
+            create table pet (id int primary key, owner int references person(id));",
+        )
+        .collect();
+
+    assert_eq!(results.len(), 4);
+    assert!(results[0].1.is_ok());
+
+    assert_eq!(
+        results[1].0,
+        "create table pet (id int primary key, owner int references missing(id))"
+    );
+    assert!(results[1].1.is_err());
+
+    // The failed statement didn't create the table.
+    assert_eq!(
+        results[2].1,
+        Err(Error::TableDoesntExist("pet".to_string()))
+    );
+    assert!(results[3].1.is_ok());
+    assert!(sim.has_table("pet"));
+}
+
+#[test]
+fn execute_iter_ignores_quoted_semicolons() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+
+    let results: Vec<_> = sim
+        .execute_iter_with_sql(
+            "create table \"a;b\" (id int primary key, note text default 'x;y');
+            -- a comment; with a semicolon
+            /* another; /* nested; */ comment */
+            select note from \"a;b\" where note <> 'it''s;';",
+        )
+        .collect();
+
+    assert_eq!(results.len(), 2);
+    assert!(results.iter().all(|(_, result)| result.is_ok()));
+    assert!(results[1].0.ends_with("'it''s;'"));
+}
+
+#[test]
+fn execute_iter_reports_script_locations() {
+    let mut sim = Simulator::default();
+
+    let results: Vec<_> = sim
+        .execute_iter("create table person (id int primary key);\nselect id from person where );")
+        .collect();
+
+    let Err(Error::Parsing(e)) = &results[1] else {
+        panic!("Expected a parsing error, got {:?}", results[1]);
+    };
+    assert!(e.to_string().contains("Line: 2"), "{e}");
+}
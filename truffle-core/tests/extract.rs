@@ -0,0 +1,98 @@
+#![cfg(feature = "time")]
+
+use truffle::{DialectKind, Error, Simulator, ty::SqlType};
+
+#[test]
+fn extract_year_postgres() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table events (id int primary key, name text not null, created_at timestamp not null, finished_at timestamp, duration interval)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select extract(year from created_at) as year, extract(epoch from duration) as seconds from events")
+        .unwrap();
+
+    let year = resolve.get_output_with_name("year").unwrap();
+    assert_eq!(year.ty, SqlType::Double);
+    assert!(!year.nullable);
+
+    let seconds = resolve.get_output_with_name("seconds").unwrap();
+    assert_eq!(seconds.ty, SqlType::Double);
+    assert!(seconds.nullable);
+}
+
+#[test]
+fn extract_year_generic() {
+    let mut sim = Simulator::with_dialect(DialectKind::Generic);
+    sim.execute("create table events (id int primary key, name text not null, created_at timestamp not null, finished_at timestamp, duration interval)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select extract(year from finished_at) as year from events")
+        .unwrap();
+
+    let year = resolve.get_output_with_name("year").unwrap();
+    assert_eq!(year.ty, SqlType::Integer);
+    assert!(year.nullable);
+}
+
+#[test]
+fn extract_group_by() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table events (id int primary key, name text not null, created_at timestamp not null, finished_at timestamp, duration interval)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select extract(year from created_at) as year, count(*) from events group by extract(year from created_at)")
+        .unwrap();
+
+    assert_eq!(resolve.outputs.len(), 2);
+}
+
+#[test]
+fn extract_non_temporal() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table events (id int primary key, name text not null, created_at timestamp not null, finished_at timestamp, duration interval)")
+        .unwrap();
+
+    assert_eq!(
+        sim.execute("select extract(year from name) from events"),
+        Err(Error::TypeMismatch {
+            expected: SqlType::Timestamp,
+            got: SqlType::Text
+        })
+    );
+}
+
+#[test]
+fn date_part() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table events (id int primary key, name text not null, created_at timestamp not null, finished_at timestamp, duration interval)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select date_part('month', created_at) as month from events where date_part($1, created_at) = $2")
+        .unwrap();
+
+    assert_eq!(
+        resolve.get_output_with_name("month").unwrap().ty,
+        SqlType::Double
+    );
+    assert_eq!(resolve.get_input(0).unwrap().ty, SqlType::Text);
+    assert_eq!(resolve.get_input(1).unwrap().ty, SqlType::Double);
+}
+
+#[test]
+fn date_part_wrong_argument_count() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table events (id int primary key, name text not null, created_at timestamp not null, finished_at timestamp, duration interval)")
+        .unwrap();
+
+    assert_eq!(
+        sim.execute("select date_part('month') from events"),
+        Err(Error::FunctionArgumentCount {
+            expected: 2,
+            got: 1
+        })
+    );
+}
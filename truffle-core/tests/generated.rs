@@ -0,0 +1,68 @@
+use truffle::{DialectKind, Error, Simulator, ty::SqlType};
+
+#[test]
+fn generated_column() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table person (id int primary key, first text not null, last text not null, full_name text generated always as (first || ' ' || last) stored)")
+        .unwrap();
+
+    let full_name = sim
+        .get_table("person")
+        .unwrap()
+        .get_column("full_name")
+        .unwrap();
+    assert_eq!(full_name.ty, SqlType::Text);
+    assert!(full_name.default);
+}
+
+#[test]
+fn generated_column_omitted_from_insert() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table item (id int primary key, price int not null, quantity int not null, total int not null generated always as (price * quantity) stored)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("insert into item (id, price, quantity) values ($1, $2, $3) returning total")
+        .unwrap();
+
+    assert_eq!(resolve.inputs.len(), 3);
+    assert_eq!(
+        resolve.get_output_with_name("total").unwrap().ty,
+        SqlType::Integer
+    );
+}
+
+#[test]
+fn generated_column_doesnt_exist() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+
+    assert_eq!(
+        sim.execute("create table person (id int primary key, first text not null, full_name text generated always as (first || ' ' || middle) stored)"),
+        Err(Error::ColumnDoesntExist("middle".to_string()))
+    );
+}
+
+#[test]
+fn generated_column_type_mismatch() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+
+    assert_eq!(
+        sim.execute("create table item (id int primary key, name text not null, total int generated always as (name) stored)"),
+        Err(Error::TypeMismatch {
+            expected: SqlType::Integer,
+            got: SqlType::Text
+        })
+    );
+}
+
+#[test]
+fn identity_column() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute(
+        "create table person (id int generated always as identity primary key, name text not null)",
+    )
+    .unwrap();
+
+    sim.execute("insert into person (name) values ($1)")
+        .unwrap();
+}
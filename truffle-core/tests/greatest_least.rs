@@ -0,0 +1,101 @@
+use truffle::{Error, Simulator, ty::SqlType};
+
+#[test]
+fn greatest_promotes_numeric_arguments() {
+    let mut sim = Simulator::default();
+    sim.execute("create table scores (id int primary key, a int not null, b bigint not null, c real, name text not null)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select greatest(a, b) as high, least(a, b, c) as low from scores")
+        .unwrap();
+
+    let high = resolve.get_output_with_name("high").unwrap();
+    assert_eq!(high.ty, SqlType::BigInt);
+    assert!(!high.nullable);
+
+    let low = resolve.get_output_with_name("low").unwrap();
+    assert_eq!(low.ty, SqlType::Float);
+    assert!(low.nullable);
+}
+
+#[test]
+fn greatest_placeholder() {
+    let mut sim = Simulator::default();
+    sim.execute("create table scores (id int primary key, a int not null, b bigint not null, c real, name text not null)")
+        .unwrap();
+
+    let resolve = sim.execute("select greatest(a, $1) from scores").unwrap();
+
+    assert_eq!(resolve.get_input(0).unwrap().ty, SqlType::Integer);
+}
+
+#[test]
+fn least_text() {
+    let mut sim = Simulator::default();
+    sim.execute("create table scores (id int primary key, a int not null, b bigint not null, c real, name text not null)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select least(name, 'm') as first from scores")
+        .unwrap();
+
+    assert_eq!(
+        resolve.get_output_with_name("first").unwrap().ty,
+        SqlType::Text
+    );
+}
+
+#[test]
+fn greatest_type_mismatch() {
+    let mut sim = Simulator::default();
+    sim.execute("create table scores (id int primary key, a int not null, b bigint not null, c real, name text not null)")
+        .unwrap();
+
+    assert_eq!(
+        sim.execute("select greatest(a, name) from scores"),
+        Err(Error::TypeMismatch {
+            expected: SqlType::Integer,
+            got: SqlType::Text
+        })
+    );
+}
+
+#[test]
+fn greatest_without_arguments() {
+    let mut sim = Simulator::default();
+    sim.execute("create table scores (id int primary key, a int not null, b bigint not null, c real, name text not null)")
+        .unwrap();
+
+    assert_eq!(
+        sim.execute("select greatest() from scores"),
+        Err(Error::FunctionArgumentCount {
+            expected: 1,
+            got: 0
+        })
+    );
+}
+
+#[test]
+fn greatest_unknown_column() {
+    let mut sim = Simulator::default();
+    sim.execute("create table scores (id int primary key, a int not null, b bigint not null, c real, name text not null)")
+        .unwrap();
+
+    assert_eq!(
+        sim.execute("select greatest(missing, $1) from scores"),
+        Err(Error::ColumnDoesntExist("missing".to_string()))
+    );
+}
+
+#[test]
+fn greatest_null_and_placeholder_only() {
+    let mut sim = Simulator::default();
+    sim.execute("create table scores (id int primary key, a int not null, b bigint not null, c real, name text not null)")
+        .unwrap();
+
+    assert!(matches!(
+        sim.execute("select greatest(null, $1) from scores"),
+        Err(Error::FunctionCall(_))
+    ));
+}
@@ -0,0 +1,64 @@
+use truffle::{Simulator, ty::SqlType};
+
+#[test]
+fn select_in_list_with_null() {
+    let mut sim = Simulator::default();
+    sim.execute("create table item (id int primary key, status int not null)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select status in (1, 2, null) as active from item")
+        .unwrap();
+
+    let output = resolve.get_output_with_name("active").unwrap();
+    assert_eq!(output.ty, SqlType::Boolean);
+    assert!(output.nullable);
+}
+
+#[test]
+fn select_in_list_without_null() {
+    let mut sim = Simulator::default();
+    sim.execute("create table item (id int primary key, status int not null)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select status in (1, 2) as active from item")
+        .unwrap();
+
+    let output = resolve.get_output_with_name("active").unwrap();
+    assert_eq!(output.ty, SqlType::Boolean);
+    assert!(!output.nullable);
+}
+
+#[test]
+fn select_in_list_nullable_expr() {
+    let mut sim = Simulator::default();
+    sim.execute("create table item (id int primary key, status int)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select status in (1, 2) as active from item")
+        .unwrap();
+
+    assert!(resolve.get_output_with_name("active").unwrap().nullable);
+}
+
+#[test]
+fn select_where_in_list_with_null() {
+    let mut sim = Simulator::default();
+    sim.execute("create table item (id int primary key, status int not null)")
+        .unwrap();
+
+    sim.execute("select id from item where status in (1, 2, null)")
+        .unwrap();
+}
+
+#[test]
+fn select_having_in_list_with_null() {
+    let mut sim = Simulator::default();
+    sim.execute("create table item (id int primary key, status int not null)")
+        .unwrap();
+
+    sim.execute("select status from item group by status having count(*) in (1, null)")
+        .unwrap();
+}
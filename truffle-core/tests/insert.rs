@@ -127,6 +127,63 @@ fn insert_missing_required_column() {
     );
 }
 
+#[test]
+fn insert_partial_columns_out_of_order() {
+    let mut sim = Simulator::default();
+    sim.execute("create table users (id integer primary key, name text not null, email text not null, bio text, active boolean not null default true);")
+        .unwrap();
+
+    let resolve = sim
+        .execute("insert into users (email, id, name) values ($1, $2, $3)")
+        .unwrap();
+
+    assert_eq!(resolve.inputs.len(), 3);
+    assert_eq!(resolve.get_input(0).unwrap().ty, SqlType::Text);
+    assert_eq!(resolve.get_input(1).unwrap().ty, SqlType::Integer);
+    assert_eq!(resolve.get_input(2).unwrap().ty, SqlType::Text);
+}
+
+#[test]
+fn insert_partial_columns_type_mismatch() {
+    let mut sim = Simulator::default();
+    sim.execute("create table users (id integer primary key, name text not null, bio text);")
+        .unwrap();
+
+    assert_eq!(
+        sim.execute("insert into users (name, id) values (1, 2)"),
+        Err(Error::TypeMismatch {
+            expected: SqlType::Text,
+            got: SqlType::SmallInt
+        })
+    );
+}
+
+#[test]
+fn insert_missing_required_column_multiple_rows() {
+    let mut sim = Simulator::default();
+    sim.execute(
+        "create table users (id integer primary key, name text not null, email text not null);",
+    )
+    .unwrap();
+
+    assert_eq!(
+        sim.execute("insert into users (id, name) values ($1, $2), ($3, $4)"),
+        Err(Error::RequiredColumnMissing("email".to_string()))
+    );
+}
+
+#[test]
+fn insert_unknown_column_in_list() {
+    let mut sim = Simulator::default();
+    sim.execute("create table users (id integer primary key, name text not null);")
+        .unwrap();
+
+    assert_eq!(
+        sim.execute("insert into users (id, name, email) values ($1, $2, $3)"),
+        Err(Error::ColumnDoesntExist("email".to_string()))
+    );
+}
+
 #[test]
 fn insert_resolved_inputs() {
     let mut sim = Simulator::default();
@@ -376,3 +433,77 @@ fn insert_with_returning_alias() {
         SqlType::Float
     );
 }
+
+#[test]
+fn insert_resolves_as_mutation() {
+    let mut sim = Simulator::default();
+    sim.execute("create table person (id integer not null, name text not null)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("insert into person (id, name) values ($1, $2) returning id")
+        .unwrap();
+    assert!(resolve.mutates);
+    assert_eq!(resolve.outputs.len(), 1);
+
+    let resolve = sim.execute("select id from person").unwrap();
+    assert!(!resolve.mutates);
+}
+
+#[test]
+fn write_without_returning_has_no_outputs() {
+    let mut sim = Simulator::default();
+    sim.execute("create table person (id integer not null, name text not null)")
+        .unwrap();
+
+    for sql in [
+        "insert into person (id, name) values ($1, $2)",
+        "update person set name = $1 where id = $2",
+        "delete from person where id = $1",
+    ] {
+        let resolve = sim.execute(sql).unwrap();
+        assert!(resolve.mutates);
+        assert!(resolve.outputs.is_empty());
+    }
+}
+
+#[test]
+fn insert_default_keyword() {
+    let mut sim = Simulator::default();
+    sim.execute("create table person (id integer primary key, name text not null, age integer not null default 0);")
+        .unwrap();
+
+    let resolve = sim
+        .execute("insert into person (id, age, name) values ($1, default, $2)")
+        .unwrap();
+
+    assert_eq!(resolve.inputs.len(), 2);
+    assert_eq!(resolve.get_input(0).unwrap().ty, SqlType::Integer);
+    assert_eq!(resolve.get_input(1).unwrap().ty, SqlType::Text);
+}
+
+#[test]
+fn insert_default_keyword_by_column_index() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table person (id serial primary key, name text not null)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("insert into person values (DEFAULT, $1)")
+        .unwrap();
+
+    assert_eq!(resolve.inputs.len(), 1);
+    assert_eq!(resolve.get_input(0).unwrap().ty, SqlType::Text);
+}
+
+#[test]
+fn insert_default_keyword_on_not_default_column() {
+    let mut sim = Simulator::default();
+    sim.execute("create table person (id integer primary key, name text not null);")
+        .unwrap();
+
+    assert_eq!(
+        sim.execute("insert into person (id, name) values ($1, default)"),
+        Err(Error::DefaultOnNotDefaultColumn("name".to_string()))
+    );
+}
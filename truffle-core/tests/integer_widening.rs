@@ -0,0 +1,43 @@
+use truffle::{Error, Simulator, ty::SqlType};
+
+#[test]
+fn compare_integers_of_different_widths() {
+    let mut sim = Simulator::default();
+    sim.execute("create table item (id int primary key, small smallint not null, big bigint not null, name text not null)")
+        .unwrap();
+
+    sim.execute("select id from item where small = id").unwrap();
+    sim.execute("select id from item where id < big").unwrap();
+    sim.execute("select id from item where small between id and big")
+        .unwrap();
+}
+
+#[test]
+fn compare_count_with_integer_column() {
+    let mut sim = Simulator::default();
+    sim.execute("create table item (id int primary key, name text not null)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select name from item group by name having count(*) > max(id)")
+        .unwrap();
+
+    assert_eq!(resolve.outputs.len(), 1);
+}
+
+#[test]
+fn compare_integer_with_text_column() {
+    let mut sim = Simulator::default();
+    sim.execute(
+        "create table item (id int primary key, small smallint not null, name text not null)",
+    )
+    .unwrap();
+
+    assert_eq!(
+        sim.execute("select id from item where small = name"),
+        Err(Error::TypeMismatch {
+            expected: SqlType::SmallInt,
+            got: SqlType::Text
+        })
+    );
+}
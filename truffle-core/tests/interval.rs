@@ -0,0 +1,272 @@
+#![cfg(feature = "time")]
+
+use truffle::{DialectKind, Error, Simulator, ty::SqlType};
+
+#[test]
+fn select_interval_literal() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table events (id int primary key, name text not null, created_at timestamp not null, finished_at timestamp, duration interval)")
+        .unwrap();
+
+    let resolve = sim.execute("select interval '1 day' as day").unwrap();
+
+    let day = resolve.get_output_with_name("day").unwrap();
+    assert_eq!(day.ty, SqlType::Interval);
+    assert!(!day.nullable);
+}
+
+#[test]
+fn update_interval_string() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table events (id int primary key, name text not null, created_at timestamp not null, finished_at timestamp, duration interval)")
+        .unwrap();
+
+    sim.execute("update events set duration = '1 day' where id = 1")
+        .unwrap();
+}
+
+#[test]
+fn select_age_two_arguments() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table events (id int primary key, name text not null, created_at timestamp not null, finished_at timestamp, duration interval)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select age(finished_at, created_at) as took from events")
+        .unwrap();
+
+    let took = resolve.get_output_with_name("took").unwrap();
+    assert_eq!(took.ty, SqlType::Interval);
+    assert!(took.nullable);
+}
+
+#[test]
+fn select_age_one_argument() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table events (id int primary key, name text not null, created_at timestamp not null, finished_at timestamp, duration interval)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select age(created_at) as took from events")
+        .unwrap();
+
+    let took = resolve.get_output_with_name("took").unwrap();
+    assert_eq!(took.ty, SqlType::Interval);
+    assert!(!took.nullable);
+}
+
+#[test]
+fn select_age_placeholder() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table events (id int primary key, name text not null, created_at timestamp not null, finished_at timestamp, duration interval)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select age($1, created_at) from events")
+        .unwrap();
+
+    assert_eq!(resolve.inputs.len(), 1);
+    assert_eq!(resolve.get_input(0).unwrap().ty, SqlType::Timestamp);
+}
+
+#[test]
+fn select_age_non_temporal() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table events (id int primary key, name text not null, created_at timestamp not null, finished_at timestamp, duration interval)")
+        .unwrap();
+
+    assert_eq!(
+        sim.execute("select age(name) from events"),
+        Err(Error::TypeMismatch {
+            expected: SqlType::Timestamp,
+            got: SqlType::Text
+        })
+    );
+}
+
+#[test]
+fn select_age_too_many_arguments() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table events (id int primary key, name text not null, created_at timestamp not null, finished_at timestamp, duration interval)")
+        .unwrap();
+
+    assert_eq!(
+        sim.execute("select age(created_at, created_at, created_at) from events"),
+        Err(Error::FunctionArgumentCount {
+            expected: 2,
+            got: 3
+        })
+    );
+}
+
+#[test]
+fn select_timestamp_plus_interval() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table events (id int primary key, name text not null, created_at timestamp not null, finished_at timestamp, duration interval)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select created_at + interval '1 day' as tomorrow, finished_at - duration as started from events")
+        .unwrap();
+
+    let tomorrow = resolve.get_output_with_name("tomorrow").unwrap();
+    assert_eq!(tomorrow.ty, SqlType::TimestampTz);
+    assert!(!tomorrow.nullable);
+
+    let started = resolve.get_output_with_name("started").unwrap();
+    assert_eq!(started.ty, SqlType::TimestampTz);
+    assert!(started.nullable);
+}
+
+#[test]
+fn select_interval_plus_timestamp() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table events (id int primary key, name text not null, created_at timestamp not null, finished_at timestamp, duration interval)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select interval '1 day' + created_at as tomorrow from events")
+        .unwrap();
+
+    assert_eq!(
+        resolve.get_output_with_name("tomorrow").unwrap().ty,
+        SqlType::TimestampTz
+    );
+}
+
+#[test]
+fn select_timestamp_minus_timestamp() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table events (id int primary key, name text not null, created_at timestamp not null, finished_at timestamp, duration interval)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select finished_at - created_at as took from events")
+        .unwrap();
+
+    let took = resolve.get_output_with_name("took").unwrap();
+    assert_eq!(took.ty, SqlType::Interval);
+    assert!(took.nullable);
+}
+
+#[test]
+fn select_date_plus_interval() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table shift (id int primary key, day date not null)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select day + interval '8 hours' as start, day - day as days from shift")
+        .unwrap();
+
+    assert_eq!(
+        resolve.get_output_with_name("start").unwrap().ty,
+        SqlType::Timestamp
+    );
+    assert_eq!(
+        resolve.get_output_with_name("days").unwrap().ty,
+        SqlType::Integer
+    );
+}
+
+#[test]
+fn select_date_plus_integer() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table shift (id int primary key, day date not null)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select day + 1 as tomorrow, day - 7 as last_week, 2 + day as later from shift")
+        .unwrap();
+
+    let tomorrow = resolve.get_output_with_name("tomorrow").unwrap();
+    assert_eq!(tomorrow.ty, SqlType::Date);
+    assert!(!tomorrow.nullable);
+
+    assert_eq!(
+        resolve.get_output_with_name("last_week").unwrap().ty,
+        SqlType::Date
+    );
+    assert_eq!(
+        resolve.get_output_with_name("later").unwrap().ty,
+        SqlType::Date
+    );
+}
+
+#[test]
+fn select_time_minus_time() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table shift (id int primary key, starts time not null, ends time)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select ends - starts as took from shift")
+        .unwrap();
+
+    let took = resolve.get_output_with_name("took").unwrap();
+    assert_eq!(took.ty, SqlType::Interval);
+    assert!(took.nullable);
+}
+
+#[test]
+fn select_interval_plus_interval() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table events (id int primary key, name text not null, created_at timestamp not null, finished_at timestamp, duration interval)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select duration + interval '1 hour' as longer, duration - duration as zero from events")
+        .unwrap();
+
+    let longer = resolve.get_output_with_name("longer").unwrap();
+    assert_eq!(longer.ty, SqlType::Interval);
+    assert!(longer.nullable);
+
+    assert_eq!(
+        resolve.get_output_with_name("zero").unwrap().ty,
+        SqlType::Interval
+    );
+}
+
+#[test]
+fn select_timestamp_minus_placeholder() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table events (id int primary key, name text not null, created_at timestamp not null, finished_at timestamp, duration interval)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select id from events where created_at > now() - $1")
+        .unwrap();
+
+    assert_eq!(resolve.get_input(0).unwrap().ty, SqlType::Interval);
+}
+
+#[test]
+fn select_timestamp_plus_integer() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table events (id int primary key, name text not null, created_at timestamp not null, finished_at timestamp, duration interval)")
+        .unwrap();
+
+    assert_eq!(
+        sim.execute("select created_at + 1 from events"),
+        Err(Error::TypeMismatch {
+            expected: SqlType::TimestampTz,
+            got: SqlType::SmallInt
+        })
+    );
+}
+
+#[test]
+fn select_age_without_arguments() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table events (id int primary key, name text not null, created_at timestamp not null, finished_at timestamp, duration interval)")
+        .unwrap();
+
+    assert_eq!(
+        sim.execute("select age() from events"),
+        Err(Error::FunctionArgumentCount {
+            expected: 1,
+            got: 0
+        })
+    );
+}
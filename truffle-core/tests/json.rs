@@ -0,0 +1,192 @@
+#![cfg(feature = "json")]
+
+use truffle::{DialectKind, Error, Simulator, ty::SqlType};
+
+#[test]
+fn json_arrow_operators() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table person (id int primary key, data json not null)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select data -> 'name' as name, data -> 'name' ->> 'first' as first, data -> 0 as head from person")
+        .unwrap();
+
+    let name = resolve.get_output_with_name("name").unwrap();
+    assert_eq!(name.ty, SqlType::Json);
+    assert!(name.nullable);
+
+    assert_eq!(
+        resolve.get_output_with_name("first").unwrap().ty,
+        SqlType::Text
+    );
+    assert_eq!(
+        resolve.get_output_with_name("head").unwrap().ty,
+        SqlType::Json
+    );
+}
+
+#[test]
+fn json_arrow_placeholder_key() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table person (id int primary key, data json not null)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select id from person where data ->> $1 = $2")
+        .unwrap();
+
+    assert_eq!(resolve.get_input(0).unwrap().ty, SqlType::Text);
+    assert_eq!(resolve.get_input(1).unwrap().ty, SqlType::Text);
+}
+
+#[test]
+fn json_arrow_requires_json() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table person (id int primary key, name text not null)")
+        .unwrap();
+
+    assert_eq!(
+        sim.execute("select name -> 'first' from person"),
+        Err(Error::TypeMismatch {
+            expected: SqlType::Json,
+            got: SqlType::Text
+        })
+    );
+}
+
+#[test]
+fn json_arrow_invalid_key() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table person (id int primary key, data json not null)")
+        .unwrap();
+
+    assert_eq!(
+        sim.execute("select data -> true from person"),
+        Err(Error::TypeMismatch {
+            expected: SqlType::Text,
+            got: SqlType::Boolean
+        })
+    );
+}
+
+#[test]
+fn json_arrow_sqlite() {
+    let mut sim = Simulator::with_dialect(DialectKind::Sqlite);
+    sim.execute("create table person (id int primary key, data json not null)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select data ->> 'name' as name from person")
+        .unwrap();
+
+    assert_eq!(
+        resolve.get_output_with_name("name").unwrap().ty,
+        SqlType::Text
+    );
+}
+
+#[test]
+fn json_arrow_unsupported_dialect() {
+    for dialect in [DialectKind::Generic, DialectKind::Ansi] {
+        let mut sim = Simulator::with_dialect(dialect);
+        sim.execute("create table person (id int primary key, data json not null)")
+            .unwrap();
+
+        assert!(matches!(
+            sim.execute("select data -> 'name' from person"),
+            Err(Error::Unsupported(_) | Error::Parsing(_))
+        ));
+    }
+}
+
+#[test]
+fn jsonb_column() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table person (id int primary key, data jsonb not null, raw json)")
+        .unwrap();
+
+    let table = sim.get_table("person").unwrap();
+    assert_eq!(table.get_column("data").unwrap().ty, SqlType::Jsonb);
+    assert_eq!(table.get_column("raw").unwrap().ty, SqlType::Json);
+
+    let resolve = sim
+        .execute("select data -> 'name' as name from person where data @> $1")
+        .unwrap();
+
+    assert_eq!(resolve.get_input(0).unwrap().ty, SqlType::Jsonb);
+    assert_eq!(
+        resolve.get_output_with_name("name").unwrap().ty,
+        SqlType::Jsonb
+    );
+}
+
+#[test]
+fn jsonb_literal() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table person (id int primary key, data jsonb not null)")
+        .unwrap();
+
+    sim.execute("insert into person (id, data) values (1, '{\"name\": \"John\"}')")
+        .unwrap();
+}
+
+#[test]
+fn jsonb_key_exists() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table person (id int primary key, data jsonb not null)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select id from person where data ? $1 or data ?| $2")
+        .unwrap();
+
+    assert_eq!(resolve.get_input(0).unwrap().ty, SqlType::Text);
+    assert_eq!(
+        resolve.get_input(1).unwrap().ty,
+        SqlType::Array(Box::new(SqlType::Text))
+    );
+}
+
+#[test]
+fn json_containment_requires_jsonb() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table person (id int primary key, data json not null)")
+        .unwrap();
+
+    assert_eq!(
+        sim.execute("select id from person where data @> $1"),
+        Err(Error::TypeMismatch {
+            expected: SqlType::Jsonb,
+            got: SqlType::Json
+        })
+    );
+    assert_eq!(
+        sim.execute("select id from person where data ? 'name'"),
+        Err(Error::TypeMismatch {
+            expected: SqlType::Jsonb,
+            got: SqlType::Json
+        })
+    );
+}
+
+#[test]
+fn jsonb_concat() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table person (id int primary key, data jsonb not null)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select data || data as merged, data || $1 as patched from person")
+        .unwrap();
+
+    assert_eq!(
+        resolve.get_output_with_name("merged").unwrap().ty,
+        SqlType::Jsonb
+    );
+    assert_eq!(
+        resolve.get_output_with_name("patched").unwrap().ty,
+        SqlType::Jsonb
+    );
+    assert_eq!(resolve.get_input(0).unwrap().ty, SqlType::Jsonb);
+}
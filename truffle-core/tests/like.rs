@@ -1,4 +1,4 @@
-use truffle::{Error, Simulator, ty::SqlType};
+use truffle::{DialectKind, Error, Simulator, ty::SqlType};
 
 #[test]
 fn select_with_like() {
@@ -49,3 +49,51 @@ fn select_with_ilike_wrong_type() {
         })
     );
 }
+
+#[test]
+fn select_with_like_operators() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table item (id int primary key, name text not null)")
+        .unwrap();
+
+    for op in ["~~", "~~*", "!~~", "!~~*"] {
+        let resolve = sim
+            .execute(format!("select name {op} $1 as matched from item"))
+            .unwrap();
+
+        assert_eq!(resolve.inputs.len(), 1);
+        assert_eq!(resolve.get_input(0).unwrap().ty, SqlType::Text);
+
+        let matched = resolve.get_output_with_name("matched").unwrap();
+        assert_eq!(matched.ty, SqlType::Boolean);
+        assert!(!matched.nullable);
+    }
+}
+
+#[test]
+fn select_with_like_operator_nullable() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table item (id int primary key, name text)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select name ~~ 'John%' as matched from item")
+        .unwrap();
+
+    assert!(resolve.get_output_with_name("matched").unwrap().nullable);
+}
+
+#[test]
+fn select_with_like_operator_wrong_type() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table item (id int primary key, name text not null, age integer not null)")
+        .unwrap();
+
+    assert_eq!(
+        sim.execute("select * from item where age ~~ 'John%'"),
+        Err(Error::TypeMismatch {
+            expected: SqlType::Text,
+            got: SqlType::Integer
+        })
+    );
+}
@@ -0,0 +1,122 @@
+use truffle::{DialectKind, Error, Simulator, ty::SqlType};
+
+#[test]
+fn select_with_offset_placeholder_without_limit() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table person (id int primary key, name text not null)")
+        .unwrap();
+
+    let resolve = sim.execute("select * from person offset $1").unwrap();
+
+    assert_eq!(resolve.inputs.len(), 1);
+    assert_eq!(resolve.get_input(0).unwrap().ty, SqlType::BigInt);
+    assert!(!resolve.get_input(0).unwrap().nullable);
+}
+
+#[test]
+fn select_with_limit_and_offset_placeholders() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table person (id int primary key, name text not null)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select * from person where name = $1 limit $2 offset $3")
+        .unwrap();
+
+    assert_eq!(resolve.inputs.len(), 3);
+    assert_eq!(resolve.get_input(0).unwrap().ty, SqlType::Text);
+    assert_eq!(resolve.get_input(1).unwrap().ty, SqlType::BigInt);
+    assert_eq!(resolve.get_input(2).unwrap().ty, SqlType::BigInt);
+}
+
+#[test]
+fn select_with_limit_placeholder_sqlite() {
+    let mut sim = Simulator::with_dialect(DialectKind::Sqlite);
+    sim.execute("create table person (id int primary key, name text not null)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select * from person limit ? offset ?")
+        .unwrap();
+
+    assert_eq!(resolve.inputs.len(), 2);
+    assert_eq!(resolve.get_input(0).unwrap().ty, SqlType::Integer);
+    assert_eq!(resolve.get_input(1).unwrap().ty, SqlType::Integer);
+}
+
+#[test]
+fn select_with_limit_literal() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table person (id int primary key, name text not null)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select * from person limit 10 offset 20")
+        .unwrap();
+
+    assert!(resolve.inputs.is_empty());
+}
+
+#[test]
+fn select_with_limit_text() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table person (id int primary key, name text not null)")
+        .unwrap();
+
+    assert_eq!(
+        sim.execute("select * from person limit 'ten'"),
+        Err(Error::TypeMismatch {
+            expected: SqlType::BigInt,
+            got: SqlType::Text
+        })
+    );
+}
+
+#[test]
+fn select_with_limit_float() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table person (id int primary key, name text not null)")
+        .unwrap();
+
+    assert!(matches!(
+        sim.execute("select * from person limit 1.5"),
+        Err(Error::TypeMismatch { .. })
+    ));
+}
+
+#[test]
+fn select_with_negative_limit() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table person (id int primary key, name text not null)")
+        .unwrap();
+
+    assert!(matches!(
+        sim.execute("select * from person limit -1"),
+        Err(Error::Sql(_))
+    ));
+}
+
+#[test]
+fn select_with_limit_by_unsupported() {
+    let mut sim = Simulator::with_dialect(DialectKind::Generic);
+    sim.execute("create table person (id int primary key, name text not null)")
+        .unwrap();
+
+    // LIMIT BY is ClickHouse syntax, which isn't parsed by any dialect.
+    assert!(matches!(
+        sim.execute("select * from person limit $1 by name"),
+        Err(Error::Parsing(_))
+    ));
+}
+
+#[test]
+fn select_with_fetch_unsupported() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table person (id int primary key, name text not null)")
+        .unwrap();
+
+    assert!(matches!(
+        sim.execute("select * from person fetch first 5 rows only"),
+        Err(Error::Unsupported(_))
+    ));
+}
@@ -0,0 +1,75 @@
+use truffle::{Simulator, ty::SqlType};
+
+#[test]
+fn select_and_non_nullable() {
+    let mut sim = Simulator::default();
+    sim.execute("create table item (id int primary key, active boolean not null, visible boolean not null, archived boolean)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select active and visible as shown from item")
+        .unwrap();
+
+    let output = resolve.get_output_with_name("shown").unwrap();
+    assert_eq!(output.ty, SqlType::Boolean);
+    assert!(!output.nullable);
+}
+
+#[test]
+fn select_or_nullable() {
+    let mut sim = Simulator::default();
+    sim.execute("create table item (id int primary key, active boolean not null, visible boolean not null, archived boolean)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select active or archived as shown from item")
+        .unwrap();
+
+    assert!(resolve.get_output_with_name("shown").unwrap().nullable);
+}
+
+#[test]
+fn select_and_with_null_literal() {
+    let mut sim = Simulator::default();
+    sim.execute("create table item (id int primary key, active boolean not null, visible boolean not null, archived boolean)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select active and null as shown from item")
+        .unwrap();
+
+    assert!(resolve.get_output_with_name("shown").unwrap().nullable);
+}
+
+#[test]
+fn select_xor_nullable() {
+    let mut sim = Simulator::with_dialect(truffle::DialectKind::Generic);
+    sim.execute("create table item (id int primary key, active boolean not null, visible boolean not null, archived boolean)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select active xor archived as shown from item")
+        .unwrap();
+
+    assert!(resolve.get_output_with_name("shown").unwrap().nullable);
+
+    let resolve = sim
+        .execute("select active xor visible as shown from item")
+        .unwrap();
+
+    assert!(!resolve.get_output_with_name("shown").unwrap().nullable);
+}
+
+#[test]
+fn select_where_nullable_predicate_doesnt_affect_outputs() {
+    let mut sim = Simulator::default();
+    sim.execute("create table item (id int primary key, active boolean not null, visible boolean not null, archived boolean)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select id, active from item where archived and visible")
+        .unwrap();
+
+    assert!(!resolve.get_output_with_name("id").unwrap().nullable);
+    assert!(!resolve.get_output_with_name("active").unwrap().nullable);
+}
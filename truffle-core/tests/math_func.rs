@@ -0,0 +1,140 @@
+use truffle::{DialectKind, Error, Simulator, ty::SqlType};
+
+#[test]
+fn select_abs_ceil_floor_keep_type() {
+    let mut sim = Simulator::with_dialect(DialectKind::Sqlite);
+    sim.execute("create table products (id int primary key, name text not null, price real not null, stock int, balance bigint not null)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select abs(balance) as a, ceil(price) as c, floor(stock) as f from products")
+        .unwrap();
+
+    assert_eq!(
+        resolve.get_output_with_name("a").unwrap().ty,
+        SqlType::BigInt
+    );
+    assert_eq!(
+        resolve.get_output_with_name("c").unwrap().ty,
+        SqlType::Float
+    );
+
+    let f = resolve.get_output_with_name("f").unwrap();
+    assert_eq!(f.ty, SqlType::Integer);
+    assert!(f.nullable);
+}
+
+#[test]
+fn select_round() {
+    let mut sim = Simulator::with_dialect(DialectKind::Sqlite);
+    sim.execute("create table products (id int primary key, name text not null, price real not null, stock int, balance bigint not null)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select round(price) as r, round(price, 2) as p from products")
+        .unwrap();
+
+    assert_eq!(
+        resolve.get_output_with_name("r").unwrap().ty,
+        SqlType::Float
+    );
+    assert_eq!(
+        resolve.get_output_with_name("p").unwrap().ty,
+        SqlType::Float
+    );
+}
+
+#[test]
+fn select_round_precision_postgres() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table products (id int primary key, name text not null, price real not null, stock int, balance bigint not null)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select round(price, $1) as p from products")
+        .unwrap();
+
+    assert_eq!(resolve.get_input(0).unwrap().ty, SqlType::Integer);
+    assert_eq!(
+        resolve.get_output_with_name("p").unwrap().ty,
+        SqlType::Double
+    );
+}
+
+#[test]
+fn select_mod_power_sqrt() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table products (id int primary key, name text not null, price real not null, stock int, balance bigint not null)")
+        .unwrap();
+
+    let resolve = sim
+        .execute(
+            "select mod(balance, id) as m, power(price, 2) as p, sqrt(stock) as s from products",
+        )
+        .unwrap();
+
+    assert_eq!(
+        resolve.get_output_with_name("m").unwrap().ty,
+        SqlType::BigInt
+    );
+    assert_eq!(
+        resolve.get_output_with_name("p").unwrap().ty,
+        SqlType::Double
+    );
+
+    let s = resolve.get_output_with_name("s").unwrap();
+    assert_eq!(s.ty, SqlType::Double);
+    assert!(s.nullable);
+}
+
+#[test]
+fn select_math_placeholder() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table products (id int primary key, name text not null, price real not null, stock int, balance bigint not null)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select * from products where balance > abs($1)")
+        .unwrap();
+
+    assert_eq!(resolve.get_input(0).unwrap().ty, SqlType::BigInt);
+}
+
+#[test]
+fn select_abs_not_numeric() {
+    let mut sim = Simulator::with_dialect(DialectKind::Sqlite);
+    sim.execute("create table products (id int primary key, name text not null, price real not null, stock int, balance bigint not null)")
+        .unwrap();
+
+    assert_eq!(
+        sim.execute("select abs(name) from products"),
+        Err(Error::TypeNotNumeric(SqlType::Text))
+    );
+}
+
+#[test]
+fn select_mod_not_numeric() {
+    let mut sim = Simulator::with_dialect(DialectKind::Sqlite);
+    sim.execute("create table products (id int primary key, name text not null, price real not null, stock int, balance bigint not null)")
+        .unwrap();
+
+    assert_eq!(
+        sim.execute("select mod(stock, name) from products"),
+        Err(Error::TypeNotNumeric(SqlType::Text))
+    );
+}
+
+#[test]
+fn select_round_fractional_precision() {
+    let mut sim = Simulator::with_dialect(DialectKind::Sqlite);
+    sim.execute("create table products (id int primary key, name text not null, price real not null, stock int, balance bigint not null)")
+        .unwrap();
+
+    assert_eq!(
+        sim.execute("select round(price, 1.5) from products"),
+        Err(Error::TypeMismatch {
+            expected: SqlType::Integer,
+            got: SqlType::Float
+        })
+    );
+}
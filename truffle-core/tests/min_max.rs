@@ -113,3 +113,15 @@ fn select_with_max_function_aliased() {
         SqlType::Integer
     );
 }
+
+#[test]
+fn select_with_min_max_nullable_over_not_null_column() {
+    let mut sim = Simulator::default();
+    sim.execute("create table item (id int primary key, name text not null)")
+        .unwrap();
+
+    let resolve = sim.execute("select min(id), max(name) from item").unwrap();
+
+    assert_eq!(resolve.outputs.len(), 2);
+    assert!(resolve.outputs.values().all(|c| c.nullable));
+}
@@ -0,0 +1,99 @@
+#![cfg(feature = "ipnetwork")]
+
+use truffle::{DialectKind, Error, Simulator, ty::SqlType};
+
+#[test]
+fn network_columns() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table host (ip inet not null, net cidr)")
+        .unwrap();
+
+    let resolve = sim.execute("select ip, net from host").unwrap();
+
+    assert_eq!(
+        resolve.get_output_with_name("ip").unwrap().ty,
+        SqlType::Inet
+    );
+    assert_eq!(
+        resolve.get_output_with_name("net").unwrap().ty,
+        SqlType::Cidr
+    );
+}
+
+#[test]
+fn network_literals() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table host (ip inet not null, net cidr)")
+        .unwrap();
+
+    sim.execute("insert into host (ip, net) values ('192.168.0.1', '10.0.0.0/8')")
+        .unwrap();
+    sim.execute("select * from host where ip = '::1'").unwrap();
+
+    // Not an address, so it stays text.
+    assert_eq!(
+        sim.execute("insert into host (ip) values ('localhost')"),
+        Err(Error::TypeMismatch {
+            expected: SqlType::Inet,
+            got: SqlType::Text
+        })
+    );
+}
+
+#[test]
+fn network_containment() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table host (ip inet not null, net cidr not null)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select ip << net as inside, net >> $1 as contains from host")
+        .unwrap();
+
+    let inside = resolve.get_output_with_name("inside").unwrap();
+    assert_eq!(inside.ty, SqlType::Boolean);
+    assert!(!inside.nullable);
+
+    assert_eq!(resolve.get_input(0).unwrap().ty, SqlType::Cidr);
+}
+
+#[test]
+fn network_containment_requires_addresses() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table host (id int not null, ip inet not null)")
+        .unwrap();
+
+    assert!(matches!(
+        sim.execute("select ip << id from host"),
+        Err(Error::TypeMismatch { .. })
+    ));
+}
+
+#[test]
+fn network_columns_only_in_postgres() {
+    let mut sim = Simulator::with_dialect(DialectKind::Sqlite);
+
+    assert!(matches!(
+        sim.execute("create table host (ip inet not null)"),
+        Err(Error::InvalidType(_))
+    ));
+}
+
+#[test]
+fn network_cidr_converts_to_inet() {
+    assert!(SqlType::Inet.is_compatible(&SqlType::Cidr));
+    assert!(!SqlType::Cidr.is_compatible(&SqlType::Inet));
+
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table host (ip inet not null, net cidr not null)")
+        .unwrap();
+
+    sim.execute("update host set ip = net").unwrap();
+    assert_eq!(
+        sim.execute("update host set net = ip"),
+        Err(Error::TypeMismatch {
+            expected: SqlType::Cidr,
+            got: SqlType::Inet
+        })
+    );
+}
@@ -0,0 +1,98 @@
+use truffle::{Error, Simulator, ty::SqlType};
+
+#[test]
+fn select_order_by_ordinal() {
+    let mut sim = Simulator::default();
+    sim.execute("create table person (id int primary key, name text not null, age int not null)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select name, age from person order by 2")
+        .unwrap();
+
+    assert_eq!(resolve.outputs.len(), 2);
+}
+
+#[test]
+fn select_order_by_ordinal_with_wildcard() {
+    let mut sim = Simulator::default();
+    sim.execute("create table person (id int primary key, name text not null, age int not null)")
+        .unwrap();
+
+    sim.execute("select * from person order by 3 desc").unwrap();
+}
+
+#[test]
+fn select_order_by_ordinal_out_of_range() {
+    let mut sim = Simulator::default();
+    sim.execute("create table person (id int primary key, name text not null, age int not null)")
+        .unwrap();
+
+    assert_eq!(
+        sim.execute("select name, age from person order by 3"),
+        Err(Error::OrdinalOutOfRange(3))
+    );
+}
+
+#[test]
+fn select_order_by_ordinal_zero() {
+    let mut sim = Simulator::default();
+    sim.execute("create table person (id int primary key, name text not null, age int not null)")
+        .unwrap();
+
+    assert_eq!(
+        sim.execute("select name from person order by 0"),
+        Err(Error::OrdinalOutOfRange(0))
+    );
+}
+
+#[test]
+fn select_group_by_ordinal() {
+    let mut sim = Simulator::default();
+    sim.execute("create table person (id int primary key, name text not null, age int not null)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select name, count(id) from person group by 1")
+        .unwrap();
+
+    assert_eq!(resolve.outputs.len(), 2);
+    assert_eq!(
+        resolve.get_output_with_name("name").unwrap().ty,
+        SqlType::Text
+    );
+}
+
+#[test]
+fn select_group_by_ordinal_expression() {
+    let mut sim = Simulator::default();
+    sim.execute("create table person (id int primary key, name text not null, age int not null)")
+        .unwrap();
+
+    sim.execute("select age / 10, count(id) from person group by 1")
+        .unwrap();
+}
+
+#[test]
+fn select_group_by_ordinal_ungrouped_column() {
+    let mut sim = Simulator::default();
+    sim.execute("create table person (id int primary key, name text not null, age int not null)")
+        .unwrap();
+
+    assert_eq!(
+        sim.execute("select name, age from person group by 1"),
+        Err(Error::IncompatibleScope)
+    );
+}
+
+#[test]
+fn select_group_by_ordinal_out_of_range() {
+    let mut sim = Simulator::default();
+    sim.execute("create table person (id int primary key, name text not null, age int not null)")
+        .unwrap();
+
+    assert_eq!(
+        sim.execute("select name from person group by 2"),
+        Err(Error::OrdinalOutOfRange(2))
+    );
+}
@@ -0,0 +1,151 @@
+use truffle::{DialectKind, Error, Simulator, resolve::Placeholder, ty::SqlType};
+
+#[test]
+fn anonymous_placeholders_are_positional() {
+    let mut sim = Simulator::default();
+    sim.execute("create table item (id int primary key, name text not null)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select * from item where id = ? and name = ?")
+        .unwrap();
+
+    assert_eq!(resolve.inputs.len(), 2);
+    assert_eq!(resolve.get_input(0).unwrap().ty, SqlType::Integer);
+    assert_eq!(resolve.get_input(1).unwrap().ty, SqlType::Text);
+    assert_eq!(
+        resolve.placeholders,
+        vec![Placeholder::Anonymous, Placeholder::Anonymous]
+    );
+}
+
+#[test]
+fn numbered_placeholder_reuse_is_one_input() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table item (id int primary key, parent int not null)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select * from item where id = $1 or parent = $1")
+        .unwrap();
+
+    assert_eq!(resolve.inputs.len(), 1);
+    assert_eq!(resolve.get_input(0).unwrap().ty, SqlType::Integer);
+    assert_eq!(resolve.placeholders, vec![Placeholder::Numbered(1)]);
+}
+
+#[test]
+fn numbered_placeholders_out_of_order() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table item (id int primary key, name text not null)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select * from item where name = $2 and id = $1")
+        .unwrap();
+
+    assert_eq!(resolve.inputs.len(), 2);
+    assert_eq!(resolve.get_input(0).unwrap().ty, SqlType::Integer);
+    assert_eq!(resolve.get_input(1).unwrap().ty, SqlType::Text);
+}
+
+#[test]
+fn named_placeholder_reuse_is_one_input() {
+    let mut sim = Simulator::default();
+    sim.execute("create table item (id int primary key, parent int not null, name text not null)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select * from item where id = :id or parent = :id or name = :name")
+        .unwrap();
+
+    assert_eq!(resolve.inputs.len(), 2);
+    assert_eq!(resolve.get_input(0).unwrap().ty, SqlType::Integer);
+    assert_eq!(resolve.get_input(1).unwrap().ty, SqlType::Text);
+    assert_eq!(
+        resolve.placeholders,
+        vec![
+            Placeholder::Named(":id".to_string()),
+            Placeholder::Named(":name".to_string())
+        ]
+    );
+}
+
+#[test]
+fn subquery_keeps_numbered_placeholders() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table item (id int primary key, name text not null)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select * from item where name = $2 and id = (select id from item where id = $1)")
+        .unwrap();
+
+    assert_eq!(resolve.inputs.len(), 2);
+    assert_eq!(resolve.get_input(0).unwrap().ty, SqlType::Integer);
+    assert_eq!(resolve.get_input(1).unwrap().ty, SqlType::Text);
+}
+
+#[test]
+fn mixed_numbered_placeholder_reuse() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table item (id int primary key, parent int not null, name text not null)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select * from item where id = $1 and name = $2 or parent = $1")
+        .unwrap();
+
+    assert_eq!(resolve.inputs.len(), 2);
+    assert_eq!(resolve.get_input(0).unwrap().ty, SqlType::Integer);
+    assert_eq!(resolve.get_input(1).unwrap().ty, SqlType::Text);
+    assert_eq!(
+        resolve.placeholders,
+        vec![Placeholder::Numbered(1), Placeholder::Numbered(2)]
+    );
+}
+
+#[test]
+fn mixed_placeholder_styles_are_rejected() {
+    let mut sim = Simulator::default();
+    sim.execute("create table item (id int primary key, name text not null)")
+        .unwrap();
+
+    let result = sim.execute("select * from item where id = ? and name = $1");
+    assert!(matches!(result, Err(Error::MixedPlaceholderStyles)));
+
+    // Each statement may use its own style.
+    sim.execute("select * from item where id = ?; select * from item where id = $1")
+        .unwrap();
+}
+
+#[test]
+fn numbered_placeholder_reuse_with_different_types() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table item (id int primary key, name text not null)")
+        .unwrap();
+
+    assert_eq!(
+        sim.execute("select * from item where name = $1 and id = $1"),
+        Err(Error::TypeMismatch {
+            expected: SqlType::Text,
+            got: SqlType::Integer
+        })
+    );
+}
+
+#[test]
+fn anonymous_placeholders_follow_the_sql_order() {
+    let mut sim = Simulator::default();
+    sim.execute("create table item (id int primary key, name text not null)")
+        .unwrap();
+
+    // The WHERE clause is resolved before the select list.
+    let resolve = sim
+        .execute("select name = ? from item where id = ?")
+        .unwrap();
+
+    assert_eq!(resolve.inputs.len(), 2);
+    assert_eq!(resolve.get_input(0).unwrap().ty, SqlType::Text);
+    assert_eq!(resolve.get_input(1).unwrap().ty, SqlType::Integer);
+}
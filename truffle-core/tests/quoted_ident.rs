@@ -0,0 +1,62 @@
+use truffle::{DialectKind, Error, Simulator};
+
+#[test]
+fn quoted_table_name_is_case_sensitive() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table \"User\" (id int primary key, name text not null)")
+        .unwrap();
+
+    assert!(sim.has_table("User"));
+
+    sim.execute("select * from \"User\"").unwrap();
+    assert_eq!(
+        sim.execute("select * from User"),
+        Err(Error::TableDoesntExist("user".to_string()))
+    );
+}
+
+#[test]
+fn unquoted_identifiers_fold_to_lowercase() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table Person (Id int primary key, FullName text not null)")
+        .unwrap();
+
+    assert!(sim.has_table("person"));
+    assert!(sim.get_table("person").unwrap().has_column("fullname"));
+
+    let resolve = sim
+        .execute("select FULLNAME from PERSON where \"id\" = $1")
+        .unwrap();
+    assert!(resolve.get_output_with_name("fullname").is_some());
+
+    assert_eq!(
+        sim.execute("select \"FullName\" from person"),
+        Err(Error::ColumnDoesntExist("FullName".to_string()))
+    );
+}
+
+#[test]
+fn quoted_column_alias_keeps_case() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table person (id int primary key, name text not null)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select name as \"fullName\", id as userId from person")
+        .unwrap();
+
+    assert!(resolve.get_output_with_name("fullName").is_some());
+    assert!(resolve.get_output_with_name("userid").is_some());
+}
+
+#[test]
+fn unquoted_identifiers_preserved_sqlite() {
+    let mut sim = Simulator::with_dialect(DialectKind::Sqlite);
+    sim.execute("create table Person (id int primary key, FullName text not null)")
+        .unwrap();
+
+    assert!(sim.has_table("Person"));
+
+    let resolve = sim.execute("select FullName from Person").unwrap();
+    assert!(resolve.get_output_with_name("FullName").is_some());
+}
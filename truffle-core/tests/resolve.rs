@@ -0,0 +1,93 @@
+use truffle::{DialectKind, Error, Simulator, ty::SqlType};
+
+#[test]
+fn resolve_select_without_mutating() {
+    let mut sim = Simulator::default();
+    sim.execute("create table person (id int primary key, name text not null)")
+        .unwrap();
+
+    let sim = sim;
+    let resolve = sim.resolve("select name from person where id = ?").unwrap();
+
+    assert_eq!(resolve.get_input(0).unwrap().ty, SqlType::Integer);
+    assert_eq!(
+        resolve.get_output_with_name("name").unwrap().ty,
+        SqlType::Text
+    );
+    assert!(!resolve.mutates);
+}
+
+#[test]
+fn resolve_returning() {
+    let mut sim = Simulator::default();
+    sim.execute("create table person (id int primary key, name text not null)")
+        .unwrap();
+
+    let resolve = sim
+        .resolve("insert into person (id, name) values (?, ?) returning id")
+        .unwrap();
+
+    assert_eq!(resolve.inputs.len(), 2);
+    assert!(resolve.get_output_with_name("id").is_some());
+    assert!(resolve.mutates);
+}
+
+#[test]
+fn resolve_rejects_schema_changes() {
+    let sim = Simulator::default();
+
+    assert!(matches!(
+        sim.resolve("create table person (id int primary key)"),
+        Err(Error::MutationInReadOnly(_))
+    ));
+    assert!(!sim.has_table("person"));
+}
+
+#[test]
+fn resolve_rejects_changes_without_returning() {
+    let mut sim = Simulator::default();
+    sim.execute("create table person (id int primary key, name text not null)")
+        .unwrap();
+
+    for sql in [
+        "insert into person (id, name) values (?, ?)",
+        "update person set name = ? where id = ?",
+        "delete from person where id = ?",
+    ] {
+        assert!(matches!(
+            sim.resolve(sql),
+            Err(Error::MutationInReadOnly(_))
+        ));
+        assert!(sim.execute(sql).unwrap().mutates);
+    }
+}
+
+#[test]
+fn resolve_rejects_truncate() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table person (id int primary key, name text not null)")
+        .unwrap();
+
+    assert!(matches!(
+        sim.resolve("truncate person"),
+        Err(Error::MutationInReadOnly(_))
+    ));
+    sim.execute("truncate person").unwrap();
+}
+
+#[test]
+fn resolve_update_and_delete_returning() {
+    let mut sim = Simulator::default();
+    sim.execute("create table person (id int primary key, name text not null)")
+        .unwrap();
+
+    let resolve = sim
+        .resolve("update person set name = ? where id = ? returning name")
+        .unwrap();
+    assert!(resolve.get_output_with_name("name").is_some());
+
+    let resolve = sim
+        .resolve("delete from person where id = ? returning id")
+        .unwrap();
+    assert!(resolve.get_output_with_name("id").is_some());
+}
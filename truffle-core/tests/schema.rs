@@ -0,0 +1,176 @@
+use truffle::{Config, DialectKind, Error, Simulator};
+
+#[test]
+fn create_schema() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create schema app").unwrap();
+
+    assert!(sim.has_schema("app"));
+}
+
+#[test]
+fn create_schema_already_exists() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create schema app").unwrap();
+
+    assert_eq!(
+        sim.execute("create schema app"),
+        Err(Error::SchemaAlreadyExists("app".to_string()))
+    );
+}
+
+#[test]
+fn create_schema_if_not_exists() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create schema if not exists app").unwrap();
+    sim.execute("create schema if not exists app").unwrap();
+
+    assert!(sim.has_schema("app"));
+}
+
+#[test]
+fn create_extension() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute(
+        "create extension if not exists pgcrypto; create table person (id int primary key)",
+    )
+    .unwrap();
+
+    assert!(sim.has_table("person"));
+}
+
+#[test]
+fn create_table_in_default_schema() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table public.users (id int primary key, name text not null)")
+        .unwrap();
+
+    assert!(sim.has_table("users"));
+
+    sim.execute("select id from users").unwrap();
+    sim.execute("select users.name from public.users where id = $1")
+        .unwrap();
+}
+
+#[test]
+fn create_table_in_schema() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create schema app").unwrap();
+    sim.execute("create table app.users (id int primary key, name text not null)")
+        .unwrap();
+
+    assert!(sim.has_table("app.users"));
+    assert!(!sim.has_table("users"));
+
+    let resolve = sim
+        .execute("select u.name from app.users u where u.id = $1")
+        .unwrap();
+    assert_eq!(resolve.outputs.len(), 1);
+
+    sim.execute("insert into app.users (id, name) values ($1, $2)")
+        .unwrap();
+    sim.execute("update app.users set name = $1 where users.id = $2")
+        .unwrap();
+    sim.execute("delete from app.users where id = $1").unwrap();
+}
+
+#[test]
+fn create_table_in_schema_doesnt_exist() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+
+    assert_eq!(
+        sim.execute("create table app.users (id int primary key)"),
+        Err(Error::SchemaDoesntExist("app".to_string()))
+    );
+}
+
+#[test]
+fn select_unqualified_from_other_schema() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create schema app").unwrap();
+    sim.execute("create table app.users (id int primary key)")
+        .unwrap();
+
+    // Unqualified names are only looked up in the default schema.
+    assert_eq!(
+        sim.execute("select id from users"),
+        Err(Error::TableDoesntExist("users".to_string()))
+    );
+    sim.execute("select id from app.users").unwrap();
+}
+
+#[test]
+fn select_unqualified_prefers_default_schema() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create schema app").unwrap();
+    sim.execute("create table app.users (id int primary key)")
+        .unwrap();
+    sim.execute("create table users (id int primary key, name text not null)")
+        .unwrap();
+
+    sim.execute("select name from users").unwrap();
+    assert_eq!(
+        sim.execute("select name from app.users"),
+        Err(Error::ColumnDoesntExist("name".to_string()))
+    );
+}
+
+#[test]
+fn select_unqualified_in_several_schemas() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create schema app; create schema audit")
+        .unwrap();
+    sim.execute("create table app.users (id int primary key)")
+        .unwrap();
+    sim.execute("create table audit.users (id int primary key)")
+        .unwrap();
+
+    assert_eq!(
+        sim.execute("select id from users"),
+        Err(Error::TableDoesntExist("users".to_string()))
+    );
+}
+
+#[test]
+fn select_schema_table_doesnt_exist() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create schema app").unwrap();
+
+    assert_eq!(
+        sim.execute("select id from app.users"),
+        Err(Error::TableDoesntExist("app.users".to_string()))
+    );
+}
+
+#[test]
+fn foreign_key_to_schema_table() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create schema app").unwrap();
+    sim.execute("create table app.users (id int primary key)")
+        .unwrap();
+    sim.execute(
+        "create table post (id int primary key, user_id int not null references app.users(id))",
+    )
+    .unwrap();
+
+    assert_eq!(
+        sim.execute("drop table app.users"),
+        Err(Error::ForeignKeyConstraint("app.users".to_string()))
+    );
+}
+
+#[test]
+fn default_schema_from_config() {
+    let config = Config {
+        dialect: DialectKind::Postgres,
+        default_schema: Some("app".to_string()),
+        ..Default::default()
+    };
+
+    let mut sim = Simulator::with_config(&config).unwrap();
+    sim.execute("create table app.users (id int primary key)")
+        .unwrap();
+
+    assert!(sim.has_table("users"));
+    sim.execute("select id from app.users").unwrap();
+}
@@ -1,4 +1,4 @@
-use truffle::{Error, Simulator, ty::SqlType};
+use truffle::{DialectKind, Error, Simulator, ty::SqlType};
 
 #[test]
 fn select_wildcard_success() {
@@ -1283,6 +1283,77 @@ fn select_outer_join_type_mismatch() {
     );
 }
 
+#[test]
+fn select_left_join_right_columns_nullable() {
+    let mut sim = Simulator::default();
+    sim.execute("create table users (id int primary key, name text not null)")
+        .unwrap();
+    sim.execute(
+        "create table orders (id int primary key, user_id int not null, total float not null)",
+    )
+    .unwrap();
+
+    let resolved = sim
+        .execute("select u.name, o.total from users u left join orders o on u.id = o.user_id")
+        .unwrap();
+
+    assert!(!resolved.get_output("u", "name").unwrap().nullable);
+    assert!(resolved.get_output("o", "total").unwrap().nullable);
+}
+
+#[test]
+fn select_right_join_left_columns_nullable() {
+    let mut sim = Simulator::default();
+    sim.execute("create table users (id int primary key, name text not null)")
+        .unwrap();
+    sim.execute(
+        "create table orders (id int primary key, user_id int not null, total float not null)",
+    )
+    .unwrap();
+
+    let resolved = sim
+        .execute("select u.name, o.total from users u right join orders o on u.id = o.user_id")
+        .unwrap();
+
+    assert!(resolved.get_output("u", "name").unwrap().nullable);
+    assert!(!resolved.get_output("o", "total").unwrap().nullable);
+}
+
+#[test]
+fn select_full_outer_join_both_columns_nullable() {
+    let mut sim = Simulator::default();
+    sim.execute("create table users (id int primary key, name text not null)")
+        .unwrap();
+    sim.execute(
+        "create table orders (id int primary key, user_id int not null, total float not null)",
+    )
+    .unwrap();
+
+    let resolved = sim
+        .execute("select u.name, o.total from users u full outer join orders o on u.id = o.user_id")
+        .unwrap();
+
+    assert!(resolved.get_output("u", "name").unwrap().nullable);
+    assert!(resolved.get_output("o", "total").unwrap().nullable);
+}
+
+#[test]
+fn select_left_join_using_shared_column_not_nullable() {
+    let mut sim = Simulator::default();
+    sim.execute("create table table1 (id int primary key, name text not null)")
+        .unwrap();
+    sim.execute("create table table2 (id int primary key, value int not null)")
+        .unwrap();
+
+    let resolved = sim
+        .execute("select id, name, value from table1 left join table2 using (id)")
+        .unwrap();
+
+    assert!(!resolved.get_output_with_name("id").unwrap().nullable);
+    assert!(!resolved.get_output_with_name("name").unwrap().nullable);
+    assert!(resolved.get_output_with_name("value").unwrap().nullable);
+}
+
 #[test]
 fn select_ambiguous_alias_in_join() {
     let mut sim = Simulator::default();
@@ -1673,6 +1744,39 @@ fn select_with_having() {
     assert_eq!(resolve.outputs.len(), 2);
 }
 
+#[test]
+fn select_with_having_without_group_by() {
+    let mut sim = Simulator::default();
+    sim.execute("create table orders (id int primary key, total int not null)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select COUNT(*) from orders having COUNT(*) > 10")
+        .unwrap();
+    assert_eq!(resolve.outputs.len(), 1);
+
+    let resolve = sim
+        .execute("select SUM(total) from orders having MAX(total) > $1")
+        .unwrap();
+    assert_eq!(resolve.get_input(0).unwrap().ty, SqlType::Integer);
+}
+
+#[test]
+fn select_with_having_without_group_by_row_column() {
+    let mut sim = Simulator::default();
+    sim.execute("create table orders (id int primary key, total int not null)")
+        .unwrap();
+
+    assert_eq!(
+        sim.execute("select COUNT(*) from orders having total > 10"),
+        Err(Error::IncompatibleScope)
+    );
+    assert_eq!(
+        sim.execute("select id from orders having COUNT(*) > 10"),
+        Err(Error::IncompatibleScope)
+    );
+}
+
 #[test]
 fn select_with_having_incorrect_scope() {
     let mut sim = Simulator::default();
@@ -1801,6 +1905,37 @@ fn select_with_having_multiple_nested_levels() {
     assert_eq!(resolve.outputs.len(), 1);
 }
 
+#[test]
+fn select_with_group_by_qualified_column() {
+    let mut sim = Simulator::default();
+    sim.execute("create table users (id int primary key, dept int not null)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select u.dept, COUNT(id) from users u group by dept having u.dept > 1")
+        .unwrap();
+    assert_eq!(resolve.outputs.len(), 2);
+
+    let resolve = sim
+        .execute("select dept from users group by users.dept having dept > 1")
+        .unwrap();
+    assert_eq!(resolve.outputs.len(), 1);
+}
+
+#[test]
+fn select_with_group_by_qualified_column_other_table() {
+    let mut sim = Simulator::default();
+    sim.execute("create table users (id int primary key, dept int not null)")
+        .unwrap();
+    sim.execute("create table managers (id int primary key, dept int not null)")
+        .unwrap();
+
+    assert_eq!(
+        sim.execute("select u.dept from users u join managers m on u.id = m.id group by m.dept"),
+        Err(Error::IncompatibleScope)
+    );
+}
+
 #[test]
 fn select_with_order_by_valid() {
     let mut sim = Simulator::default();
@@ -1834,3 +1969,114 @@ fn select_with_order_by_aggregate_function() {
         .unwrap();
     assert_eq!(resolve.outputs.len(), 1);
 }
+
+#[test]
+fn select_where_bigint_compare_literal() {
+    let mut sim = Simulator::default();
+    sim.execute("create table t (id int primary key, big bigint not null, price real)")
+        .unwrap();
+
+    sim.execute("select * from t where big = 5").unwrap();
+    sim.execute("select * from t where 5 = big").unwrap();
+    sim.execute("select * from t where 5000000000 > big")
+        .unwrap();
+}
+
+#[test]
+fn select_where_literal_widened_to_float() {
+    let mut sim = Simulator::default();
+    sim.execute("create table t (id int primary key, big bigint not null, price real)")
+        .unwrap();
+
+    sim.execute("select * from t where 5 < price").unwrap();
+}
+
+#[test]
+fn select_union_unsupported() {
+    let mut sim = Simulator::default();
+    sim.execute("create table person (id int primary key, name text not null)")
+        .unwrap();
+
+    assert!(matches!(
+        sim.execute("select id from person where name = ? union select id from person"),
+        Err(Error::Unsupported(_))
+    ));
+}
+
+#[test]
+fn select_with_cte_unsupported() {
+    let mut sim = Simulator::default();
+    sim.execute("create table person (id int primary key, name text not null)")
+        .unwrap();
+
+    assert!(matches!(
+        sim.execute("with named as (select * from person where name = ?) select * from named"),
+        Err(Error::Unsupported(_))
+    ));
+}
+
+#[test]
+fn select_expression_with_alias() {
+    let mut sim = Simulator::default();
+    sim.execute("create table person (id int primary key, name text not null)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select name || '!' as shout, id + 1 as next from person")
+        .unwrap();
+
+    assert_eq!(resolve.outputs.len(), 2);
+    assert_eq!(
+        resolve.get_output_with_name("shout").unwrap().ty,
+        SqlType::Text
+    );
+    assert_eq!(
+        resolve.get_output_with_name("next").unwrap().ty,
+        SqlType::Integer
+    );
+}
+
+#[test]
+fn select_with_order_by_options() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute(
+        "create table person (id int primary key, name text not null, age int, created_at int)",
+    )
+    .unwrap();
+
+    for direction in ["", " asc", " desc"] {
+        for nulls in ["", " nulls first", " nulls last"] {
+            sim.execute(format!(
+                "select id from person order by created_at{direction}{nulls}, id{direction}{nulls}"
+            ))
+            .unwrap();
+        }
+    }
+}
+
+#[test]
+fn select_with_order_by_options_column_doesnt_exist() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table person (id int primary key, name text not null, age int)")
+        .unwrap();
+
+    assert_eq!(
+        sim.execute("select id from person order by updated_at desc nulls last"),
+        Err(Error::ColumnDoesntExist("updated_at".to_string()))
+    );
+}
+
+#[test]
+fn select_with_having_placeholder_compared_to_aggregate() {
+    let mut sim = Simulator::default();
+    sim.execute("create table orders (id int primary key, name text not null, total int not null)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select name, SUM(total) from orders group by name having MAX(total) > ? and COUNT(*) < ?")
+        .unwrap();
+
+    assert_eq!(resolve.inputs.len(), 2);
+    assert_eq!(resolve.get_input(0).unwrap().ty, SqlType::Integer);
+    assert_eq!(resolve.get_input(1).unwrap().ty, SqlType::BigInt);
+}
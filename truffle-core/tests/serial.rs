@@ -0,0 +1,88 @@
+use truffle::{DialectKind, Error, Simulator, ty::SqlType};
+
+#[test]
+fn serial_primary_key_is_defaulted() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table person (id serial primary key, name text not null)")
+        .unwrap();
+
+    let id = sim.get_table("person").unwrap().get_column("id").unwrap();
+    assert_eq!(id.ty, SqlType::Integer);
+    assert!(!id.nullable);
+    assert!(id.default);
+
+    let resolve = sim
+        .execute("insert into person (name) values ($1) returning id")
+        .unwrap();
+
+    assert_eq!(resolve.inputs.len(), 1);
+    assert_eq!(resolve.get_input(0).unwrap().ty, SqlType::Text);
+    assert_eq!(
+        resolve.get_output_with_name("id").unwrap().ty,
+        SqlType::Integer
+    );
+}
+
+#[test]
+fn serial_variants() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table counters (a smallserial, b serial4, c bigserial, d serial8)")
+        .unwrap();
+
+    let table = sim.get_table("counters").unwrap();
+    assert_eq!(table.get_column("a").unwrap().ty, SqlType::SmallInt);
+    assert_eq!(table.get_column("b").unwrap().ty, SqlType::Integer);
+    assert_eq!(table.get_column("c").unwrap().ty, SqlType::BigInt);
+    assert_eq!(table.get_column("d").unwrap().ty, SqlType::BigInt);
+    assert!(table.columns.values().all(|c| c.default && !c.nullable));
+}
+
+#[test]
+fn serial_type_mismatch() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table person (id bigserial primary key, name text not null)")
+        .unwrap();
+
+    assert_eq!(
+        sim.execute("insert into person (id, name) values ('abc', 'John')"),
+        Err(Error::TypeMismatch {
+            expected: SqlType::BigInt,
+            got: SqlType::Text
+        })
+    );
+}
+
+#[test]
+fn autoincrement_primary_key_is_defaulted() {
+    let mut sim = Simulator::with_dialect(DialectKind::Sqlite);
+    sim.execute("create table person (id integer primary key autoincrement, name text not null)")
+        .unwrap();
+
+    let id = sim.get_table("person").unwrap().get_column("id").unwrap();
+    assert_eq!(id.ty, SqlType::Integer);
+    assert!(!id.nullable);
+    assert!(id.default);
+
+    sim.execute("insert into person (name) values ($1)")
+        .unwrap();
+}
+
+#[test]
+fn autoincrement_requires_primary_key() {
+    let mut sim = Simulator::with_dialect(DialectKind::Sqlite);
+
+    assert!(matches!(
+        sim.execute("create table person (id integer autoincrement, name text not null)"),
+        Err(Error::Sql(_))
+    ));
+}
+
+#[test]
+fn autoincrement_requires_integer() {
+    let mut sim = Simulator::with_dialect(DialectKind::Sqlite);
+
+    assert!(matches!(
+        sim.execute("create table person (id text primary key autoincrement, name text not null)"),
+        Err(Error::Sql(_))
+    ));
+}
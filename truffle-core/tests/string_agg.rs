@@ -0,0 +1,86 @@
+use truffle::{DialectKind, Error, Simulator, ty::SqlType};
+
+#[test]
+fn select_string_agg() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table person (id int primary key, name text not null, team text not null)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select team, string_agg(name, ', ') as names from person group by team")
+        .unwrap();
+
+    let names = resolve.get_output_with_name("names").unwrap();
+    assert_eq!(names.ty, SqlType::Text);
+    assert!(names.nullable);
+}
+
+#[test]
+fn select_group_concat() {
+    let mut sim = Simulator::with_dialect(DialectKind::Sqlite);
+    sim.execute("create table person (id int primary key, name text not null)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select group_concat(name) as names from person")
+        .unwrap();
+
+    let names = resolve.get_output_with_name("names").unwrap();
+    assert_eq!(names.ty, SqlType::Text);
+    assert!(names.nullable);
+}
+
+#[test]
+fn select_string_agg_placeholder_separator() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table person (id int primary key, name text not null)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select string_agg(name, $1) from person")
+        .unwrap();
+
+    assert_eq!(resolve.get_input(0).unwrap().ty, SqlType::Text);
+}
+
+#[test]
+fn select_string_agg_non_text() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table person (id int primary key, name text not null)")
+        .unwrap();
+
+    assert_eq!(
+        sim.execute("select string_agg(id, ', ') from person"),
+        Err(Error::TypeMismatch {
+            expected: SqlType::Text,
+            got: SqlType::Integer
+        })
+    );
+}
+
+#[test]
+fn select_string_agg_mixed_with_row_column() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table person (id int primary key, name text not null)")
+        .unwrap();
+
+    assert_eq!(
+        sim.execute("select id, string_agg(name, ', ') from person"),
+        Err(Error::IncompatibleScope)
+    );
+}
+
+#[test]
+fn select_string_agg_too_many_arguments() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table person (id int primary key, name text not null)")
+        .unwrap();
+
+    assert_eq!(
+        sim.execute("select string_agg(name, ', ', ';') from person"),
+        Err(Error::FunctionArgumentCount {
+            expected: 2,
+            got: 3
+        })
+    );
+}
@@ -0,0 +1,129 @@
+use truffle::{Error, Simulator, ty::SqlType};
+
+#[test]
+fn select_upper_lower() {
+    let mut sim = Simulator::default();
+    sim.execute("create table users (id int primary key, name text not null, email text)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select upper(name) as u, lower(email) as l from users")
+        .unwrap();
+
+    let u = resolve.get_output_with_name("u").unwrap();
+    assert_eq!(u.ty, SqlType::Text);
+    assert!(!u.nullable);
+
+    let l = resolve.get_output_with_name("l").unwrap();
+    assert_eq!(l.ty, SqlType::Text);
+    assert!(l.nullable);
+}
+
+#[test]
+fn select_ltrim_rtrim() {
+    let mut sim = Simulator::default();
+    sim.execute("create table users (id int primary key, name text not null, email text)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select ltrim(name) as l, rtrim(name, ' ') as r from users")
+        .unwrap();
+
+    assert_eq!(resolve.get_output_with_name("l").unwrap().ty, SqlType::Text);
+    assert_eq!(resolve.get_output_with_name("r").unwrap().ty, SqlType::Text);
+}
+
+#[test]
+fn select_length() {
+    let mut sim = Simulator::default();
+    sim.execute("create table users (id int primary key, name text not null, email text)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select length(name) as n, char_length(email) as e from users")
+        .unwrap();
+
+    let n = resolve.get_output_with_name("n").unwrap();
+    assert_eq!(n.ty, SqlType::Integer);
+    assert!(!n.nullable);
+
+    let e = resolve.get_output_with_name("e").unwrap();
+    assert_eq!(e.ty, SqlType::Integer);
+    assert!(e.nullable);
+}
+
+#[test]
+fn select_string_function_placeholder() {
+    let mut sim = Simulator::default();
+    sim.execute("create table users (id int primary key, name text not null, email text)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select * from users where lower(email) = lower(?)")
+        .unwrap();
+
+    assert_eq!(resolve.inputs.len(), 1);
+    assert_eq!(resolve.get_input(0).unwrap().ty, SqlType::Text);
+}
+
+#[test]
+fn select_length_wrong_type() {
+    let mut sim = Simulator::default();
+    sim.execute("create table users (id int primary key, name text not null, email text)")
+        .unwrap();
+
+    assert_eq!(
+        sim.execute("select length(id) from users"),
+        Err(Error::TypeMismatch {
+            expected: SqlType::Text,
+            got: SqlType::Integer
+        })
+    );
+}
+
+#[test]
+fn select_upper_argument_count() {
+    let mut sim = Simulator::default();
+    sim.execute("create table users (id int primary key, name text not null, email text)")
+        .unwrap();
+
+    assert_eq!(
+        sim.execute("select upper(name, email) from users"),
+        Err(Error::FunctionArgumentCount {
+            expected: 1,
+            got: 2
+        })
+    );
+}
+
+#[test]
+fn select_upper_of_aggregate() {
+    let mut sim = Simulator::default();
+    sim.execute("create table users (id int primary key, name text not null, email text)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select upper(max(name)) as top, length(min(email)) as shortest from users")
+        .unwrap();
+
+    assert_eq!(
+        resolve.get_output_with_name("top").unwrap().ty,
+        SqlType::Text
+    );
+    assert!(resolve.get_output_with_name("shortest").unwrap().nullable);
+}
+
+#[test]
+fn select_upper_grouped() {
+    let mut sim = Simulator::default();
+    sim.execute("create table users (id int primary key, name text not null, email text)")
+        .unwrap();
+
+    sim.execute("select upper(name), count(*) from users group by name")
+        .unwrap();
+
+    assert_eq!(
+        sim.execute("select upper(email), count(*) from users group by name"),
+        Err(Error::IncompatibleScope)
+    );
+}
@@ -20,30 +20,29 @@ fn select_with_scalar_subquery() {
     assert_eq!(resolve.outputs.iter().next().unwrap().1.ty, SqlType::Text);
 }
 
-// #[test]
-// fn select_with_subquery_in_select_clause() {
-//     let mut sim = Simulator::default();
-//     sim.execute("create table department (id int primary key, name text not null)")
-//         .unwrap();
-//     sim.execute("create table employee (id int primary key, name text not null, dept_id int)")
-//         .unwrap();
+#[test]
+fn select_with_subquery_in_select_clause() {
+    let mut sim = Simulator::default();
+    sim.execute("create table department (id int primary key, name text not null)")
+        .unwrap();
+    sim.execute("create table employee (id int primary key, name text not null, dept_id int)")
+        .unwrap();
 
-//     let resolve = sim
-//         .execute("select name, (select name from department where id = employee.dept_id) as dept_name from employee where id = $1")
-//         .unwrap();
+    let resolve = sim
+        .execute("select name, (select name from department where id = employee.dept_id) as dept_name from employee where id = $1")
+        .unwrap();
 
-//     assert_eq!(resolve.inputs.len(), 1);
-//     assert_eq!(resolve.get_input(0).unwrap().ty, SqlType::Integer);
-//     assert_eq!(resolve.outputs.len(), 2);
-//     assert_eq!(
-//         resolve.get_output_with_name("name").unwrap().ty,
-//         SqlType::Text
-//     );
-//     assert_eq!(
-//         resolve.get_output_with_name("dept_name").unwrap().ty,
-//         SqlType::Text
-//     );
-// }
+    assert_eq!(resolve.inputs.len(), 1);
+    assert_eq!(resolve.get_input(0).unwrap().ty, SqlType::Integer);
+    assert_eq!(resolve.outputs.len(), 2);
+    assert_eq!(
+        resolve.get_output_with_name("name").unwrap().ty,
+        SqlType::Text
+    );
+    let dept_name = resolve.get_output_with_name("dept_name").unwrap();
+    assert_eq!(dept_name.ty, SqlType::Text);
+    assert!(dept_name.nullable);
+}
 
 // #[test]
 // fn select_with_exists_subquery() {
@@ -63,23 +62,23 @@ fn select_with_scalar_subquery() {
 //     assert_eq!(resolve.outputs.iter().next().unwrap().1.ty, SqlType::Text);
 // }
 
-// #[test]
-// fn select_with_in_subquery() {
-//     let mut sim = Simulator::default();
-//     sim.execute("create table department (id int primary key, name text not null)")
-//         .unwrap();
-//     sim.execute("create table employee (id int primary key, name text not null, dept_id int)")
-//         .unwrap();
+#[test]
+fn select_with_in_subquery() {
+    let mut sim = Simulator::default();
+    sim.execute("create table department (id int primary key, name text not null)")
+        .unwrap();
+    sim.execute("create table employee (id int primary key, name text not null, dept_id int)")
+        .unwrap();
 
-//     let resolve = sim
-//         .execute("select name from employee where dept_id in (select id from department where name = $1)")
-//         .unwrap();
+    let resolve = sim
+        .execute("select name from employee where dept_id in (select id from department where name = $1)")
+        .unwrap();
 
-//     assert_eq!(resolve.inputs.len(), 1);
-//     assert_eq!(resolve.get_input(0).unwrap().ty, SqlType::Text);
-//     assert_eq!(resolve.outputs.len(), 1);
-//     assert_eq!(resolve.outputs.iter().next().unwrap().1.ty, SqlType::Text);
-// }
+    assert_eq!(resolve.inputs.len(), 1);
+    assert_eq!(resolve.get_input(0).unwrap().ty, SqlType::Text);
+    assert_eq!(resolve.outputs.len(), 1);
+    assert_eq!(resolve.outputs.iter().next().unwrap().1.ty, SqlType::Text);
+}
 
 #[test]
 fn select_with_subquery_column_doesnt_exist() {
@@ -115,22 +114,22 @@ fn select_with_multiple_column_subquery_in_tuple() {
     );
 }
 
-// #[test]
-// fn select_with_correlated_subquery() {
-//     let mut sim = Simulator::default();
-//     sim.execute(
-//         "create table employee (id int primary key, name text not null, salary int, dept_id int)",
-//     )
-//     .unwrap();
+#[test]
+fn select_with_correlated_subquery() {
+    let mut sim = Simulator::default();
+    sim.execute(
+        "create table employee (id int primary key, name text not null, salary int, dept_id int)",
+    )
+    .unwrap();
 
-//     let resolve = sim
-//         .execute("select name from employee e1 where salary > (select avg(salary) from employee e2 where e2.dept_id = e1.dept_id)")
-//         .unwrap();
+    let resolve = sim
+        .execute("select name from employee e1 where salary > (select avg(salary) from employee e2 where e2.dept_id = e1.dept_id)")
+        .unwrap();
 
-//     assert_eq!(resolve.inputs.len(), 0);
-//     assert_eq!(resolve.outputs.len(), 1);
-//     assert_eq!(resolve.outputs.iter().next().unwrap().1.ty, SqlType::Text);
-// }
+    assert_eq!(resolve.inputs.len(), 0);
+    assert_eq!(resolve.outputs.len(), 1);
+    assert_eq!(resolve.outputs.iter().next().unwrap().1.ty, SqlType::Text);
+}
 
 #[test]
 fn select_with_subquery_table_doesnt_exist() {
@@ -143,3 +142,88 @@ fn select_with_subquery_table_doesnt_exist() {
         Err(Error::TableDoesntExist("nonexistent_table".to_string()))
     );
 }
+
+#[test]
+fn select_with_tuple_in_subquery() {
+    let mut sim = Simulator::default();
+    sim.execute("create table person (id int primary key, first_name text not null, last_name text not null)").unwrap();
+    sim.execute("create table contact (person_id int, first_name text, last_name text)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select id from person where (first_name, last_name) in (select first_name, last_name from contact where person_id = $1)")
+        .unwrap();
+
+    assert_eq!(resolve.inputs.len(), 1);
+    assert_eq!(resolve.get_input(0).unwrap().ty, SqlType::Integer);
+    assert_eq!(resolve.outputs.len(), 1);
+}
+
+#[test]
+fn select_with_tuple_in_subquery_count_mismatch() {
+    let mut sim = Simulator::default();
+    sim.execute("create table person (id int primary key, first_name text not null, last_name text not null)").unwrap();
+    sim.execute("create table contact (person_id int, first_name text, last_name text)")
+        .unwrap();
+
+    assert_eq!(
+        sim.execute("select id from person where (id, first_name, last_name) in (select first_name, last_name from contact)"),
+        Err(Error::ColumnCountMismatch {
+            expected: 2,
+            got: 3
+        })
+    );
+}
+
+#[test]
+fn select_with_tuple_in_subquery_type_mismatch() {
+    let mut sim = Simulator::default();
+    sim.execute("create table person (id int primary key, first_name text not null, last_name text not null)").unwrap();
+    sim.execute("create table contact (person_id int, first_name text, last_name text)")
+        .unwrap();
+
+    assert_eq!(
+        sim.execute("select id from person where (id, last_name) in (select first_name, last_name from contact)"),
+        Err(Error::TypeMismatch {
+            expected: SqlType::Text,
+            got: SqlType::Integer
+        })
+    );
+}
+
+#[test]
+fn select_with_correlated_count_subquery_in_select_clause() {
+    let mut sim = Simulator::default();
+    sim.execute("create table person (id int primary key, name text not null)")
+        .unwrap();
+    sim.execute(
+        "create table orders (id int primary key, user_id int not null references person(id))",
+    )
+    .unwrap();
+
+    let resolve = sim
+        .execute("select name, (select count(*) from orders where orders.user_id = person.id) as order_count from person")
+        .unwrap();
+
+    assert_eq!(resolve.outputs.len(), 2);
+
+    let order_count = resolve.get_output_with_name("order_count").unwrap();
+    assert_eq!(order_count.ty, SqlType::BigInt);
+    assert!(order_count.nullable);
+}
+
+#[test]
+fn select_with_correlated_subquery_unknown_outer_column() {
+    let mut sim = Simulator::default();
+    sim.execute("create table person (id int primary key, name text not null)")
+        .unwrap();
+    sim.execute("create table orders (id int primary key, user_id int not null)")
+        .unwrap();
+
+    assert_eq!(
+        sim.execute(
+            "select name, (select count(*) from orders where user_id = person_id) from person"
+        ),
+        Err(Error::ColumnDoesntExist("person_id".to_string()))
+    );
+}
@@ -1,4 +1,4 @@
-use truffle::Simulator;
+use truffle::{DialectKind, Simulator, ty::SqlType};
 
 #[test]
 fn select_substring() {
@@ -10,3 +10,46 @@ fn select_substring() {
     sim.execute("select 1 from item where substring(name, 1, 3) = 'abc'")
         .unwrap();
 }
+
+#[test]
+fn select_substring_placeholders() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+
+    sim.execute("create table item (id int primary key, name text not null)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select substring(name from $1 for $2) from item")
+        .unwrap();
+
+    assert_eq!(resolve.inputs.len(), 2);
+    assert_eq!(resolve.get_input(0).unwrap().ty, SqlType::Integer);
+    assert_eq!(resolve.get_input(1).unwrap().ty, SqlType::Integer);
+}
+
+#[test]
+fn select_substring_text_placeholder() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+
+    let resolve = sim.execute("select substring($1 from 1 for 3)").unwrap();
+
+    assert_eq!(resolve.get_input(0).unwrap().ty, SqlType::Text);
+}
+
+#[test]
+fn select_trim_placeholders() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+
+    sim.execute("create table item (id int primary key, name text not null)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select trim(both $1 from name) as trimmed from item")
+        .unwrap();
+
+    assert_eq!(resolve.get_input(0).unwrap().ty, SqlType::Text);
+    assert_eq!(
+        resolve.get_output_with_name("trimmed").unwrap().ty,
+        SqlType::Text
+    );
+}
@@ -0,0 +1,60 @@
+use truffle::{DialectKind, Error, Simulator, ty::SqlType};
+
+#[test]
+fn select_from_json_to_recordset() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+
+    let resolve = sim
+        .execute("select t.id, t.name from json_to_recordset($1) as t(id int, name text)")
+        .unwrap();
+
+    assert_eq!(resolve.inputs.len(), 1);
+    assert_eq!(resolve.outputs.len(), 2);
+
+    let id = resolve.get_output_with_name("id").unwrap();
+    assert_eq!(id.ty, SqlType::Integer);
+    assert!(id.nullable);
+
+    let name = resolve.get_output_with_name("name").unwrap();
+    assert_eq!(name.ty, SqlType::Text);
+}
+
+#[test]
+fn select_from_table_function_joined() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table person (id int primary key, name text not null)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select p.name, t.age from json_to_recordset($1) as t(id int, age int) join person p on p.id = t.id")
+        .unwrap();
+
+    assert_eq!(resolve.inputs.len(), 1);
+    assert_eq!(
+        resolve.get_output_with_name("age").unwrap().ty,
+        SqlType::Integer
+    );
+}
+
+#[test]
+fn select_from_table_function_column_doesnt_exist() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+
+    assert_eq!(
+        sim.execute("select t.email from json_to_recordset($1) as t(id int, name text)"),
+        Err(Error::QualifiedColumnDoesntExist {
+            qualifier: "t".to_string(),
+            column: "email".to_string()
+        })
+    );
+}
+
+#[test]
+fn select_from_table_function_without_column_definitions() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+
+    assert!(matches!(
+        sim.execute("select * from json_to_recordset($1) as t"),
+        Err(Error::Unsupported(_))
+    ));
+}
@@ -0,0 +1,34 @@
+use truffle::{DialectKind, Simulator};
+
+#[test]
+fn transaction_statements() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute(
+        "begin; create table person (id int primary key); savepoint before_pet; rollback to savepoint before_pet; release savepoint before_pet; commit;",
+    )
+    .unwrap();
+
+    assert!(sim.has_table("person"));
+
+    sim.execute("start transaction; rollback;").unwrap();
+}
+
+#[test]
+fn transaction_statements_sqlite() {
+    let mut sim = Simulator::with_dialect(DialectKind::Sqlite);
+    sim.execute("begin transaction; create table person (id int primary key); end transaction;")
+        .unwrap();
+
+    assert!(sim.has_table("person"));
+}
+
+#[test]
+fn set_statements() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("set search_path = public; set local statement_timeout = 0; set time zone 'UTC';")
+        .unwrap();
+
+    let resolve = sim.execute("set client_encoding to 'UTF8'").unwrap();
+    assert!(resolve.inputs.is_empty());
+    assert!(resolve.outputs.is_empty());
+}
@@ -0,0 +1,54 @@
+use truffle::{DialectKind, Error, Simulator};
+
+#[test]
+fn truncate_table() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table events (id int primary key, name text not null)")
+        .unwrap();
+
+    let resolve = sim.execute("truncate table events").unwrap();
+    assert!(resolve.inputs.is_empty());
+    assert!(resolve.outputs.is_empty());
+
+    assert!(sim.has_table("events"));
+}
+
+#[test]
+fn truncate_multiple_tables() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table person (id int primary key)")
+        .unwrap();
+    sim.execute("create table events (id int primary key, person_id int references person(id))")
+        .unwrap();
+
+    sim.execute("truncate person, events restart identity")
+        .unwrap();
+}
+
+#[test]
+fn truncate_table_doesnt_exist() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table events (id int primary key)")
+        .unwrap();
+
+    assert_eq!(
+        sim.execute("truncate table events, logs"),
+        Err(Error::TableDoesntExist("logs".to_string()))
+    );
+}
+
+#[test]
+fn truncate_referenced_table() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table person (id int primary key)")
+        .unwrap();
+    sim.execute("create table events (id int primary key, person_id int references person(id))")
+        .unwrap();
+
+    assert_eq!(
+        sim.execute("truncate table person"),
+        Err(Error::ForeignKeyConstraint("person".to_string()))
+    );
+
+    sim.execute("truncate table person cascade").unwrap();
+}
@@ -0,0 +1,142 @@
+use truffle::{Error, ty::SqlType};
+
+#[test]
+fn sql_type_display() {
+    assert_eq!(SqlType::Integer.to_string(), "integer");
+    assert_eq!(SqlType::BigInt.to_string(), "bigint");
+    assert_eq!(SqlType::Text.to_string(), "text");
+    assert_eq!(SqlType::Unknown("citext".to_string()).to_string(), "citext");
+}
+
+#[test]
+fn sql_type_round_trip() {
+    let types = [
+        SqlType::SmallInt,
+        SqlType::Integer,
+        SqlType::BigInt,
+        SqlType::Float,
+        SqlType::Double,
+        SqlType::Text,
+        SqlType::Boolean,
+        SqlType::Bytes,
+        SqlType::Unknown("citext".to_string()),
+    ];
+
+    for ty in types {
+        assert_eq!(ty.to_string().parse::<SqlType>().unwrap(), ty);
+    }
+}
+
+#[test]
+fn sql_type_enum_round_trip() {
+    let ty = SqlType::Enum {
+        name: "Mood".to_string(),
+        variants: vec!["Happy".to_string(), "it's, fine".to_string()],
+    };
+
+    assert_eq!(ty.to_string(), "enum Mood('Happy', 'it''s, fine')");
+    assert_eq!(ty.to_string().parse::<SqlType>().unwrap(), ty);
+    assert_eq!(
+        "tuple(enum Mood('Happy', 'it''s, fine') not null)"
+            .parse::<SqlType>()
+            .unwrap()
+            .to_string(),
+        "tuple(enum Mood('Happy', 'it''s, fine') not null)"
+    );
+    assert!("enum mood(happy)".parse::<SqlType>().is_err());
+}
+
+#[test]
+fn sql_type_parse_case_insensitive() {
+    assert_eq!(SqlType::try_from("INTEGER").unwrap(), SqlType::Integer);
+    assert_eq!(SqlType::try_from(" Text ").unwrap(), SqlType::Text);
+}
+
+#[test]
+fn sql_type_tuple_round_trip() {
+    let ty: SqlType = "tuple(integer not null, text null)".parse().unwrap();
+
+    let SqlType::Tuple(columns) = &ty else {
+        panic!("Expected a tuple");
+    };
+
+    assert_eq!(columns.len(), 2);
+    assert_eq!(columns[0].ty, SqlType::Integer);
+    assert!(!columns[0].nullable);
+    assert_eq!(columns[1].ty, SqlType::Text);
+    assert!(columns[1].nullable);
+
+    assert_eq!(ty.to_string(), "tuple(integer not null, text null)");
+}
+
+#[test]
+fn sql_type_parse_empty() {
+    assert_eq!(
+        "".parse::<SqlType>(),
+        Err(Error::InvalidType("".to_string()))
+    );
+}
+
+#[cfg(feature = "time")]
+#[test]
+fn sql_type_time_round_trip() {
+    assert_eq!(SqlType::TimestampTz.to_string(), "timestamptz");
+    assert_eq!(
+        "timestamptz".parse::<SqlType>().unwrap(),
+        SqlType::TimestampTz
+    );
+    assert_eq!(SqlType::Interval.to_string(), "interval");
+    assert_eq!("interval".parse::<SqlType>().unwrap(), SqlType::Interval);
+}
+
+#[cfg(feature = "decimal")]
+#[test]
+fn sql_type_decimal_round_trip() {
+    let ty = SqlType::Decimal {
+        precision: Some(12),
+        scale: Some(2),
+    };
+
+    assert_eq!(ty.to_string(), "decimal(12,2)");
+    assert_eq!("decimal(12,2)".parse::<SqlType>().unwrap(), ty);
+    assert_eq!("decimal(12, 2)".parse::<SqlType>().unwrap(), ty);
+    assert_eq!(
+        "decimal".parse::<SqlType>().unwrap(),
+        SqlType::Decimal {
+            precision: None,
+            scale: None
+        }
+    );
+    assert!("decimal(a)".parse::<SqlType>().is_err());
+}
+
+#[test]
+fn sql_type_varchar_round_trip() {
+    let ty = SqlType::Varchar { len: 50 };
+
+    assert_eq!(ty.to_string(), "varchar(50)");
+    assert_eq!("varchar(50)".parse::<SqlType>().unwrap(), ty);
+    assert_eq!("VARCHAR( 50 )".parse::<SqlType>().unwrap(), ty);
+    assert_eq!(
+        "char(2)".parse::<SqlType>().unwrap(),
+        SqlType::Char { len: 2 }
+    );
+    assert!("varchar(a)".parse::<SqlType>().is_err());
+}
+
+#[test]
+fn sql_type_array_round_trip() {
+    let ty = SqlType::Array(Box::new(SqlType::Integer));
+
+    assert_eq!(ty.to_string(), "integer[]");
+    assert_eq!("integer[]".parse::<SqlType>().unwrap(), ty);
+    assert!("[]".parse::<SqlType>().is_err());
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn sql_type_jsonb_round_trip() {
+    assert_eq!(SqlType::Jsonb.to_string(), "jsonb");
+    assert_eq!("jsonb".parse::<SqlType>().unwrap(), SqlType::Jsonb);
+    assert_ne!(SqlType::Jsonb, SqlType::Json);
+}
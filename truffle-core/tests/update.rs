@@ -380,3 +380,71 @@ fn update_with_join_and_returning() {
         SqlType::Text
     );
 }
+
+#[test]
+fn update_from_assigns_other_table_column() {
+    let mut sim = Simulator::default();
+    sim.execute("create table person (id int primary key, name text not null, department_id int)")
+        .unwrap();
+    sim.execute("create table department (id int primary key, name text not null)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("update person set name = d.name from department d where person.department_id = d.id and d.id = $1 returning person.id, d.name")
+        .unwrap();
+
+    assert_eq!(resolve.inputs.len(), 1);
+    assert_eq!(resolve.get_input(0).unwrap().ty, SqlType::Integer);
+    assert_eq!(resolve.outputs.len(), 2);
+    assert_eq!(
+        resolve.get_output_with_name("name").unwrap().ty,
+        SqlType::Text
+    );
+}
+
+#[test]
+fn update_from_type_mismatch() {
+    let mut sim = Simulator::default();
+    sim.execute("create table person (id int primary key, name text not null, department_id int)")
+        .unwrap();
+    sim.execute("create table department (id int primary key, name text not null)")
+        .unwrap();
+
+    assert_eq!(
+        sim.execute(
+            "update person set department_id = department.name from department where person.id = $1"
+        ),
+        Err(Error::TypeMismatch {
+            expected: SqlType::Integer,
+            got: SqlType::Text
+        })
+    );
+}
+
+#[test]
+fn update_from_ambiguous_column() {
+    let mut sim = Simulator::default();
+    sim.execute("create table person (id int primary key, name text not null, department_id int)")
+        .unwrap();
+    sim.execute("create table department (id int primary key, name text not null)")
+        .unwrap();
+
+    assert_eq!(
+        sim.execute("update person set name = 'x' from department where id = $1"),
+        Err(Error::AmbiguousColumn("id".to_string()))
+    );
+}
+
+#[test]
+fn update_from_alias_conflicts_with_table_name() {
+    let mut sim = Simulator::default();
+    sim.execute("create table person (id int primary key, name text not null, department_id int)")
+        .unwrap();
+    sim.execute("create table department (id int primary key, name text not null)")
+        .unwrap();
+
+    assert_eq!(
+        sim.execute("update person set name = 'x' from department person where person.id = $1"),
+        Err(Error::AliasIsTableName("person".to_string()))
+    );
+}
@@ -0,0 +1,83 @@
+use truffle::{DialectKind, Error, Simulator, ty::SqlType};
+
+#[test]
+fn insert_on_conflict_do_update() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table users (id int primary key, name text not null, email text)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("insert into users (id, name) values ($1, $2) on conflict (id) do update set name = excluded.name")
+        .unwrap();
+
+    assert_eq!(resolve.inputs.len(), 2);
+    assert_eq!(resolve.get_input(0).unwrap().ty, SqlType::Integer);
+    assert_eq!(resolve.get_input(1).unwrap().ty, SqlType::Text);
+}
+
+#[test]
+fn insert_on_conflict_do_update_placeholder() {
+    let mut sim = Simulator::with_dialect(DialectKind::Sqlite);
+    sim.execute("create table users (id int primary key, name text not null, email text unique)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("insert into users (id, name, email) values ($1, $2, $3) on conflict (email) do update set name = $4 where users.id > 0")
+        .unwrap();
+
+    assert_eq!(resolve.inputs.len(), 4);
+    assert_eq!(resolve.get_input(3).unwrap().ty, SqlType::Text);
+}
+
+#[test]
+fn insert_on_conflict_do_nothing() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table users (id int primary key, name text not null, email text)")
+        .unwrap();
+
+    sim.execute("insert into users (id, name) values ($1, $2) on conflict do nothing")
+        .unwrap();
+    sim.execute("insert into users (id, name) values ($1, $2) on conflict (id) do nothing")
+        .unwrap();
+}
+
+#[test]
+fn insert_on_conflict_target_not_unique() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table users (id int primary key, name text not null, email text)")
+        .unwrap();
+
+    assert_eq!(
+        sim.execute("insert into users (id, name) values ($1, $2) on conflict (name) do nothing"),
+        Err(Error::ConflictTargetNotUnique("name".to_string()))
+    );
+}
+
+#[test]
+fn insert_on_conflict_target_doesnt_exist() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table users (id int primary key, name text not null, email text)")
+        .unwrap();
+
+    assert_eq!(
+        sim.execute(
+            "insert into users (id, name) values ($1, $2) on conflict (missing) do nothing"
+        ),
+        Err(Error::ColumnDoesntExist("missing".to_string()))
+    );
+}
+
+#[test]
+fn insert_on_conflict_do_update_type_mismatch() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+    sim.execute("create table users (id int primary key, name text not null, email text)")
+        .unwrap();
+
+    assert_eq!(
+        sim.execute("insert into users (id, name) values ($1, $2) on conflict (id) do update set name = excluded.id"),
+        Err(Error::TypeMismatch {
+            expected: SqlType::Text,
+            got: SqlType::Integer
+        })
+    );
+}
@@ -0,0 +1,171 @@
+use std::collections::HashMap;
+
+use truffle::{
+    Arity, Config, Error, FunctionConfig, FunctionSignature, Nullability, Simulator, ty::SqlType,
+};
+
+#[test]
+fn register_function_fixed() {
+    let mut sim = Simulator::default();
+    sim.execute("create table users (id int primary key, tenant_id int not null, name text not null, bio text)")
+        .unwrap();
+    sim.register_function(
+        "slugify",
+        FunctionSignature {
+            arity: Arity::Fixed(vec![SqlType::Text]),
+            returns: SqlType::Text,
+            nullability: Nullability::Arguments,
+        },
+    );
+
+    let resolve = sim
+        .execute("select slugify(name) as slug, slugify(bio) as bio_slug from users")
+        .unwrap();
+
+    let slug = resolve.get_output_with_name("slug").unwrap();
+    assert_eq!(slug.ty, SqlType::Text);
+    assert!(!slug.nullable);
+    assert!(resolve.get_output_with_name("bio_slug").unwrap().nullable);
+}
+
+#[test]
+fn register_function_no_arguments() {
+    let mut sim = Simulator::default();
+    sim.execute("create table users (id int primary key, tenant_id int not null, name text not null, bio text)")
+        .unwrap();
+    sim.register_function(
+        "my_tenant_id",
+        FunctionSignature {
+            arity: Arity::Fixed(vec![]),
+            returns: SqlType::Integer,
+            nullability: Nullability::NotNull,
+        },
+    );
+
+    let resolve = sim
+        .execute("select * from users where tenant_id = my_tenant_id() and id = ?")
+        .unwrap();
+
+    assert_eq!(resolve.inputs.len(), 1);
+}
+
+#[test]
+fn register_function_argument_type() {
+    let mut sim = Simulator::default();
+    sim.execute("create table users (id int primary key, tenant_id int not null, name text not null, bio text)")
+        .unwrap();
+    sim.register_function(
+        "slugify",
+        FunctionSignature {
+            arity: Arity::Fixed(vec![SqlType::Text]),
+            returns: SqlType::Text,
+            nullability: Nullability::Arguments,
+        },
+    );
+
+    assert_eq!(
+        sim.execute("select slugify(id) from users"),
+        Err(Error::TypeMismatch {
+            expected: SqlType::Text,
+            got: SqlType::Integer
+        })
+    );
+    assert_eq!(
+        sim.execute("select slugify(name, name) from users"),
+        Err(Error::FunctionArgumentCount {
+            expected: 1,
+            got: 2
+        })
+    );
+}
+
+#[test]
+fn register_function_optional() {
+    let mut sim = Simulator::default();
+    sim.execute("create table users (id int primary key, tenant_id int not null, name text not null, bio text)")
+        .unwrap();
+    sim.register_function(
+        "truncate_to",
+        FunctionSignature {
+            arity: Arity::Optional {
+                required: vec![SqlType::Text],
+                optional: vec![SqlType::Integer],
+            },
+            returns: SqlType::Text,
+            nullability: Nullability::Arguments,
+        },
+    );
+
+    sim.execute("select truncate_to(name) from users").unwrap();
+
+    let resolve = sim
+        .execute("select truncate_to(name, ?) from users")
+        .unwrap();
+    assert_eq!(resolve.get_input(0).unwrap().ty, SqlType::Integer);
+
+    assert_eq!(
+        sim.execute("select truncate_to(name, 1, 2) from users"),
+        Err(Error::FunctionArgumentCount {
+            expected: 2,
+            got: 3
+        })
+    );
+}
+
+#[test]
+fn unregistered_function() {
+    let mut sim = Simulator::default();
+    sim.execute("create table users (id int primary key, tenant_id int not null, name text not null, bio text)")
+        .unwrap();
+
+    assert_eq!(
+        sim.execute("select slugify(name) from users"),
+        Err(Error::FunctionDoesntExist("slugify".to_string()))
+    );
+}
+
+#[test]
+fn functions_from_config() {
+    let config = Config {
+        functions: HashMap::from([(
+            "slugify".to_string(),
+            FunctionConfig {
+                args: vec!["text".to_string()],
+                returns: "text".to_string(),
+                ..Default::default()
+            },
+        )]),
+        ..Default::default()
+    };
+
+    let mut sim = Simulator::with_config(&config).unwrap();
+    sim.execute("create table users (id int primary key, name text not null)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select slugify(name) as slug from users")
+        .unwrap();
+    assert_eq!(
+        resolve.get_output_with_name("slug").unwrap().ty,
+        SqlType::Text
+    );
+}
+
+#[test]
+fn functions_from_config_invalid_type() {
+    let config = Config {
+        functions: HashMap::from([(
+            "slugify".to_string(),
+            FunctionConfig {
+                args: vec!["text".to_string()],
+                ..Default::default()
+            },
+        )]),
+        ..Default::default()
+    };
+
+    assert!(matches!(
+        Simulator::with_config(&config),
+        Err(Error::InvalidType(_))
+    ));
+}
@@ -0,0 +1,116 @@
+use truffle::{DialectKind, Error, Simulator, ty::SqlType};
+
+#[test]
+fn select_values() {
+    let mut sim = Simulator::default();
+
+    let resolve = sim.execute("values (1, 'a'), (2, 'b')").unwrap();
+
+    assert_eq!(resolve.outputs.len(), 2);
+
+    let column1 = resolve.get_output_with_name("column1").unwrap();
+    assert_eq!(column1.ty, SqlType::SmallInt);
+    assert!(!column1.nullable);
+
+    let column2 = resolve.get_output_with_name("column2").unwrap();
+    assert_eq!(column2.ty, SqlType::Text);
+    assert!(!column2.nullable);
+}
+
+#[test]
+fn select_values_widens_numbers() {
+    let mut sim = Simulator::default();
+
+    let resolve = sim.execute("values (1), (100000), (2.5)").unwrap();
+
+    assert_eq!(
+        resolve.get_output_with_name("column1").unwrap().ty,
+        SqlType::Float
+    );
+}
+
+#[test]
+fn select_values_null() {
+    let mut sim = Simulator::default();
+
+    let resolve = sim.execute("values (null, 1), ('a', 2)").unwrap();
+
+    let column1 = resolve.get_output_with_name("column1").unwrap();
+    assert_eq!(column1.ty, SqlType::Text);
+    assert!(column1.nullable);
+}
+
+#[test]
+fn select_values_placeholder() {
+    let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+
+    let resolve = sim.execute("values ($1, 'a'), (2, $2)").unwrap();
+
+    assert_eq!(resolve.inputs.len(), 2);
+    assert_eq!(resolve.get_input(0).unwrap().ty, SqlType::SmallInt);
+    assert_eq!(resolve.get_input(1).unwrap().ty, SqlType::Text);
+}
+
+#[test]
+fn select_values_only_null() {
+    let mut sim = Simulator::default();
+
+    assert!(sim.execute("values (null), (null)").is_err());
+}
+
+#[test]
+fn select_values_type_mismatch() {
+    let mut sim = Simulator::default();
+
+    assert_eq!(
+        sim.execute("values (1), ('a')"),
+        Err(Error::TypeMismatch {
+            expected: SqlType::SmallInt,
+            got: SqlType::Text
+        })
+    );
+}
+
+#[test]
+fn select_values_ragged_rows() {
+    let mut sim = Simulator::default();
+
+    assert_eq!(
+        sim.execute("values (1, 2), (3)"),
+        Err(Error::ColumnCountMismatch {
+            expected: 2,
+            got: 1
+        })
+    );
+}
+
+#[test]
+fn select_values_column() {
+    let mut sim = Simulator::default();
+
+    assert_eq!(
+        sim.execute("values (id)"),
+        Err(Error::ColumnDoesntExist("id".to_string()))
+    );
+}
+
+#[test]
+fn select_values_anonymous_placeholders_in_order() {
+    let mut sim = Simulator::default();
+
+    let resolve = sim.execute("values (1, ?), (?, 'b')").unwrap();
+
+    assert_eq!(resolve.inputs.len(), 2);
+    assert_eq!(resolve.get_input(0).unwrap().ty, SqlType::Text);
+    assert_eq!(resolve.get_input(1).unwrap().ty, SqlType::SmallInt);
+}
+
+#[test]
+fn select_values_invalid_row() {
+    let mut sim = Simulator::default();
+
+    assert_eq!(
+        sim.execute("values (1), (missing)"),
+        Err(Error::ColumnDoesntExist("missing".to_string()))
+    );
+}
@@ -0,0 +1,59 @@
+use truffle::{Error, Simulator, ty::SqlType};
+
+#[test]
+fn varchar_keeps_length() {
+    let mut sim = Simulator::default();
+    sim.execute("create table person (id int primary key, name varchar(50) not null, code char(2), bio varchar)")
+        .unwrap();
+
+    let table = sim.get_table("person").unwrap();
+    assert_eq!(
+        table.get_column("name").unwrap().ty,
+        SqlType::Varchar { len: 50 }
+    );
+    assert_eq!(
+        table.get_column("code").unwrap().ty,
+        SqlType::Char { len: 2 }
+    );
+    assert_eq!(table.get_column("bio").unwrap().ty, SqlType::Text);
+}
+
+#[test]
+fn varchar_interoperates_with_text() {
+    let mut sim = Simulator::default();
+    sim.execute(
+        "create table person (id int primary key, name varchar(50) not null, nickname text)",
+    )
+    .unwrap();
+
+    let resolve = sim
+        .execute("select upper(name) as loud from person where name = nickname or name = $1 or name = 'John'")
+        .unwrap();
+
+    assert_eq!(
+        resolve.get_input(0).unwrap().ty,
+        SqlType::Varchar { len: 50 }
+    );
+    assert_eq!(
+        resolve.get_output_with_name("loud").unwrap().ty,
+        SqlType::Text
+    );
+
+    sim.execute("insert into person (id, name, nickname) values (1, 'John', 'Johnny')")
+        .unwrap();
+}
+
+#[test]
+fn varchar_type_mismatch() {
+    let mut sim = Simulator::default();
+    sim.execute("create table person (id int primary key, name varchar(50) not null)")
+        .unwrap();
+
+    assert_eq!(
+        sim.execute("select id from person where name = 1"),
+        Err(Error::TypeMismatch {
+            expected: SqlType::Varchar { len: 50 },
+            got: SqlType::SmallInt
+        })
+    );
+}
@@ -0,0 +1,135 @@
+use truffle::{Error, Simulator, ty::SqlType};
+
+#[test]
+fn select_row_number_over() {
+    let mut sim = Simulator::default();
+    sim.execute("create table employees (id int primary key, dept text not null, salary int not null, bonus int)")
+        .unwrap();
+
+    let resolve = sim
+        .execute(
+            "select id, row_number() over (partition by dept order by salary) as rn from employees",
+        )
+        .unwrap();
+
+    assert_eq!(resolve.outputs.len(), 2);
+    let rn = resolve.get_output_with_name("rn").unwrap();
+    assert_eq!(rn.ty, SqlType::BigInt);
+    assert!(!rn.nullable);
+}
+
+#[test]
+fn select_rank_and_dense_rank_over() {
+    let mut sim = Simulator::default();
+    sim.execute("create table employees (id int primary key, dept text not null, salary int not null, bonus int)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select rank() over (order by salary) as r, dense_rank() over (order by salary) as dr from employees")
+        .unwrap();
+
+    assert_eq!(
+        resolve.get_output_with_name("r").unwrap().ty,
+        SqlType::BigInt
+    );
+    assert_eq!(
+        resolve.get_output_with_name("dr").unwrap().ty,
+        SqlType::BigInt
+    );
+}
+
+#[test]
+fn select_sum_over_is_row_scoped() {
+    let mut sim = Simulator::default();
+    sim.execute("create table employees (id int primary key, dept text not null, salary int not null, bonus int)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select id, dept, sum(salary) over (partition by dept) as total from employees")
+        .unwrap();
+
+    assert_eq!(resolve.outputs.len(), 3);
+    assert_eq!(
+        resolve.get_output_with_name("total").unwrap().ty,
+        SqlType::Integer
+    );
+}
+
+#[test]
+fn select_count_over_empty_window() {
+    let mut sim = Simulator::default();
+    sim.execute("create table employees (id int primary key, dept text not null, salary int not null, bonus int)")
+        .unwrap();
+
+    let resolve = sim
+        .execute("select id, count(*) over () as total from employees")
+        .unwrap();
+
+    assert_eq!(
+        resolve.get_output_with_name("total").unwrap().ty,
+        SqlType::BigInt
+    );
+}
+
+#[test]
+fn select_window_partition_column_doesnt_exist() {
+    let mut sim = Simulator::default();
+    sim.execute("create table employees (id int primary key, dept text not null, salary int not null, bonus int)")
+        .unwrap();
+
+    assert_eq!(
+        sim.execute("select row_number() over (partition by team) from employees"),
+        Err(Error::ColumnDoesntExist("team".to_string()))
+    );
+}
+
+#[test]
+fn select_window_order_column_doesnt_exist() {
+    let mut sim = Simulator::default();
+    sim.execute("create table employees (id int primary key, dept text not null, salary int not null, bonus int)")
+        .unwrap();
+
+    assert_eq!(
+        sim.execute("select sum(salary) over (order by age) from employees"),
+        Err(Error::ColumnDoesntExist("age".to_string()))
+    );
+}
+
+#[test]
+fn select_row_number_without_over() {
+    let mut sim = Simulator::default();
+    sim.execute("create table employees (id int primary key, dept text not null, salary int not null, bonus int)")
+        .unwrap();
+
+    assert!(matches!(
+        sim.execute("select row_number() from employees"),
+        Err(Error::FunctionCall(_))
+    ));
+}
+
+#[test]
+fn select_row_number_with_arguments() {
+    let mut sim = Simulator::default();
+    sim.execute("create table employees (id int primary key, dept text not null, salary int not null, bonus int)")
+        .unwrap();
+
+    assert_eq!(
+        sim.execute("select row_number(id) over () from employees"),
+        Err(Error::FunctionArgumentCount {
+            expected: 0,
+            got: 1
+        })
+    );
+}
+
+#[test]
+fn select_over_on_non_aggregate() {
+    let mut sim = Simulator::default();
+    sim.execute("create table employees (id int primary key, dept text not null, salary int not null, bonus int)")
+        .unwrap();
+
+    assert!(matches!(
+        sim.execute("select coalesce(bonus, 0) over () from employees"),
+        Err(Error::FunctionCall(_))
+    ));
+}
@@ -1,15 +1,27 @@
-use std::{fs, path::Path};
-use truffle::Config;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+use truffle::{Config, Simulator};
 
-pub fn load_config() -> Result<Config, String> {
+/// Where `truffle prepare` writes the schema cache when the config doesn't name one.
+pub const DEFAULT_CACHE: &str = ".truffle/schema.json";
+
+/// The directory of the crate being compiled.
+pub fn manifest_dir() -> PathBuf {
     let manifest_str =
         std::env::var("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR must be defined");
-    let manifest_dir = Path::new(&manifest_str);
-    let config_file = manifest_dir
-        .join("truffle.toml")
-        .to_str()
-        .unwrap()
-        .to_string();
+
+    PathBuf::from(manifest_str)
+}
+
+pub fn load_config() -> Result<Config, String> {
+    load_config_from(&manifest_dir())
+}
+
+/// Loads the `truffle.toml` in the directory, or the default config if there is none.
+pub fn load_config_from(dir: &Path) -> Result<Config, String> {
+    let config_file = dir.join("truffle.toml").to_str().unwrap().to_string();
 
     let config = if Path::new(&config_file).exists() {
         let config_data = fs::read_to_string(&config_file)
@@ -25,3 +37,13 @@ pub fn load_config() -> Result<Config, String> {
 
     Ok(config)
 }
+
+/// The path of the schema cache, relative to the directory of the config.
+pub fn cache_path(dir: &Path, config: &Config) -> PathBuf {
+    dir.join(config.cache.as_deref().unwrap_or(DEFAULT_CACHE))
+}
+
+/// Creates a Simulator with the dialect, types and functions from the config.
+pub fn load_simulator(config: &Config) -> Result<Simulator, String> {
+    Simulator::with_config(config).map_err(|e| format!("Invalid config: {e}"))
+}
@@ -0,0 +1,261 @@
+use std::collections::{HashMap, HashSet};
+
+use sqlx::{Connection, Row, postgres::PgConnection, sqlite::SqliteConnection};
+use truffle::{Config, DialectKind, Simulator};
+
+use crate::config::load_simulator;
+
+/// Introspects the schema of a live database into a Simulator.
+///
+/// The dialect comes from the scheme of the URL (`postgres://` or `sqlite:`).
+pub fn load_database(url: &str, config: &Config) -> Result<Simulator, String> {
+    let dialect = dialect_from_url(url)?;
+    let config = Config {
+        dialect,
+        ..config.clone()
+    };
+
+    let mut sim = load_simulator(&config)?;
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .map_err(|e| format!("Failed to start runtime: {e}"))?;
+
+    runtime.block_on(async {
+        match dialect {
+            DialectKind::Postgres => introspect_postgres(url, &mut sim).await,
+            _ => introspect_sqlite(url, &mut sim).await,
+        }
+    })?;
+
+    Ok(sim)
+}
+
+fn dialect_from_url(url: &str) -> Result<DialectKind, String> {
+    let scheme = url
+        .split_once(':')
+        .map(|(scheme, _)| scheme)
+        .unwrap_or_default();
+
+    match scheme {
+        "postgres" | "postgresql" => Ok(DialectKind::Postgres),
+        "sqlite" => Ok(DialectKind::Sqlite),
+        _ => Err(format!("Unsupported database URL scheme '{scheme}'")),
+    }
+}
+
+fn execute(sim: &mut Simulator, sql: &str) -> Result<(), String> {
+    sim.execute(sql)
+        .map(|_| ())
+        .map_err(|e| format!("Introspected schema '{sql}': {e}"))
+}
+
+/// SQLite keeps the CREATE TABLE of every table, so they are replayed in creation order.
+async fn introspect_sqlite(url: &str, sim: &mut Simulator) -> Result<(), String> {
+    let mut conn = SqliteConnection::connect(url)
+        .await
+        .map_err(|e| format!("Failed to connect to '{url}': {e}"))?;
+
+    let rows = sqlx::query(
+        "select sql from sqlite_master where type = 'table' and name not like 'sqlite_%' order by rowid",
+    )
+    .fetch_all(&mut conn)
+    .await
+    .map_err(|e| format!("Failed to introspect tables: {e}"))?;
+
+    for row in rows {
+        let sql: String = row.try_get("sql").map_err(|e| e.to_string())?;
+        execute(sim, &sql)?;
+    }
+
+    Ok(())
+}
+
+#[derive(Default)]
+struct PgTable {
+    columns: Vec<String>,
+    constraints: Vec<String>,
+    defaults: Vec<String>,
+    /// Tables that have to exist before this one, because of foreign keys.
+    references: HashSet<(String, String)>,
+}
+
+/// Postgres tables are rebuilt from the catalog as CREATE TABLE statements.
+async fn introspect_postgres(url: &str, sim: &mut Simulator) -> Result<(), String> {
+    let mut conn = PgConnection::connect(url)
+        .await
+        .map_err(|e| format!("Failed to connect to '{url}': {e}"))?;
+
+    let user_schemas =
+        "n.nspname not in ('pg_catalog', 'information_schema') and n.nspname not like 'pg_toast%'";
+
+    let schemas = sqlx::query(&format!(
+        "select n.nspname::text as name from pg_namespace n where {user_schemas}"
+    ))
+    .fetch_all(&mut conn)
+    .await
+    .map_err(|e| format!("Failed to introspect schemas: {e}"))?;
+
+    for row in schemas {
+        let name: String = row.try_get("name").map_err(|e| e.to_string())?;
+
+        if !sim.has_schema(&name) {
+            execute(sim, &format!("create schema \"{name}\""))?;
+        }
+    }
+
+    let enums = sqlx::query(&format!(
+        "select n.nspname::text as schema, t.typname::text as name, \
+        array_agg(e.enumlabel::text order by e.enumsortorder) as labels \
+        from pg_type t join pg_enum e on e.enumtypid = t.oid \
+        join pg_namespace n on t.typnamespace = n.oid \
+        where {user_schemas} group by n.nspname, t.typname"
+    ))
+    .fetch_all(&mut conn)
+    .await
+    .map_err(|e| format!("Failed to introspect enums: {e}"))?;
+
+    for row in enums {
+        let schema: String = row.try_get("schema").map_err(|e| e.to_string())?;
+        let name: String = row.try_get("name").map_err(|e| e.to_string())?;
+        let labels: Vec<String> = row.try_get("labels").map_err(|e| e.to_string())?;
+
+        let labels = labels
+            .iter()
+            .map(|label| format!("'{}'", label.replace('\'', "''")))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        // Named the way `format_type` names the type of its columns.
+        let name = if schema == sim.default_schema {
+            name
+        } else {
+            format!("{schema}.{name}")
+        };
+
+        execute(sim, &format!("create type {name} as enum ({labels})"))?;
+    }
+
+    let mut tables: HashMap<(String, String), PgTable> = HashMap::new();
+
+    let columns = sqlx::query(&format!(
+        "select n.nspname::text as schema, c.relname::text as table_name, a.attname::text as name, \
+        format_type(a.atttypid, a.atttypmod) as ty, a.attnotnull as not_null, \
+        (a.atthasdef or a.attidentity <> '' or a.attgenerated <> '') as has_default \
+        from pg_attribute a join pg_class c on a.attrelid = c.oid \
+        join pg_namespace n on c.relnamespace = n.oid \
+        where c.relkind in ('r', 'p') and a.attnum > 0 and not a.attisdropped and {user_schemas} \
+        order by n.nspname, c.relname, a.attnum"
+    ))
+    .fetch_all(&mut conn)
+    .await
+    .map_err(|e| format!("Failed to introspect columns: {e}"))?;
+
+    for row in columns {
+        let schema: String = row.try_get("schema").map_err(|e| e.to_string())?;
+        let table: String = row.try_get("table_name").map_err(|e| e.to_string())?;
+        let name: String = row.try_get("name").map_err(|e| e.to_string())?;
+        let ty: String = row.try_get("ty").map_err(|e| e.to_string())?;
+        let not_null: bool = row.try_get("not_null").map_err(|e| e.to_string())?;
+        let has_default: bool = row.try_get("has_default").map_err(|e| e.to_string())?;
+
+        let pg_table = tables.entry((schema, table)).or_default();
+        let not_null = if not_null { " not null" } else { "" };
+        pg_table.columns.push(format!("\"{name}\" {ty}{not_null}"));
+
+        // Default expressions (eg. `nextval(...)`) aren't literals, only their existence matters.
+        if has_default {
+            pg_table.defaults.push(name);
+        }
+    }
+
+    let constraints = sqlx::query(&format!(
+        "select n.nspname::text as schema, c.relname::text as table_name, \
+        pg_get_constraintdef(con.oid) as definition, \
+        fns.nspname::text as foreign_schema, fc.relname::text as foreign_table \
+        from pg_constraint con join pg_class c on con.conrelid = c.oid \
+        join pg_namespace n on c.relnamespace = n.oid \
+        left join pg_class fc on con.confrelid = fc.oid \
+        left join pg_namespace fns on fc.relnamespace = fns.oid \
+        where con.contype in ('p', 'u', 'f') and {user_schemas}"
+    ))
+    .fetch_all(&mut conn)
+    .await
+    .map_err(|e| format!("Failed to introspect constraints: {e}"))?;
+
+    for row in constraints {
+        let schema: String = row.try_get("schema").map_err(|e| e.to_string())?;
+        let table: String = row.try_get("table_name").map_err(|e| e.to_string())?;
+        let definition: String = row.try_get("definition").map_err(|e| e.to_string())?;
+        let foreign_schema: Option<String> =
+            row.try_get("foreign_schema").map_err(|e| e.to_string())?;
+        let foreign_table: Option<String> =
+            row.try_get("foreign_table").map_err(|e| e.to_string())?;
+
+        let key = (schema, table);
+        let pg_table = tables.entry(key.clone()).or_default();
+        pg_table.constraints.push(definition);
+
+        if let (Some(foreign_schema), Some(foreign_table)) = (foreign_schema, foreign_table) {
+            let foreign_key = (foreign_schema, foreign_table);
+
+            // A table can reference itself.
+            if foreign_key != key {
+                pg_table.references.insert(foreign_key);
+            }
+        }
+    }
+
+    // Tables are created once every table they reference exists.
+    let mut created: HashSet<(String, String)> = HashSet::new();
+    while created.len() < tables.len() {
+        let mut ready: Vec<_> = tables
+            .iter()
+            .filter(|(key, table)| {
+                !created.contains(*key) && table.references.iter().all(|r| created.contains(r))
+            })
+            .map(|(key, _)| key.clone())
+            .collect();
+
+        if ready.is_empty() {
+            return Err("Foreign keys between the tables are cyclic".to_string());
+        }
+
+        ready.sort();
+
+        for (schema, name) in ready {
+            let table = &tables[&(schema.clone(), name.clone())];
+            let definitions = table
+                .columns
+                .iter()
+                .chain(&table.constraints)
+                .cloned()
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            execute(
+                sim,
+                &format!("create table \"{schema}\".\"{name}\" ({definitions})"),
+            )?;
+
+            let key = if schema == sim.default_schema {
+                name.clone()
+            } else {
+                format!("{schema}.{name}")
+            };
+
+            if let Some(sim_table) = sim.get_tables_mut().get_mut(&key) {
+                for column in &table.defaults {
+                    if let Some(column) = sim_table.columns.get_mut(column) {
+                        column.default = true;
+                    }
+                }
+            }
+
+            created.insert((schema, name));
+        }
+    }
+
+    Ok(())
+}
@@ -1,2 +1,4 @@
 pub mod config;
+#[cfg(feature = "database")]
+pub mod database;
 pub mod migrations;
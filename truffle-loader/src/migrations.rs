@@ -1,26 +1,86 @@
 use std::{
+    collections::HashMap,
     fs,
     path::{Path, PathBuf},
 };
 
 use truffle::{Config, Simulator};
 
+use crate::config::manifest_dir;
+
 pub fn load_migrations(config: &Config) -> Result<Vec<(PathBuf, String)>, String> {
-    let manifest_str = std::env::var("CARGO_MANIFEST_DIR").unwrap();
-    let manifest_dir = Path::new(&manifest_str);
-    let migrations_dir = manifest_dir
-        .join(&config.migrations)
-        .to_str()
-        .unwrap()
-        .to_string();
+    load_migrations_from(&manifest_dir(), config)
+}
+
+/// Loads the migrations (or the schema file) of the config, relative to the directory.
+///
+/// Migrations from every directory are sorted together by their file name.
+pub fn load_migrations_from(dir: &Path, config: &Config) -> Result<Vec<(PathBuf, String)>, String> {
+    if let Some(schema_path) = &config.schema_path {
+        if !config.migrations.is_empty() {
+            return Err(
+                "Both `schema_path` and `migrations` are configured, only one can be used"
+                    .to_string(),
+            );
+        }
+
+        let schema_path = dir.join(schema_path);
+        let content = fs::read_to_string(&schema_path)
+            .map_err(|e| format!("Failed to read Schema file '{schema_path:?}': {e}"))?;
+
+        return Ok(vec![(schema_path, content)]);
+    }
+
+    let mut migration_paths = Vec::new();
+    for pattern in config.migrations_dirs() {
+        for migrations_dir in expand_pattern(dir, pattern)? {
+            migration_paths.extend(load_migration_paths(&migrations_dir)?);
+        }
+    }
+
+    // Migrations will be processed in alphabetical order of their file name.
+    migration_paths.sort_by(|a, b| a.file_name().cmp(&b.file_name()).then(a.cmp(b)));
+    migration_paths.dedup();
+
+    // The same version in two directories makes the order ambiguous.
+    let mut versions: HashMap<String, &Path> = HashMap::new();
+    for path in &migration_paths {
+        let Some(version) = version(path) else {
+            continue;
+        };
+
+        match versions.get(&version) {
+            Some(other) if other.parent() != path.parent() => {
+                return Err(format!(
+                    "Migrations {other:?} and {path:?} have the same version '{version}'"
+                ));
+            }
+            Some(_) => {}
+            None => {
+                versions.insert(version, path);
+            }
+        }
+    }
 
     let mut migration_contents = Vec::new();
+    for migration_path in migration_paths {
+        let content = fs::read_to_string(&migration_path)
+            .map_err(|e| format!("Failed to read Migration file '{migration_path:?}': {e}"))?;
+
+        migration_contents.push((migration_path, content));
+    }
+
+    Ok(migration_contents)
+}
+
+/// The `.sql` files of the directory, if it exists.
+fn load_migration_paths(migrations_dir: &Path) -> Result<Vec<PathBuf>, String> {
+    let mut migration_paths: Vec<_> = Vec::new();
 
-    if Path::new(&migrations_dir).exists() {
-        let entries = fs::read_dir(&migrations_dir)
-            .map_err(|e| format!("Failed to read migrations diretory '{migrations_dir}': {e}"))?;
+    if migrations_dir.exists() {
+        let entries = fs::read_dir(migrations_dir)
+            .map_err(|e| format!("Failed to read migrations diretory '{migrations_dir:?}': {e}"))?;
 
-        let mut migration_paths: Vec<_> = Vec::new();
         for entry in entries {
             let entry =
                 entry.map_err(|e| format!("Failed to read directory entry in migrations: {e}"))?;
@@ -30,19 +90,84 @@ pub fn load_migrations(config: &Config) -> Result<Vec<(PathBuf, String)>, String
                 migration_paths.push(path);
             }
         }
+    }
+
+    Ok(migration_paths)
+}
+
+/// The directories matching the pattern, where `*` in a component matches any name.
+fn expand_pattern(dir: &Path, pattern: &str) -> Result<Vec<PathBuf>, String> {
+    let mut dirs = vec![dir.to_path_buf()];
+
+    for component in Path::new(pattern).components() {
+        let component = component.as_os_str().to_string_lossy();
+
+        if !component.contains('*') {
+            dirs.iter_mut().for_each(|dir| dir.push(component.as_ref()));
+            continue;
+        }
+
+        let mut matched = Vec::new();
+        for dir in dirs.iter().filter(|dir| dir.is_dir()) {
+            let entries = fs::read_dir(dir)
+                .map_err(|e| format!("Failed to read directory '{dir:?}': {e}"))?;
+
+            for entry in entries.flatten() {
+                let path = entry.path();
+
+                if path.is_dir() && wildcard_match(&component, &entry.file_name().to_string_lossy())
+                {
+                    matched.push(path);
+                }
+            }
+        }
+
+        matched.sort();
+        dirs = matched;
+    }
+
+    Ok(dirs)
+}
+
+fn wildcard_match(pattern: &str, name: &str) -> bool {
+    match pattern.split_once('*') {
+        None => pattern == name,
+        Some((prefix, rest)) => {
+            let Some(name) = name.strip_prefix(prefix) else {
+                return false;
+            };
+
+            // Try every split of the name for the rest of the pattern.
+            (0..=name.len())
+                .filter(|i| name.is_char_boundary(*i))
+                .any(|i| wildcard_match(rest, &name[i..]))
+        }
+    }
+}
+
+/// The version prefix of a migration, eg. `0001` of `0001_create_account.sql`.
+fn version(path: &Path) -> Option<String> {
+    let name = path.file_name()?.to_string_lossy();
+    let version: String = name.chars().take_while(|c| c.is_ascii_digit()).collect();
 
-        // Migrations will be processed in alphabetical order.
-        migration_paths.sort();
+    (!version.is_empty()).then_some(version)
+}
+
+/// A hash of the migrations, so that a schema cache can be matched to them.
+pub fn migrations_hash(migrations: &[(PathBuf, String)]) -> String {
+    // FNV-1a, unlike `DefaultHasher` it's the same between Rust versions.
+    let mut hash: u64 = 0xcbf29ce484222325;
 
-        for migration_path in migration_paths {
-            let content = fs::read_to_string(&migration_path)
-                .map_err(|e| format!("Failed to read Migration file '{migration_path:?}': {e}"))?;
+    for (path, content) in migrations {
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
 
-            migration_contents.push((migration_path, content));
+        for byte in name.bytes().chain([0]).chain(content.bytes()).chain([0]) {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(0x100000001b3);
         }
     }
 
-    Ok(migration_contents)
+    format!("{hash:016x}")
 }
 
 pub fn apply_migrations(
@@ -1,8 +1,5 @@
 use sqlx::sqlite::SqlitePool;
-use truffle_sqlx::{
-    convert::{FromSql, IntoSql},
-    dialect::Dialect,
-};
+use truffle_sqlx::SqlEnum;
 
 #[derive(Debug)]
 pub struct Account {
@@ -13,30 +10,13 @@ pub struct Account {
     pub status: AccountStatus,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, SqlEnum)]
 pub enum AccountStatus {
     Active = 1,
     Inactive = 2,
     Deleted = 3,
 }
 
-impl<D: Dialect> IntoSql<i32, D> for AccountStatus {
-    fn into_sql_type(self) -> i32 {
-        self as i32
-    }
-}
-
-impl<D: Dialect> FromSql<i32, D> for AccountStatus {
-    fn from_sql_type(value: i32) -> Self {
-        match value {
-            1 => AccountStatus::Active,
-            2 => AccountStatus::Inactive,
-            3 => AccountStatus::Deleted,
-            _ => unreachable!(),
-        }
-    }
-}
-
 #[tokio::main]
 async fn main() {
     let db = SqlitePool::connect(":memory:").await.unwrap();
@@ -62,7 +42,7 @@ async fn main() {
         .await
         .unwrap();
 
-    let name_status: (String, i32) =
+    let name_status: (String, i64) =
         truffle_sqlx::query_as!("select name, status from account where id = ?", 0)
             .fetch_one(&db)
             .await
@@ -75,7 +55,13 @@ async fn main() {
         .map(|p| p.email.unwrap_or_else(|| "No Email".to_string()))
         .unwrap();
 
+    let account_count: i64 = truffle_sqlx::query_scalar!("select count(*) from account")
+        .fetch_one(&db)
+        .await
+        .unwrap();
+
     println!("Fetched Item: {account:?}");
     println!("Item Pair: {name_status:?}");
     println!("Email: {email:?}");
+    println!("Account Count: {account_count:?}");
 }
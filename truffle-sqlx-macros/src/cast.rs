@@ -0,0 +1,152 @@
+use truffle::{DialectKind, resolve::ResolvedQuery, ty::SqlType};
+
+/// Casts the enums of a query from and to text, which is how they are bound and read.
+///
+/// Postgres doesn't convert text to an enum (or back) on its own,
+/// so enum placeholders are cast to the enum and enum outputs are selected as text.
+pub(crate) fn cast_enums(sql: &str, resolve: &ResolvedQuery, dialect: &DialectKind) -> String {
+    if *dialect != DialectKind::Postgres {
+        return sql.to_string();
+    }
+
+    let mut sql = sql.to_string();
+
+    let mut casts: Vec<_> = resolve
+        .placeholder_locations()
+        .into_iter()
+        .filter_map(|(location, index)| {
+            let cast = enum_cast(&resolve.inputs.get(index)?.ty)?;
+            let start = byte_offset(&sql, location.line, location.column)?;
+
+            // The cast goes right after the placeholder, eg. `$1::status`.
+            let len = sql[start..]
+                .char_indices()
+                .skip(1)
+                .find(|(_, c)| !c.is_alphanumeric() && *c != '_')
+                .map_or(sql.len() - start, |(i, _)| i);
+
+            Some((start + len, cast))
+        })
+        .collect();
+
+    // Inserted from the back, so the earlier offsets stay valid.
+    casts.sort_by_key(|(offset, _)| std::cmp::Reverse(*offset));
+    for (offset, cast) in casts {
+        sql.insert_str(offset, &format!("::{cast}"));
+    }
+
+    if !resolve
+        .outputs
+        .values()
+        .any(|col| enum_cast(&col.ty).is_some())
+    {
+        return sql;
+    }
+
+    // The outputs are selected from the query, so RETURNING and `*` are cast as well.
+    let columns = resolve
+        .outputs
+        .iter()
+        .map(|(name, col)| {
+            let column = quote_ident(&name.name);
+            match &col.ty {
+                SqlType::Array(inner) if matches!(**inner, SqlType::Enum { .. }) => {
+                    format!("{column}::text[] as {column}")
+                }
+                SqlType::Enum { .. } => format!("{column}::text as {column}"),
+                _ => column,
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    // The newline ends a trailing line comment, which would hide the parenthesis.
+    let query = sql.trim_end().trim_end_matches(';');
+    format!("with _truffle_query as ({query}\n) select {columns} from _truffle_query")
+}
+
+/// The type that a placeholder of the type is cast to, if it's an enum (or an array of them).
+fn enum_cast(ty: &SqlType) -> Option<String> {
+    match ty {
+        SqlType::Enum { name, .. } => Some(
+            name.split('.')
+                .map(quote_ident)
+                .collect::<Vec<_>>()
+                .join("."),
+        ),
+        SqlType::Array(inner) => enum_cast(inner).map(|cast| format!("{cast}[]")),
+        _ => None,
+    }
+}
+
+fn quote_ident(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+/// The offset of a line and column of the tokenizer, which counts characters from 1.
+fn byte_offset(sql: &str, line: u64, column: u64) -> Option<usize> {
+    let (mut current_line, mut current_column) = (1, 1);
+
+    for (offset, c) in sql.char_indices() {
+        if (current_line, current_column) == (line, column) {
+            return Some(offset);
+        }
+
+        if c == '\n' {
+            current_line += 1;
+            current_column = 1;
+        } else {
+            current_column += 1;
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use truffle::{DialectKind, Simulator};
+
+    use super::cast_enums;
+
+    fn simulator() -> Simulator {
+        let mut sim = Simulator::with_dialect(DialectKind::Postgres);
+        sim.execute("create type status as enum ('active', 'banned')")
+            .unwrap();
+        sim.execute("create table account (id int primary key, s status not null)")
+            .unwrap();
+        sim
+    }
+
+    #[test]
+    fn cast_enum_bind() {
+        let mut sim = simulator();
+
+        let sql = "insert into account (id, s) values ($1, $2)";
+        let resolve = sim.execute(sql).unwrap();
+        assert_eq!(
+            cast_enums(sql, &resolve, &DialectKind::Postgres),
+            r#"insert into account (id, s) values ($1, $2::"status")"#
+        );
+
+        let sql = "select id from account where s = $1 or s <> $1";
+        let resolve = sim.execute(sql).unwrap();
+        assert_eq!(
+            cast_enums(sql, &resolve, &DialectKind::Postgres),
+            r#"select id from account where s = $1::"status" or s <> $1::"status""#
+        );
+    }
+
+    #[test]
+    fn cast_enum_select() {
+        let mut sim = simulator();
+
+        let sql = "select * from account where id = $1;";
+        let resolve = sim.execute(sql).unwrap();
+        assert_eq!(
+            cast_enums(sql, &resolve, &DialectKind::Postgres),
+            "with _truffle_query as (select * from account where id = $1\n) \
+             select \"id\", \"s\"::text as \"s\" from _truffle_query"
+        );
+    }
+}
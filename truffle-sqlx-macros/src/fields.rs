@@ -0,0 +1,133 @@
+use std::{
+    collections::HashMap,
+    fs::read_to_string,
+    path::{Path, PathBuf},
+    sync::LazyLock,
+};
+
+use syn::{Fields, Item, LitStr, Token};
+use truffle_loader::config::manifest_dir;
+
+/// A named field of a struct and the column that it's read from.
+#[derive(Debug, Clone)]
+pub(crate) struct Field {
+    pub(crate) name: String,
+    pub(crate) column: String,
+}
+
+/// The fields of a struct defined in the source of the crate.
+#[derive(Debug, Clone)]
+pub(crate) struct StructFields {
+    /// The file that defines the struct, so that changes to it are tracked.
+    pub(crate) path: PathBuf,
+    pub(crate) fields: Vec<Field>,
+}
+
+impl StructFields {
+    pub(crate) fn field_for(&self, column: &str) -> Option<&Field> {
+        self.fields.iter().find(|field| field.column == column)
+    }
+}
+
+/// The structs with named fields in the `src` directory of the crate, by name.
+///
+/// A name that is defined more than once maps to `None`, as it can't be told apart.
+static STRUCTS: LazyLock<HashMap<String, Option<StructFields>>> = LazyLock::new(|| {
+    let mut structs = HashMap::new();
+    load_dir(&manifest_dir().join("src"), &mut structs);
+    structs
+});
+
+/// Finds the definition of the struct that the type names.
+///
+/// Only structs defined in the crate itself can be found.
+pub(crate) fn find_struct(ty: &syn::Type) -> Option<&'static StructFields> {
+    let syn::Type::Path(path) = ty else {
+        return None;
+    };
+
+    let name = path.path.segments.last()?.ident.to_string();
+    STRUCTS.get(&name)?.as_ref()
+}
+
+fn load_dir(dir: &Path, structs: &mut HashMap<String, Option<StructFields>>) {
+    let Ok(entries) = dir.read_dir() else {
+        return;
+    };
+
+    for path in entries.filter_map(|entry| entry.ok().map(|entry| entry.path())) {
+        if path.is_dir() {
+            load_dir(&path, structs);
+        } else if path.extension().is_some_and(|ext| ext == "rs") {
+            // Files that don't parse (eg. while being edited) are skipped.
+            let Some(file) = read_to_string(&path)
+                .ok()
+                .and_then(|source| syn::parse_file(&source).ok())
+            else {
+                continue;
+            };
+
+            load_items(&path, &file.items, structs);
+        }
+    }
+}
+
+fn load_items(path: &Path, items: &[Item], structs: &mut HashMap<String, Option<StructFields>>) {
+    for item in items {
+        match item {
+            Item::Struct(item) => {
+                let Fields::Named(named) = &item.fields else {
+                    continue;
+                };
+
+                let fields = named
+                    .named
+                    .iter()
+                    .filter_map(|field| {
+                        let name = field.ident.as_ref()?.to_string();
+                        let column = rename(field)
+                            .unwrap_or_else(|| name.trim_start_matches("r#").to_string());
+
+                        Some(Field { name, column })
+                    })
+                    .collect();
+
+                let fields = StructFields {
+                    path: path.to_path_buf(),
+                    fields,
+                };
+
+                structs
+                    .entry(item.ident.to_string())
+                    .and_modify(|existing| *existing = None)
+                    .or_insert(Some(fields));
+            }
+            Item::Mod(item) => {
+                if let Some((_, items)) = &item.content {
+                    load_items(path, items, structs);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// The column of a field renamed with `#[sqlx(rename = "...")]`.
+fn rename(field: &syn::Field) -> Option<String> {
+    let mut column = None;
+
+    for attr in field.attrs.iter().filter(|a| a.path().is_ident("sqlx")) {
+        // Other sqlx attributes are skipped over.
+        let _ = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename") {
+                column = Some(meta.value()?.parse::<LitStr>()?.value());
+            } else if meta.input.peek(Token![=]) {
+                meta.value()?.parse::<syn::Expr>()?;
+            }
+
+            Ok(())
+        });
+    }
+
+    column
+}
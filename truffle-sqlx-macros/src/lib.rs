@@ -1,24 +1,51 @@
+mod cast;
+mod fields;
+mod sql_enum;
+
+use cast::cast_enums;
+use fields::{StructFields, find_struct};
 use proc_macro2::{Span, TokenStream};
 use quote::quote;
-use std::{
-    hash::{DefaultHasher, Hash, Hasher},
-    sync::LazyLock,
-};
+use std::{fs::read_to_string, sync::LazyLock};
 use syn::{
     Error, Token,
     parse::{Parse, discouraged::Speculative},
     parse_quote,
+    spanned::Spanned,
 };
-use truffle::{DialectKind, Simulator, ty::SqlType};
+use truffle::{DialectKind, Simulator, resolve::ResolvedQuery, ty::SqlType};
 use truffle_loader::{
-    config::load_config,
+    config::{load_config, load_simulator, manifest_dir},
     migrations::{apply_migrations, load_migrations},
 };
 
 static SIMULATOR: LazyLock<Result<Simulator, String>> = LazyLock::new(|| {
     let config = load_config().map_err(|e| e.to_string())?;
-    let mut sim = Simulator::with_dialect(config.dialect);
+
+    // Like the sqlx macros, a live database is used when there is one.
+    #[cfg(feature = "database")]
+    if let Ok(url) = std::env::var("DATABASE_URL") {
+        return truffle_loader::database::load_database(&url, &config);
+    }
+
     let migrations = load_migrations(&config).map_err(|e| e.to_string())?;
+
+    // Prefer the prebuilt schema (eg. from `truffle prepare`),
+    // falling back to the migrations if it's missing, invalid or stale.
+    #[cfg(feature = "serde")]
+    {
+        use truffle_loader::{config::cache_path, migrations::migrations_hash};
+
+        let cache_path = cache_path(&manifest_dir(), &config);
+        let hash = migrations_hash(&migrations);
+
+        if let Ok(mut sim) = Simulator::from_cache_with_hash(cache_path, config.dialect, &hash) {
+            sim.apply_config(&config).map_err(|e| e.to_string())?;
+            return Ok(sim);
+        }
+    }
+
+    let mut sim = load_simulator(&config)?;
     apply_migrations(&mut sim, &migrations).map_err(|e| e.to_string())?;
 
     Ok(sim)
@@ -116,11 +143,14 @@ fn sql_type_to_rust_type(sql_type: &SqlType, dialect: &DialectKind) -> syn::Type
         SqlType::BigInt => parse_quote!(i64),
         SqlType::Float => parse_quote!(f32),
         SqlType::Double => parse_quote!(f64),
-        SqlType::Text => parse_quote!(String),
+        SqlType::Text | SqlType::Varchar { .. } | SqlType::Char { .. } => parse_quote!(String),
+        // Enum labels are bound and read as text, with casts added to the SQL.
+        SqlType::Enum { .. } => parse_quote!(String),
         SqlType::Boolean => match dialect {
             DialectKind::Generic | DialectKind::Ansi | DialectKind::Postgres => parse_quote!(bool),
             DialectKind::Sqlite => parse_quote!(i32),
         },
+        SqlType::Bytes => parse_quote!(Vec<u8>),
         #[cfg(feature = "time")]
         SqlType::Date => parse_quote!(time::Date),
         #[cfg(feature = "time")]
@@ -129,14 +159,49 @@ fn sql_type_to_rust_type(sql_type: &SqlType, dialect: &DialectKind) -> syn::Type
         SqlType::Timestamp => parse_quote!(time::PrimitiveDateTime),
         #[cfg(feature = "time")]
         SqlType::TimestampTz => parse_quote!(time::OffsetDateTime),
+        #[cfg(feature = "time")]
+        SqlType::Interval => match dialect {
+            DialectKind::Postgres => parse_quote!(sqlx::postgres::types::PgInterval),
+            _ => parse_quote!(String),
+        },
         #[cfg(feature = "uuid")]
         SqlType::Uuid => parse_quote!(uuid::Uuid),
         #[cfg(feature = "json")]
-        SqlType::Json => parse_quote!(serde_json::Value),
+        SqlType::Json | SqlType::Jsonb => parse_quote!(serde_json::Value),
+        // BigDecimal takes precedence when both features are enabled.
+        #[cfg(all(feature = "decimal", not(feature = "bigdecimal")))]
+        SqlType::Decimal { .. } => match dialect {
+            DialectKind::Sqlite => parse_quote!(String),
+            _ => parse_quote!(rust_decimal::Decimal),
+        },
+        #[cfg(feature = "bigdecimal")]
+        SqlType::Decimal { .. } => match dialect {
+            DialectKind::Sqlite => parse_quote!(String),
+            _ => parse_quote!(bigdecimal::BigDecimal),
+        },
+        #[cfg(feature = "ipnetwork")]
+        SqlType::Inet | SqlType::Cidr => parse_quote!(ipnetwork::IpNetwork),
+        SqlType::Array(inner) if *dialect == DialectKind::Postgres => {
+            let inner = sql_type_to_rust_type(inner, dialect);
+            parse_quote!(Vec<#inner>)
+        }
         _ => panic!("Unsupported Type: {sql_type:?}"),
     }
 }
 
+/// The type that a placeholder of the SQL type is bound as.
+///
+/// Strings are bound as a `Cow` so that borrowed strings don't have to be copied.
+fn sql_type_to_input_type(sql_type: &SqlType, dialect: &DialectKind) -> syn::Type {
+    let storage_type = sql_type_to_rust_type(sql_type, dialect);
+
+    if storage_type == parse_quote!(String) {
+        parse_quote!(std::borrow::Cow<'_, str>)
+    } else {
+        storage_type
+    }
+}
+
 fn sql_type_into(
     name: &syn::Ident,
     sql_type: &SqlType,
@@ -144,7 +209,7 @@ fn sql_type_into(
     expr: &syn::Expr,
     dialect: &DialectKind,
 ) -> TokenStream {
-    let storage_type = sql_type_to_rust_type(sql_type, dialect);
+    let storage_type = sql_type_to_input_type(sql_type, dialect);
     let dialect_type: syn::Type = match dialect {
         DialectKind::Sqlite => parse_quote!(truffle_sqlx::dialect::SqliteDialect),
         DialectKind::Postgres => parse_quote!(truffle_sqlx::dialect::PostgreSqlDialect),
@@ -176,9 +241,12 @@ fn sql_type_from(
     };
 
     if nullable {
+        // Goes through `NullableField` so a non-`Option` field errors about the nullability.
         quote! {
-            row.try_get::<Option<#storage_type>, _>(#field_name)?
-                .map(|v| <_ as truffle_sqlx::convert::FromSql<#storage_type, #dialect_type>>::from_sql_type(v))
+            <_ as truffle_sqlx::convert::NullableField>::from_option(
+                row.try_get::<Option<#storage_type>, _>(#field_name)?
+                    .map(|v| <_ as truffle_sqlx::convert::FromSql<#storage_type, #dialect_type>>::from_sql_type(v))
+            )
         }
     } else {
         quote! {
@@ -189,10 +257,157 @@ fn sql_type_from(
     }
 }
 
+/// The field that an output column is read into, named exactly like the column.
+///
+/// Keywords become raw identifiers (eg. `r#type`), other columns have to be renamed with `as`.
+fn column_ident(name: &str, span: Span, sql_lit: &syn::LitStr) -> syn::Result<syn::Ident> {
+    let mut ident = syn::parse_str::<syn::Ident>(name)
+        .or_else(|_| syn::parse_str::<syn::Ident>(&format!("r#{name}")))
+        .map_err(|_| {
+            Error::new(
+                sql_lit.span(),
+                format!(
+                    "Column '{name}' can't be a field name, rename it with `as` (eg. `as {}`)",
+                    name.to_lowercase()
+                        .replace(|c: char| !c.is_alphanumeric(), "_")
+                        .trim_matches('_')
+                ),
+            )
+        })?;
+
+    ident.set_span(span);
+    Ok(ident)
+}
+
+/// Checks that every column of the query has a field in the struct, and every field a column.
+fn check_fields(target: &StructFields, resolve: &ResolvedQuery, ty: &syn::Type) -> syn::Result<()> {
+    let columns: Vec<_> = resolve
+        .outputs
+        .keys()
+        .map(|name| name.name.as_str())
+        .filter(|column| target.field_for(column).is_none())
+        .collect();
+
+    let fields: Vec<_> = target
+        .fields
+        .iter()
+        .filter(|field| !resolve.outputs.keys().any(|name| name.name == field.column))
+        .map(|field| field.name.as_str())
+        .collect();
+
+    let mut mismatches = vec![];
+    if !columns.is_empty() {
+        mismatches.push(format!("columns without a field: {}", columns.join(", ")));
+    }
+    if !fields.is_empty() {
+        mismatches.push(format!("fields without a column: {}", fields.join(", ")));
+    }
+
+    if mismatches.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::new_spanned(
+            ty,
+            format!(
+                "The columns of the query don't match the fields of the struct ({})",
+                mismatches.join("; ")
+            ),
+        ))
+    }
+}
+
+/// Replaces the path literal with the SQL of the file, keeping the span of the path.
+///
+/// Returns the absolute path of the file, so that changes to it are tracked.
+fn read_query_file(sql_lit: &mut syn::LitStr) -> syn::Result<String> {
+    let path = manifest_dir().join(sql_lit.value());
+
+    let sql = read_to_string(&path).map_err(|e| {
+        Error::new(
+            sql_lit.span(),
+            format!("Failed to read '{}': {e}", path.display()),
+        )
+    })?;
+
+    *sql_lit = syn::LitStr::new(&sql, sql_lit.span());
+
+    Ok(path.display().to_string())
+}
+
+/// Wraps the expansion so the file is a dependency of the crate and recompiles it when changed.
+fn with_tracked_file(path: String, expanded: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let expanded = TokenStream::from(expanded);
+
+    quote! {
+        {
+            const _: &str = include_str!(#path);
+            #expanded
+        }
+    }
+    .into()
+}
+
+/// Checks that there is an argument for every input of the query.
+///
+/// Inputs are counted by distinct placeholder, so a reused `$1` takes a single argument.
+/// The error points at the first extra argument, or the SQL when arguments are missing.
+fn check_placeholders(
+    resolve: &ResolvedQuery,
+    sql_lit: &syn::LitStr,
+    arguments: &[syn::Expr],
+) -> syn::Result<()> {
+    let expected = resolve.inputs.len();
+
+    if let Some(extra) = arguments.get(expected) {
+        return Err(Error::new_spanned(
+            extra,
+            format!(
+                "Unexpected argument, the query has {expected} placeholders but got {} arguments",
+                arguments.len()
+            ),
+        ));
+    }
+
+    if arguments.len() < expected {
+        let missing = resolve.placeholders[arguments.len()..]
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        return Err(Error::new(
+            sql_lit.span(),
+            format!(
+                "Expected {expected} placeholders but got {} arguments, missing {missing}",
+                arguments.len()
+            ),
+        ));
+    }
+
+    Ok(())
+}
+
 // Validates the syntax and semantics of your SQL at compile time.
 #[proc_macro]
 pub fn query(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let parsed = syn::parse_macro_input!(input as QueryInput);
+    expand_query(parsed)
+}
+
+// Like `query!`, with the SQL read from a file relative to the crate root.
+#[proc_macro]
+pub fn query_file(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let mut parsed = syn::parse_macro_input!(input as QueryInput);
+
+    let tracked = match read_query_file(&mut parsed.sql_lit) {
+        Ok(tracked) => tracked,
+        Err(e) => return e.to_compile_error().into(),
+    };
+
+    with_tracked_file(tracked, expand_query(parsed))
+}
+
+fn expand_query(parsed: QueryInput) -> proc_macro::TokenStream {
     let sql = parsed.sql_lit.value();
 
     let mut sim = match get_simulator() {
@@ -210,15 +425,15 @@ pub fn query(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     };
 
     // Ensure that we have matched all of the placeholders.
-    // TODO: we only really only care if they are different as multiple `$1` is 1.
-    if resolve.inputs.len() != parsed.placeholders.len() {
+    if let Err(e) = check_placeholders(&resolve, &parsed.sql_lit, &parsed.placeholders) {
+        return e.to_compile_error().into();
+    }
+
+    // query! doesn't expose the rows, so RETURNING would be silently dropped.
+    if resolve.mutates && !resolve.outputs.is_empty() {
         return Error::new(
             parsed.sql_lit.span(),
-            format!(
-                "Expected {} placeholders but got {}",
-                resolve.inputs.len(),
-                parsed.placeholders.len()
-            ),
+            "RETURNING rows are discarded by query!, use query_as! instead",
         )
         .to_compile_error()
         .into();
@@ -244,6 +459,7 @@ pub fn query(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
         .collect::<Vec<_>>();
 
     let (conversions, binding_names): (Vec<_>, Vec<_>) = bindings.into_iter().unzip();
+    let sql = cast_enums(&sql, &resolve, &sim.dialect.kind());
 
     quote! {
         {
@@ -255,9 +471,30 @@ pub fn query(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
 }
 
 // Validates the syntax and semantics of your SQL at compile time.
+//
+// Each output column is read into the field of the same name, or the field renamed to it
+// with `#[sqlx(rename = "...")]`. Renames are read from the definition of the struct,
+// so they are only known for structs defined in the crate itself.
 #[proc_macro]
 pub fn query_as(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let parsed = syn::parse_macro_input!(input as QueryAsInput);
+    expand_query_as(parsed)
+}
+
+// Like `query_as!`, with the SQL read from a file relative to the crate root.
+#[proc_macro]
+pub fn query_file_as(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let mut parsed = syn::parse_macro_input!(input as QueryAsInput);
+
+    let tracked = match read_query_file(&mut parsed.sql_lit) {
+        Ok(tracked) => tracked,
+        Err(e) => return e.to_compile_error().into(),
+    };
+
+    with_tracked_file(tracked, expand_query_as(parsed))
+}
+
+fn expand_query_as(parsed: QueryAsInput) -> proc_macro::TokenStream {
     let sql = parsed.sql_lit.value();
 
     let mut sim = match get_simulator() {
@@ -275,14 +512,15 @@ pub fn query_as(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     };
 
     // Ensure that we have matched all of the placeholders.
-    if resolve.inputs.len() != parsed.placeholders.len() {
+    if let Err(e) = check_placeholders(&resolve, &parsed.sql_lit, &parsed.placeholders) {
+        return e.to_compile_error().into();
+    }
+
+    // A write without RETURNING has no rows to build the result from.
+    if resolve.mutates && resolve.outputs.is_empty() {
         return Error::new(
             parsed.sql_lit.span(),
-            format!(
-                "Expected {} placeholders but got {}",
-                resolve.inputs.len(),
-                parsed.placeholders.len()
-            ),
+            "query_as! requires a query that returns rows; use query! for this statement",
         )
         .to_compile_error()
         .into();
@@ -308,23 +546,55 @@ pub fn query_as(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
         .collect::<Vec<_>>();
 
     let (conversions, binding_names): (Vec<_>, Vec<_>) = bindings.into_iter().unzip();
+    let sql = cast_enums(&sql, &resolve, &sim.dialect.kind());
 
     if let Some(ty) = parsed.ty {
-        let fields: Vec<_> = resolve
+        let target = find_struct(&ty);
+
+        if let Some(target) = target
+            && let Err(e) = check_fields(target, &resolve, &ty)
+        {
+            return e.to_compile_error().into();
+        }
+
+        // The fields are spanned to the type, so missing or unknown fields point at it.
+        let fields = resolve
             .outputs
             .iter()
             .map(|(name, col)| {
-                let field_name = &name.name;
-                let field_ident = syn::Ident::new(field_name, Span::call_site());
+                let column_name = &name.name;
+                let field_ident = match target.and_then(|target| target.field_for(column_name)) {
+                    Some(field) => {
+                        let mut ident = syn::parse_str::<syn::Ident>(&field.name)?;
+                        ident.set_span(ty.span());
+                        ident
+                    }
+                    None => column_ident(column_name, ty.span(), &parsed.sql_lit)?,
+                };
 
                 let conversion =
-                    sql_type_from(field_name, &col.ty, col.nullable, &sim.dialect.kind());
+                    sql_type_from(column_name, &col.ty, col.nullable, &sim.dialect.kind());
 
-                quote! {
-                    #field_ident: #conversion,
-                }
+                // Option fields are only caught once the struct is built and their type is known.
+                let check = (!col.nullable).then(|| {
+                    quote! {
+                        truffle_sqlx::convert::NotNullCheck(&result.#field_ident).check();
+                    }
+                });
+
+                Ok((
+                    quote! {
+                        #field_ident: #conversion,
+                    },
+                    check,
+                ))
             })
-            .collect();
+            .collect::<syn::Result<Vec<_>>>();
+
+        let (fields, checks): (Vec<_>, Vec<_>) = match fields {
+            Ok(fields) => fields.into_iter().unzip(),
+            Err(e) => return e.to_compile_error().into(),
+        };
 
         let row_type: syn::Type = match sim.dialect.kind() {
             DialectKind::Generic | DialectKind::Ansi => {
@@ -338,42 +608,57 @@ pub fn query_as(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
         };
 
         // Run your SQL.
-        quote! {
+        let expanded: proc_macro::TokenStream = quote! {
             {
                 #(#conversions)*
                 sqlx::query(#sql)#(.bind(#binding_names))*.try_map(|row: #row_type| {
                     use sqlx::Row as _;
-                    Ok(#ty { #(#fields)* })
+                    use truffle_sqlx::convert::NotNullFallback as _;
+
+                    let result = #ty { #(#fields)* };
+                    #(#checks)*
+                    Ok(result)
                 })
             }
         }
-        .into()
+        .into();
+
+        // The renames are read from the struct, so it's expanded again when they change.
+        match target {
+            Some(target) => with_tracked_file(target.path.display().to_string(), expanded),
+            None => expanded,
+        }
     } else {
-        let result_fields: Vec<_> = resolve
+        let result_fields = resolve
             .outputs
             .iter()
             .map(|(name, col)| {
                 let true_type = sql_type_to_rust_type(&col.ty, &sim.dialect.kind());
-                let field_name = syn::Ident::new(&name.name, Span::call_site());
+                let field_name = column_ident(&name.name, Span::call_site(), &parsed.sql_lit)?;
 
                 if col.nullable {
-                    quote! {
+                    Ok(quote! {
                         pub #field_name: Option<#true_type>,
-                    }
+                    })
                 } else {
-                    quote! {
+                    Ok(quote! {
                         pub #field_name: #true_type,
-                    }
+                    })
                 }
             })
-            .collect();
+            .collect::<syn::Result<Vec<_>>>();
 
-        let mut hasher = DefaultHasher::new();
-        sql.hash(&mut hasher);
-        let hashed = hasher.finish();
+        let result_fields = match result_fields {
+            Ok(result_fields) => result_fields,
+            Err(e) => return e.to_compile_error().into(),
+        };
 
-        let result_struct_name =
-            syn::Ident::new(&format!("QueryResult_{hashed}"), Span::call_site());
+        // Named after the call site, the block scope keeps it from clashing with other calls.
+        let call_site = proc_macro::Span::call_site();
+        let result_struct_name = syn::Ident::new(
+            &format!("QueryResult_L{}_C{}", call_site.line(), call_site.column()),
+            Span::call_site(),
+        );
 
         // Run your SQL.
         quote! {
@@ -391,29 +676,135 @@ pub fn query_as(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     }
 }
 
-// #[proc_macro]
-// pub fn query_scalar(input: TokenStream) -> TokenStream {
-//     let parsed = syn::parse_macro_input!(input as QueryInput);
-//     let sql = parsed.sql_lit.value();
-
-//     let mut sim = match SIMULATOR.as_ref() {
-//         Ok(simulator) => simulator.clone(),
-//         Err(e) => return e.to_compile_error().into(),
-//     };
-
-//     if let Err(e) = sim.execute(&sql) {
-//         return Error::new(parsed.sql_lit.span(), e.to_string())
-//             .to_compile_error()
-//             .into();
-//     }
-
-//     // Run your SQL.
-//     match parsed.ty {
-//         Some(ty) => TokenStream::from(quote! {
-//             sqlx::query_scalar::<_, #ty>(#sql)
-//         }),
-//         None => TokenStream::from(quote! {
-//             sqlx::query_scalar(#sql)
-//         }),
-//     }
-// }
+// Validates the syntax and semantics of your SQL at compile time.
+#[proc_macro]
+pub fn query_scalar(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let parsed = syn::parse_macro_input!(input as QueryInput);
+    let sql = parsed.sql_lit.value();
+
+    let mut sim = match get_simulator() {
+        Ok(sim) => sim,
+        Err(tokens) => return tokens,
+    };
+
+    let resolve = match sim.execute(&sql) {
+        Ok(resolve) => resolve,
+        Err(e) => {
+            return Error::new(parsed.sql_lit.span(), e.to_string())
+                .to_compile_error()
+                .into();
+        }
+    };
+
+    // Ensure that we have matched all of the placeholders.
+    if let Err(e) = check_placeholders(&resolve, &parsed.sql_lit, &parsed.placeholders) {
+        return e.to_compile_error().into();
+    }
+
+    // A scalar query must return exactly one column.
+    if resolve.outputs.len() != 1 {
+        return Error::new(
+            parsed.sql_lit.span(),
+            format!(
+                "Expected exactly 1 output column but got {}",
+                resolve.outputs.len()
+            ),
+        )
+        .to_compile_error()
+        .into();
+    }
+
+    let bindings: Vec<_> = resolve
+        .inputs
+        .iter()
+        .zip(parsed.placeholders.iter())
+        .enumerate()
+        .map(|(i, (column, rust_expr))| {
+            let binding = syn::Ident::new(&format!("_arg_{i}"), Span::call_site());
+            let conversion = sql_type_into(
+                &binding,
+                &column.ty,
+                column.nullable,
+                rust_expr,
+                &sim.dialect.kind(),
+            );
+
+            (conversion, binding)
+        })
+        .collect::<Vec<_>>();
+
+    let (conversions, binding_names): (Vec<_>, Vec<_>) = bindings.into_iter().unzip();
+    let sql = cast_enums(&sql, &resolve, &sim.dialect.kind());
+
+    let (_, column) = resolve.outputs.get_index(0).unwrap();
+    let true_type = sql_type_to_rust_type(&column.ty, &sim.dialect.kind());
+    let scalar_type: syn::Type = if column.nullable {
+        parse_quote!(Option<#true_type>)
+    } else {
+        true_type
+    };
+
+    // Run your SQL.
+    quote! {
+        {
+            #(#conversions)*
+            sqlx::query_scalar::<_, #scalar_type>(#sql)#(.bind(#binding_names))*
+        }
+    }
+    .into()
+}
+
+// Runs your SQL without validating it, for queries that truffle can't analyze yet.
+//
+// The arguments are bound as they are, without any conversions.
+#[proc_macro]
+pub fn query_unchecked(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let parsed = syn::parse_macro_input!(input as QueryInput);
+    let sql = &parsed.sql_lit;
+    let placeholders = &parsed.placeholders;
+
+    quote! {
+        sqlx::query(#sql)#(.bind(#placeholders))*
+    }
+    .into()
+}
+
+// Runs your SQL without validating it, for queries that truffle can't analyze yet.
+//
+// The result type must implement `sqlx::FromRow`, as the columns aren't known.
+#[proc_macro]
+pub fn query_as_unchecked(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let parsed = syn::parse_macro_input!(input as QueryAsInput);
+    let sql = &parsed.sql_lit;
+    let placeholders = &parsed.placeholders;
+
+    let Some(ty) = &parsed.ty else {
+        return Error::new(
+            sql.span(),
+            "query_as_unchecked! requires a result type, eg. query_as_unchecked!(Account, ...)",
+        )
+        .to_compile_error()
+        .into();
+    };
+
+    quote! {
+        sqlx::query_as::<_, #ty>(#sql)#(.bind(#placeholders))*
+    }
+    .into()
+}
+
+/// Implements `IntoSql` and `FromSql` for a C-like enum, for every dialect.
+///
+/// By default the enum is stored as its discriminant (in every integer type that fits).
+/// With `#[sql(text)]` it is stored as the name of the variant,
+/// which can be changed with `#[sql(rename = "...")]`.
+///
+/// Converting a value that isn't one of the variants panics.
+#[proc_macro_derive(SqlEnum, attributes(sql))]
+pub fn derive_sql_enum(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = syn::parse_macro_input!(input as syn::DeriveInput);
+
+    sql_enum::derive_sql_enum(input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
@@ -0,0 +1,159 @@
+use proc_macro2::{Literal, TokenStream};
+use quote::quote;
+use syn::{Data, DeriveInput, Error, Expr, ExprLit, ExprUnary, Fields, Lit, LitStr, UnOp};
+
+pub(crate) fn derive_sql_enum(input: DeriveInput) -> syn::Result<TokenStream> {
+    let Data::Enum(data) = &input.data else {
+        return Err(Error::new_spanned(
+            &input,
+            "SqlEnum can only be derived for enums",
+        ));
+    };
+
+    if !input.generics.params.is_empty() {
+        return Err(Error::new_spanned(
+            &input.generics,
+            "SqlEnum can't be derived for generic enums",
+        ));
+    }
+
+    let mut text = false;
+    for attr in input.attrs.iter().filter(|a| a.path().is_ident("sql")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("text") {
+                text = true;
+                Ok(())
+            } else {
+                Err(meta.error("expected `text`"))
+            }
+        })?;
+    }
+
+    let mut variants = Vec::new();
+    let mut names = Vec::new();
+    let mut discriminants = Vec::new();
+    let mut next = 0i64;
+
+    for variant in &data.variants {
+        if !matches!(variant.fields, Fields::Unit) {
+            return Err(Error::new_spanned(
+                variant,
+                "SqlEnum variants can't have fields",
+            ));
+        }
+
+        let mut name = variant.ident.to_string();
+        for attr in variant.attrs.iter().filter(|a| a.path().is_ident("sql")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") {
+                    name = meta.value()?.parse::<LitStr>()?.value();
+                    Ok(())
+                } else {
+                    Err(meta.error("expected `rename = \"...\"`"))
+                }
+            })?;
+        }
+
+        // Variants without a discriminant follow the previous one, like in Rust.
+        let discriminant = match &variant.discriminant {
+            Some((_, expr)) => integer_literal(expr)?,
+            None => next,
+        };
+        next = discriminant.wrapping_add(1);
+
+        variants.push(&variant.ident);
+        names.push(name);
+        discriminants.push(discriminant);
+    }
+
+    let ident = &input.ident;
+
+    let impls = if text {
+        let into: Vec<_> = names.iter().map(|n| quote!(#n.to_string())).collect();
+        let from: Vec<_> = names.iter().map(|n| quote!(#n)).collect();
+
+        vec![conversions(
+            ident,
+            quote!(String),
+            quote!(value.as_str()),
+            &variants,
+            &into,
+            &from,
+        )]
+    } else {
+        let values: Vec<_> = discriminants
+            .iter()
+            .map(|d| {
+                let value = Literal::i64_unsuffixed(*d);
+                quote!(#value)
+            })
+            .collect();
+
+        // Every integer type that can hold all of the discriminants.
+        [
+            (quote!(i16), i16::MIN as i64, i16::MAX as i64),
+            (quote!(i32), i32::MIN as i64, i32::MAX as i64),
+            (quote!(i64), i64::MIN, i64::MAX),
+        ]
+        .into_iter()
+        .filter(|(_, min, max)| discriminants.iter().all(|d| (*min..=*max).contains(d)))
+        .map(|(storage, _, _)| {
+            conversions(ident, storage, quote!(value), &variants, &values, &values)
+        })
+        .collect()
+    };
+
+    Ok(quote! { #(#impls)* })
+}
+
+/// Builds `IntoSql` and `FromSql` of the storage type, mapping each variant to its value.
+fn conversions(
+    ident: &syn::Ident,
+    storage: TokenStream,
+    matched: TokenStream,
+    variants: &[&syn::Ident],
+    into: &[TokenStream],
+    from: &[TokenStream],
+) -> TokenStream {
+    let unknown = format!("Unknown value for {ident}: {{}}");
+
+    quote! {
+        impl<D: truffle_sqlx::dialect::Dialect> truffle_sqlx::convert::IntoSql<#storage, D> for #ident {
+            fn into_sql_type(self) -> #storage {
+                match self {
+                    #(#ident::#variants => #into,)*
+                }
+            }
+        }
+
+        impl<D: truffle_sqlx::dialect::Dialect> truffle_sqlx::convert::FromSql<#storage, D> for #ident {
+            /// # Panics
+            ///
+            /// If the value isn't one of the variants.
+            fn from_sql_type(value: #storage) -> Self {
+                match #matched {
+                    #(#from => #ident::#variants,)*
+                    _ => panic!(#unknown, value),
+                }
+            }
+        }
+    }
+}
+
+/// The value of a discriminant, which has to be an integer literal (eg. `1` or `-1`).
+fn integer_literal(expr: &Expr) -> syn::Result<i64> {
+    match expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Int(int), ..
+        }) => int.base10_parse(),
+        Expr::Unary(ExprUnary {
+            op: UnOp::Neg(_),
+            expr,
+            ..
+        }) => integer_literal(expr).map(|value| -value),
+        _ => Err(Error::new_spanned(
+            expr,
+            "SqlEnum discriminants must be integer literals",
+        )),
+    }
+}
@@ -1,16 +1,117 @@
 use truffle::dialect::Dialect;
 
-// pub mod postgres;
+pub mod postgres;
 pub mod sqlite;
 
+/// Converts a bound Rust value into the storage type `T` of the dialect.
+///
+/// Every placeholder bound by the checked macros (`query!`, `query_as!`, ...) goes through
+/// this trait, so implementing it for your own types (eg. an enum stored as an integer)
+/// lets them be bound. The unchecked macros bind their arguments as they are.
 pub trait IntoSql<T, D: Dialect> {
     fn into_sql_type(self) -> T;
 }
 
+/// Converts the storage type `T` of the dialect into a Rust value.
+///
+/// Used for the fields of a named struct in `query_as!`.
+#[diagnostic::on_unimplemented(message = "`{Self}` can't be read from a column stored as `{T}`")]
 pub trait FromSql<T, D: Dialect> {
     fn from_sql_type(value: T) -> Self;
 }
 
+/// An `Option` field that a NOT NULL column is read into, which is never implemented.
+///
+/// Required by [`NotNullCheck::check`] for `Option` fields, so that reading a NOT NULL column
+/// into an `Option` field errors about the nullability.
+#[diagnostic::on_unimplemented(
+    message = "a NOT NULL column can't be read into a `{Self}` field",
+    label = "the column is NOT NULL",
+    note = "remove the `Option` from the field or make the column nullable"
+)]
+pub trait NotNullField {}
+
+/// A field of a named struct in `query_as!` that a NOT NULL column was read into.
+#[doc(hidden)]
+pub struct NotNullCheck<'a, T>(pub &'a T);
+
+impl<T> NotNullCheck<'_, Option<T>> {
+    /// Takes precedence over [`NotNullFallback::check`] for `Option` fields.
+    pub fn check(&self)
+    where
+        Option<T>: NotNullField,
+    {
+    }
+}
+
+/// Accepts any field that isn't an `Option`.
+#[doc(hidden)]
+pub trait NotNullFallback {
+    fn check(&self) {}
+}
+
+impl<T> NotNullFallback for NotNullCheck<'_, T> {}
+
+impl<T, D: Dialect, F: FromSql<T, D>> FromSql<T, D> for Option<F> {
+    fn from_sql_type(value: T) -> Self {
+        Some(F::from_sql_type(value))
+    }
+}
+
+/// A field that a nullable column can be read into, which is only an `Option`.
+///
+/// Used for the fields of a named struct in `query_as!`.
+#[diagnostic::on_unimplemented(
+    message = "a nullable column can't be read into a `{Self}` field",
+    label = "the column can be NULL",
+    note = "make the field an `Option` or make the column NOT NULL"
+)]
+pub trait NullableField: Sized {
+    type Inner;
+
+    fn from_option(value: Option<Self::Inner>) -> Self;
+}
+
+impl<T> NullableField for Option<T> {
+    type Inner = T;
+
+    fn from_option(value: Option<T>) -> Self {
+        value
+    }
+}
+
+/// Strings are bound as a `Cow`, so borrowed strings are bound without a copy.
+///
+/// Any other type is bound through its `IntoSql<String, D>` conversion.
+#[macro_export]
+macro_rules! impl_cow_compat {
+    ($d:ty) => {
+        impl<'a> IntoSql<std::borrow::Cow<'a, str>, $d> for &'a str {
+            fn into_sql_type(self) -> std::borrow::Cow<'a, str> {
+                std::borrow::Cow::Borrowed(self)
+            }
+        }
+
+        impl<'a> IntoSql<std::borrow::Cow<'a, str>, $d> for &'a String {
+            fn into_sql_type(self) -> std::borrow::Cow<'a, str> {
+                std::borrow::Cow::Borrowed(self)
+            }
+        }
+
+        impl<'a> IntoSql<std::borrow::Cow<'a, str>, $d> for std::borrow::Cow<'a, str> {
+            fn into_sql_type(self) -> std::borrow::Cow<'a, str> {
+                self
+            }
+        }
+
+        impl<'a, T: IntoSql<String, $d>> IntoSql<std::borrow::Cow<'a, str>, $d> for T {
+            fn into_sql_type(self) -> std::borrow::Cow<'a, str> {
+                std::borrow::Cow::Owned(self.into_sql_type())
+            }
+        }
+    };
+}
+
 #[macro_export]
 macro_rules! impl_string_compat {
     ($d:ty, $($t:ty),*) => {
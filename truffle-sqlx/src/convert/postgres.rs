@@ -1,6 +1,49 @@
+use crate::{impl_cow_compat, impl_transparent_compat, impl_upcast_compat};
+
+use truffle::dialect::PostgreSqlDialect;
+
+use super::{FromSql, IntoSql};
+
+impl_transparent_compat!(PostgreSqlDialect, i16, i32, i64, f32, f64, String, bool);
+impl_upcast_compat!(PostgreSqlDialect, i32, u8, i8, u16, i16);
+impl_upcast_compat!(PostgreSqlDialect, i64, u8, i8, u16, i16, u32, i32);
+
+impl_cow_compat!(PostgreSqlDialect);
+
+// Covers both `bytea` (`Vec<u8>`) and arrays.
+impl<T> IntoSql<Vec<T>, PostgreSqlDialect> for Vec<T> {
+    fn into_sql_type(self) -> Vec<T> {
+        self
+    }
+}
+
+impl<T> FromSql<Vec<T>, PostgreSqlDialect> for Vec<T> {
+    fn from_sql_type(value: Vec<T>) -> Self {
+        value
+    }
+}
+
+impl<T: Clone> IntoSql<Vec<T>, PostgreSqlDialect> for &[T] {
+    fn into_sql_type(self) -> Vec<T> {
+        self.to_vec()
+    }
+}
+
 #[cfg(feature = "uuid")]
 impl_transparent_compat!(PostgreSqlDialect, uuid::Uuid);
 
+#[cfg(feature = "decimal")]
+impl_transparent_compat!(PostgreSqlDialect, rust_decimal::Decimal);
+
+#[cfg(feature = "bigdecimal")]
+impl_transparent_compat!(PostgreSqlDialect, bigdecimal::BigDecimal);
+
+#[cfg(feature = "ipnetwork")]
+impl_transparent_compat!(PostgreSqlDialect, ipnetwork::IpNetwork);
+
+#[cfg(feature = "json")]
+impl_transparent_compat!(PostgreSqlDialect, serde_json::Value);
+
 #[cfg(feature = "time")]
 impl_transparent_compat!(
     PostgreSqlDialect,
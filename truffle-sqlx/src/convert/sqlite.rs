@@ -1,4 +1,6 @@
-use crate::{impl_transparent_compat, impl_upcast_compat};
+#[cfg(any(feature = "decimal", feature = "bigdecimal"))]
+use crate::impl_string_compat;
+use crate::{impl_cow_compat, impl_transparent_compat, impl_upcast_compat};
 
 use truffle::dialect::SqliteDialect;
 
@@ -9,7 +11,7 @@ use time::macros::format_description;
 
 use super::{FromSql, IntoSql};
 
-impl_transparent_compat!(SqliteDialect, i16, i32, i64, f32, f64, String);
+impl_transparent_compat!(SqliteDialect, i16, i32, i64, f32, f64, String, Vec<u8>);
 impl_upcast_compat!(SqliteDialect, i32, u8, i8, u16, i16);
 impl_upcast_compat!(SqliteDialect, i64, u8, i8, u16, i16, u32, i32);
 
@@ -25,18 +27,20 @@ impl FromSql<i32, SqliteDialect> for bool {
     }
 }
 
-impl IntoSql<String, SqliteDialect> for &str {
-    fn into_sql_type(self) -> String {
-        self.to_string()
-    }
-}
+impl_cow_compat!(SqliteDialect);
 
-impl IntoSql<String, SqliteDialect> for &String {
-    fn into_sql_type(self) -> String {
-        self.to_string()
+impl IntoSql<Vec<u8>, SqliteDialect> for &[u8] {
+    fn into_sql_type(self) -> Vec<u8> {
+        self.to_vec()
     }
 }
 
+#[cfg(feature = "decimal")]
+impl_string_compat!(SqliteDialect, rust_decimal::Decimal);
+
+#[cfg(feature = "bigdecimal")]
+impl_string_compat!(SqliteDialect, bigdecimal::BigDecimal);
+
 #[cfg(feature = "uuid")]
 impl IntoSql<String, SqliteDialect> for uuid::Uuid {
     fn into_sql_type(self) -> String {